@@ -0,0 +1,3 @@
+CREATE INDEX IF NOT EXISTS idx_users_created_at_id ON users (created_at, id);
+CREATE INDEX IF NOT EXISTS idx_users_full_name_id ON users (full_name, id);
+CREATE INDEX IF NOT EXISTS idx_users_email_id ON users (email, id);
@@ -0,0 +1,17 @@
+CREATE TABLE IF NOT EXISTS export_jobs (
+    id UUID PRIMARY KEY,
+    requested_by UUID REFERENCES users (id) ON DELETE SET NULL,
+    format VARCHAR(10) NOT NULL,
+    filters JSONB NOT NULL,
+    status VARCHAR(20) NOT NULL,
+    processed_rows BIGINT NOT NULL DEFAULT 0,
+    total_rows BIGINT,
+    file_path TEXT,
+    error TEXT,
+    created_at TIMESTAMP NOT NULL,
+    started_at TIMESTAMP,
+    completed_at TIMESTAMP,
+    expires_at TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_export_jobs_expires_at ON export_jobs (expires_at) WHERE file_path IS NOT NULL;
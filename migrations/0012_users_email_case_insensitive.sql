@@ -0,0 +1,8 @@
+-- Unicidade de email sem diferenciar maiúsculas e minúsculas.
+-- Se já existirem contas que diferem apenas na caixa do email, a criação do índice falha
+-- e os registros duplicados precisam ser unificados manualmente antes de rodar a migração.
+CREATE UNIQUE INDEX IF NOT EXISTS idx_users_email_lower ON users (LOWER(email));
+
+ALTER TABLE users DROP CONSTRAINT IF EXISTS users_email_key;
+
+UPDATE users SET email = LOWER(TRIM(email)) WHERE email <> LOWER(TRIM(email));
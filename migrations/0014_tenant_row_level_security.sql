@@ -0,0 +1,41 @@
+-- Isolamento entre organizações aplicado pelo próprio banco (RLS).
+-- A aplicação define `app.current_tenant` por transação; sem ele (autenticação, jobs e administradores
+-- globais sem organização ativa) as políticas não restringem as linhas.
+-- FORCE aplica as políticas também ao dono das tabelas. Superusuários e papéis com BYPASSRLS ignoram RLS,
+-- portanto a aplicação deve se conectar com um papel comum.
+CREATE OR REPLACE FUNCTION app_current_tenant() RETURNS UUID
+    LANGUAGE sql STABLE
+    AS $$ SELECT NULLIF(current_setting('app.current_tenant', true), '')::uuid $$;
+
+ALTER TABLE organizations ENABLE ROW LEVEL SECURITY;
+ALTER TABLE organizations FORCE ROW LEVEL SECURITY;
+DROP POLICY IF EXISTS organizations_tenant_isolation ON organizations;
+CREATE POLICY organizations_tenant_isolation ON organizations
+    USING (app_current_tenant() IS NULL OR id = app_current_tenant());
+
+ALTER TABLE organization_memberships ENABLE ROW LEVEL SECURITY;
+ALTER TABLE organization_memberships FORCE ROW LEVEL SECURITY;
+DROP POLICY IF EXISTS organization_memberships_tenant_isolation ON organization_memberships;
+CREATE POLICY organization_memberships_tenant_isolation ON organization_memberships
+    USING (app_current_tenant() IS NULL OR organization_id = app_current_tenant());
+
+ALTER TABLE organization_invitations ENABLE ROW LEVEL SECURITY;
+ALTER TABLE organization_invitations FORCE ROW LEVEL SECURITY;
+DROP POLICY IF EXISTS organization_invitations_tenant_isolation ON organization_invitations;
+CREATE POLICY organization_invitations_tenant_isolation ON organization_invitations
+    USING (app_current_tenant() IS NULL OR organization_id = app_current_tenant());
+
+-- Usuários pertencem às organizações pelos vínculos; o vínculo não muda numa atualização do usuário,
+-- então a escrita não precisa de verificação adicional
+ALTER TABLE users ENABLE ROW LEVEL SECURITY;
+ALTER TABLE users FORCE ROW LEVEL SECURITY;
+DROP POLICY IF EXISTS users_tenant_isolation ON users;
+CREATE POLICY users_tenant_isolation ON users
+    USING (
+        app_current_tenant() IS NULL
+        OR EXISTS (
+            SELECT 1 FROM organization_memberships m
+            WHERE m.user_id = users.id AND m.organization_id = app_current_tenant()
+        )
+    )
+    WITH CHECK (true);
@@ -0,0 +1,15 @@
+ALTER TABLE users ADD COLUMN IF NOT EXISTS attributes JSONB NOT NULL DEFAULT '{}'::jsonb;
+
+-- Filtro por atributos na listagem (`attributes @> ...`)
+CREATE INDEX IF NOT EXISTS idx_users_attributes ON users USING GIN (attributes jsonb_path_ops);
+
+-- Esquema dos atributos aceitos, mantido pelos administradores
+CREATE TABLE IF NOT EXISTS user_attribute_definitions (
+    key VARCHAR(50) PRIMARY KEY,
+    label VARCHAR(255) NOT NULL,
+    attribute_type VARCHAR(20) NOT NULL,
+    required BOOLEAN NOT NULL DEFAULT FALSE,
+    allowed_values TEXT[],
+    created_at TIMESTAMP NOT NULL,
+    updated_at TIMESTAMP NOT NULL
+);
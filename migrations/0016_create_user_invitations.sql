@@ -0,0 +1,30 @@
+CREATE TABLE IF NOT EXISTS user_invitations (
+    id UUID PRIMARY KEY,
+    email VARCHAR(255) NOT NULL,
+    role VARCHAR(20) NOT NULL DEFAULT 'user',
+    organization_id UUID REFERENCES organizations (id) ON DELETE CASCADE,
+    organization_role VARCHAR(20),
+    invited_by UUID REFERENCES users (id) ON DELETE SET NULL,
+    -- Incrementado a cada reenvio; entra na assinatura do token e invalida os links anteriores
+    send_count INTEGER NOT NULL DEFAULT 1,
+    last_sent_at TIMESTAMP NOT NULL,
+    expires_at TIMESTAMP NOT NULL,
+    accepted_at TIMESTAMP,
+    accepted_user_id UUID REFERENCES users (id) ON DELETE SET NULL,
+    revoked_at TIMESTAMP,
+    created_at TIMESTAMP NOT NULL
+);
+
+-- Um convite pendente por email
+CREATE UNIQUE INDEX IF NOT EXISTS idx_user_invitations_pending_email
+    ON user_invitations (LOWER(email))
+    WHERE accepted_at IS NULL AND revoked_at IS NULL;
+
+CREATE INDEX IF NOT EXISTS idx_user_invitations_created_at ON user_invitations (created_at DESC);
+
+-- Convites feitos dentro de uma organização ficam visíveis apenas nela (veja 0014)
+ALTER TABLE user_invitations ENABLE ROW LEVEL SECURITY;
+ALTER TABLE user_invitations FORCE ROW LEVEL SECURITY;
+DROP POLICY IF EXISTS user_invitations_tenant_isolation ON user_invitations;
+CREATE POLICY user_invitations_tenant_isolation ON user_invitations
+    USING (app_current_tenant() IS NULL OR organization_id = app_current_tenant());
@@ -0,0 +1,2 @@
+-- Marca usuários cujos dados pessoais foram anonimizados a pedido do titular
+ALTER TABLE users ADD COLUMN IF NOT EXISTS erased_at TIMESTAMP;
@@ -0,0 +1,20 @@
+CREATE TABLE IF NOT EXISTS email_changes (
+    id UUID PRIMARY KEY,
+    user_id UUID NOT NULL REFERENCES users (id) ON DELETE CASCADE,
+    old_email VARCHAR(255) NOT NULL,
+    new_email VARCHAR(255) NOT NULL,
+    -- Apenas os hashes: o link de confirmação vai para o novo email e o de reversão para o antigo
+    confirm_token_hash TEXT NOT NULL UNIQUE,
+    revert_token_hash TEXT NOT NULL UNIQUE,
+    confirm_expires_at TIMESTAMP NOT NULL,
+    revert_expires_at TIMESTAMP NOT NULL,
+    confirmed_at TIMESTAMP,
+    reverted_at TIMESTAMP,
+    cancelled_at TIMESTAMP,
+    created_at TIMESTAMP NOT NULL
+);
+
+-- Uma troca pendente por usuário; um novo pedido cancela o anterior
+CREATE UNIQUE INDEX IF NOT EXISTS idx_email_changes_pending_user
+    ON email_changes (user_id)
+    WHERE confirmed_at IS NULL AND reverted_at IS NULL AND cancelled_at IS NULL;
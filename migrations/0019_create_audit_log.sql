@@ -0,0 +1,84 @@
+-- Numerada pelo trigger de encadeamento, não por um DEFAULT
+CREATE SEQUENCE IF NOT EXISTS audit_log_seq;
+
+-- Trilha de auditoria das alterações de usuários. Sem chaves estrangeiras: as entradas sobrevivem
+-- à exclusão definitiva do usuário e à remoção de organizações.
+CREATE TABLE IF NOT EXISTS audit_log (
+    seq BIGINT PRIMARY KEY,
+    id UUID NOT NULL UNIQUE,
+    occurred_at TIMESTAMP NOT NULL,
+    actor_id UUID,
+    organization_id UUID,
+    request_id VARCHAR(128),
+    ip VARCHAR(64),
+    action VARCHAR(64) NOT NULL,
+    resource_type VARCHAR(64) NOT NULL,
+    resource_id UUID,
+    -- Diferença entre antes e depois: {"campo": {"before": ..., "after": ...}}
+    changes JSONB NOT NULL DEFAULT '{}',
+    prev_hash CHAR(64) NOT NULL,
+    hash CHAR(64) NOT NULL UNIQUE
+);
+
+CREATE INDEX IF NOT EXISTS idx_audit_log_resource ON audit_log (resource_type, resource_id, seq);
+CREATE INDEX IF NOT EXISTS idx_audit_log_actor ON audit_log (actor_id, seq);
+CREATE INDEX IF NOT EXISTS idx_audit_log_occurred_at ON audit_log (occurred_at);
+
+-- Forma canônica da entrada: um array JSON não tem ambiguidade entre campos, e o texto de JSONB
+-- e de to_char não depende de configurações da sessão
+CREATE OR REPLACE FUNCTION audit_entry_digest(entry audit_log) RETURNS TEXT
+    LANGUAGE sql STABLE
+    AS $$
+        SELECT encode(sha256(convert_to(jsonb_build_array(
+            entry.prev_hash,
+            entry.id,
+            to_char(entry.occurred_at, 'YYYY-MM-DD"T"HH24:MI:SS.US'),
+            entry.actor_id,
+            entry.organization_id,
+            entry.request_id,
+            entry.ip,
+            entry.action,
+            entry.resource_type,
+            entry.resource_id,
+            entry.changes
+        )::text, 'UTF8')), 'hex')
+    $$;
+
+-- Encadeia cada entrada à anterior. O lock serializa as inserções até o commit, e o `seq` é
+-- sorteado depois dele para que a ordem da sequência seja a mesma da cadeia.
+CREATE OR REPLACE FUNCTION audit_log_chain() RETURNS trigger
+    LANGUAGE plpgsql
+    AS $$
+        BEGIN
+            PERFORM pg_advisory_xact_lock(hashtext('audit_log_chain'));
+            NEW.seq := nextval('audit_log_seq');
+            SELECT hash INTO NEW.prev_hash FROM audit_log ORDER BY seq DESC LIMIT 1;
+            NEW.prev_hash := COALESCE(NEW.prev_hash, repeat('0', 64));
+            NEW.hash := audit_entry_digest(NEW);
+            RETURN NEW;
+        END
+    $$;
+
+DROP TRIGGER IF EXISTS audit_log_chain ON audit_log;
+CREATE TRIGGER audit_log_chain
+    BEFORE INSERT ON audit_log
+    FOR EACH ROW EXECUTE FUNCTION audit_log_chain();
+
+-- Somente inserção. Quem consegue remover os triggers ainda é detectado pela verificação da cadeia.
+CREATE OR REPLACE FUNCTION audit_log_append_only() RETURNS trigger
+    LANGUAGE plpgsql
+    AS $$
+        BEGIN
+            RAISE EXCEPTION 'audit_log is append-only';
+        END
+    $$;
+
+DROP TRIGGER IF EXISTS audit_log_append_only ON audit_log;
+CREATE TRIGGER audit_log_append_only
+    BEFORE UPDATE OR DELETE ON audit_log
+    FOR EACH ROW EXECUTE FUNCTION audit_log_append_only();
+
+DROP TRIGGER IF EXISTS audit_log_no_truncate ON audit_log;
+CREATE TRIGGER audit_log_no_truncate
+    BEFORE TRUNCATE ON audit_log
+    FOR EACH STATEMENT EXECUTE FUNCTION audit_log_append_only();
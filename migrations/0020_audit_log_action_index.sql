@@ -0,0 +1,2 @@
+-- Filtro por tipo de ação da consulta de auditoria, em ordem de `seq` como a paginação
+CREATE INDEX IF NOT EXISTS idx_audit_log_action ON audit_log (action, seq);
@@ -0,0 +1,15 @@
+-- Tentativas de login por senha, com e sem sucesso. `user_id` fica vazio quando o email não existe.
+CREATE TABLE IF NOT EXISTS login_attempts (
+    id UUID PRIMARY KEY,
+    user_id UUID REFERENCES users (id) ON DELETE CASCADE,
+    email VARCHAR(255) NOT NULL,
+    succeeded BOOLEAN NOT NULL,
+    failure_reason VARCHAR(64),
+    ip VARCHAR(64),
+    user_agent VARCHAR(512),
+    request_id VARCHAR(128),
+    created_at TIMESTAMP NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_login_attempts_user ON login_attempts (user_id, created_at DESC, id DESC);
+CREATE INDEX IF NOT EXISTS idx_login_attempts_created_at ON login_attempts (created_at);
@@ -0,0 +1,32 @@
+-- Tokens emitidos até este instante deixam de valer; a redefinição obrigatória bloqueia o login por senha
+ALTER TABLE users ADD COLUMN IF NOT EXISTS sessions_revoked_at TIMESTAMP;
+ALTER TABLE users ADD COLUMN IF NOT EXISTS password_reset_required BOOLEAN NOT NULL DEFAULT FALSE;
+
+-- Dispositivos que já entraram na conta: user agent e rede do IP, identificados pelo hash dos dois
+CREATE TABLE IF NOT EXISTS known_devices (
+    id UUID PRIMARY KEY,
+    user_id UUID NOT NULL REFERENCES users (id) ON DELETE CASCADE,
+    fingerprint CHAR(64) NOT NULL,
+    user_agent VARCHAR(512),
+    ip_network VARCHAR(64) NOT NULL,
+    first_seen_at TIMESTAMP NOT NULL,
+    last_seen_at TIMESTAMP NOT NULL,
+    UNIQUE (user_id, fingerprint)
+);
+
+-- Avisos de login por dispositivo novo; o link "não fui eu" carrega o token, guardado apenas como hash
+CREATE TABLE IF NOT EXISTS device_alerts (
+    id UUID PRIMARY KEY,
+    user_id UUID NOT NULL REFERENCES users (id) ON DELETE CASCADE,
+    -- Vazio depois que o dispositivo é esquecido pela denúncia
+    device_id UUID REFERENCES known_devices (id) ON DELETE SET NULL,
+    token_hash TEXT NOT NULL UNIQUE,
+    ip VARCHAR(64),
+    user_agent VARCHAR(512),
+    expires_at TIMESTAMP NOT NULL,
+    reported_at TIMESTAMP,
+    password_reset_at TIMESTAMP,
+    created_at TIMESTAMP NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_device_alerts_user ON device_alerts (user_id, created_at DESC);
@@ -0,0 +1,12 @@
+-- Contas existentes começam a contar o prazo de troca a partir desta migração
+ALTER TABLE users ADD COLUMN IF NOT EXISTS password_changed_at TIMESTAMP NOT NULL DEFAULT NOW();
+
+-- Hashes das senhas anteriores, para impedir a reutilização; a senha atual fica apenas em `users`
+CREATE TABLE IF NOT EXISTS password_history (
+    id UUID PRIMARY KEY,
+    user_id UUID NOT NULL REFERENCES users (id) ON DELETE CASCADE,
+    password_hash TEXT NOT NULL,
+    created_at TIMESTAMP NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_password_history_user ON password_history (user_id, created_at DESC);
@@ -0,0 +1,54 @@
+-- As políticas de 0014 e 0016 liberavam todas as linhas quando `app.current_tenant` não estava definido.
+-- Agora nada é liberado por padrão: a transação define a organização ativa ou declara explicitamente o
+-- acesso a todas as organizações com `app.bypass_rls` (autenticação, jobs, anonimização e administradores
+-- globais sem organização ativa).
+CREATE OR REPLACE FUNCTION app_bypass_rls() RETURNS BOOLEAN
+    LANGUAGE sql STABLE
+    AS $$ SELECT COALESCE(current_setting('app.bypass_rls', true), '') = 'on' $$;
+
+DROP POLICY IF EXISTS organizations_tenant_isolation ON organizations;
+CREATE POLICY organizations_tenant_isolation ON organizations
+    USING (app_bypass_rls() OR id = app_current_tenant());
+
+DROP POLICY IF EXISTS organization_memberships_tenant_isolation ON organization_memberships;
+CREATE POLICY organization_memberships_tenant_isolation ON organization_memberships
+    USING (app_bypass_rls() OR organization_id = app_current_tenant());
+
+DROP POLICY IF EXISTS organization_invitations_tenant_isolation ON organization_invitations;
+CREATE POLICY organization_invitations_tenant_isolation ON organization_invitations
+    USING (app_bypass_rls() OR organization_id = app_current_tenant());
+
+-- Sem WITH CHECK próprio a escrita usa a mesma condição da leitura: uma alteração dentro da organização
+-- não alcança nem produz usuários fora dela. A criação, que insere o usuário antes do vínculo, declara o bypass.
+DROP POLICY IF EXISTS users_tenant_isolation ON users;
+CREATE POLICY users_tenant_isolation ON users
+    USING (
+        app_bypass_rls()
+        OR EXISTS (
+            SELECT 1 FROM organization_memberships m
+            WHERE m.user_id = users.id AND m.organization_id = app_current_tenant()
+        )
+    );
+
+DROP POLICY IF EXISTS user_invitations_tenant_isolation ON user_invitations;
+CREATE POLICY user_invitations_tenant_isolation ON user_invitations
+    USING (app_bypass_rls() OR organization_id = app_current_tenant());
+
+-- Entradas gravadas fora de uma organização ficam visíveis apenas com o bypass
+ALTER TABLE audit_log ENABLE ROW LEVEL SECURITY;
+ALTER TABLE audit_log FORCE ROW LEVEL SECURITY;
+DROP POLICY IF EXISTS audit_log_tenant_isolation ON audit_log;
+CREATE POLICY audit_log_tenant_isolation ON audit_log
+    USING (app_bypass_rls() OR organization_id = app_current_tenant());
+
+-- O encadeamento precisa da última entrada de toda a trilha, não só da organização ativa
+ALTER FUNCTION audit_log_chain() SET app.bypass_rls = 'on';
+
+-- Exportações passam a registrar a organização em que foram pedidas
+ALTER TABLE export_jobs ADD COLUMN IF NOT EXISTS organization_id UUID REFERENCES organizations (id) ON DELETE SET NULL;
+
+ALTER TABLE export_jobs ENABLE ROW LEVEL SECURITY;
+ALTER TABLE export_jobs FORCE ROW LEVEL SECURITY;
+DROP POLICY IF EXISTS export_jobs_tenant_isolation ON export_jobs;
+CREATE POLICY export_jobs_tenant_isolation ON export_jobs
+    USING (app_bypass_rls() OR organization_id = app_current_tenant());
@@ -0,0 +1,26 @@
+-- Usuários sem organização ativa e que não são administradores globais não declaram mais acesso a todas as
+-- organizações: a transação define apenas `app.current_user`, que libera a própria conta e as entradas da
+-- trilha de auditoria feitas por ela ou sobre ela.
+CREATE OR REPLACE FUNCTION app_current_user() RETURNS UUID
+    LANGUAGE sql STABLE
+    AS $$ SELECT NULLIF(current_setting('app.current_user', true), '')::uuid $$;
+
+DROP POLICY IF EXISTS users_tenant_isolation ON users;
+CREATE POLICY users_tenant_isolation ON users
+    USING (
+        app_bypass_rls()
+        OR users.id = app_current_user()
+        OR EXISTS (
+            SELECT 1 FROM organization_memberships m
+            WHERE m.user_id = users.id AND m.organization_id = app_current_tenant()
+        )
+    );
+
+DROP POLICY IF EXISTS audit_log_tenant_isolation ON audit_log;
+CREATE POLICY audit_log_tenant_isolation ON audit_log
+    USING (
+        app_bypass_rls()
+        OR organization_id = app_current_tenant()
+        OR actor_id = app_current_user()
+        OR (resource_type = 'user' AND resource_id = app_current_user())
+    );
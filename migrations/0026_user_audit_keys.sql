@@ -0,0 +1,8 @@
+-- Chave por usuário para pseudonimizar os dados pessoais gravados na trilha de auditoria (HMAC-SHA256).
+-- A trilha não pode ser reescrita: a anonimização apaga a chave, e o expurgo a remove junto com o usuário,
+-- então os valores deixam de poder ser confirmados. Entradas anteriores mantêm o hash `sha256:` sem chave.
+CREATE TABLE IF NOT EXISTS user_audit_keys (
+    user_id UUID PRIMARY KEY REFERENCES users (id) ON DELETE CASCADE,
+    key BYTEA NOT NULL,
+    created_at TIMESTAMP NOT NULL
+);
@@ -1,2 +1,6 @@
 pub mod password_hasher;
 pub mod token_hasher;
+pub mod passkey_verifier;
+pub mod oidc_provider;
+pub mod file_storage;
+pub mod mailer;
@@ -0,0 +1,196 @@
+use async_trait::async_trait;
+use jsonwebtoken::jwk::JwkSet;
+use jsonwebtoken::{decode, decode_header, DecodingKey, Validation};
+use serde::Deserialize;
+use std::sync::RwLock;
+use std::time::Duration;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum OidcError {
+    #[error("identity provider request failed: {0}")]
+    Http(#[from] reqwest::Error),
+    #[error("identity provider returned an error: {0}")]
+    Provider(String),
+    #[error("invalid id token: {0}")]
+    InvalidIdToken(String),
+}
+
+pub struct OidcIdentity {
+    pub subject: String,
+    pub email: Option<String>,
+    pub email_verified: bool,
+    pub name: Option<String>,
+}
+
+// Interface para o provedor de identidade externo (OpenID Connect)
+#[async_trait]
+pub trait OidcProviderPort: Send + Sync {
+    fn issuer(&self) -> &str;
+    async fn authorization_url(&self, state: &str, nonce: &str, code_challenge: &str) -> Result<String, OidcError>;
+    async fn exchange_code(&self, code: &str, code_verifier: &str) -> Result<String, OidcError>;
+    async fn validate_id_token(&self, id_token: &str, nonce: &str) -> Result<OidcIdentity, OidcError>;
+}
+
+#[derive(Clone, Deserialize)]
+struct ProviderMetadata {
+    issuer: String,
+    authorization_endpoint: String,
+    token_endpoint: String,
+    jwks_uri: String,
+}
+
+#[derive(Deserialize)]
+struct TokenEndpointResponse {
+    id_token: Option<String>,
+    error: Option<String>,
+    error_description: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct IdTokenClaims {
+    sub: String,
+    nonce: Option<String>,
+    email: Option<String>,
+    #[serde(default)]
+    email_verified: bool,
+    name: Option<String>,
+}
+
+// Implementação via discovery (/.well-known/openid-configuration), com metadados e JWKS em cache
+pub struct HttpOidcProvider {
+    issuer: String,
+    client_id: String,
+    client_secret: String,
+    redirect_uri: String,
+    http: reqwest::Client,
+    metadata: RwLock<Option<ProviderMetadata>>,
+    jwks: RwLock<Option<JwkSet>>,
+}
+
+impl HttpOidcProvider {
+    pub fn new(issuer: String, client_id: String, client_secret: String, redirect_uri: String) -> Self {
+        let http = reqwest::Client::builder()
+            .timeout(Duration::from_secs(10))
+            .build()
+            .expect("Failed to create HTTP client");
+
+        Self {
+            issuer: issuer.trim_end_matches('/').to_string(),
+            client_id,
+            client_secret,
+            redirect_uri,
+            http,
+            metadata: RwLock::new(None),
+            jwks: RwLock::new(None),
+        }
+    }
+
+    async fn metadata(&self) -> Result<ProviderMetadata, OidcError> {
+        if let Some(metadata) = self.metadata.read().unwrap().clone() {
+            return Ok(metadata);
+        }
+
+        let url = format!("{}/.well-known/openid-configuration", self.issuer);
+        let metadata: ProviderMetadata = self.http.get(url).send().await?.error_for_status()?.json().await?;
+        if metadata.issuer.trim_end_matches('/') != self.issuer {
+            return Err(OidcError::Provider("discovery document issuer does not match configuration".into()));
+        }
+
+        *self.metadata.write().unwrap() = Some(metadata.clone());
+        Ok(metadata)
+    }
+
+    async fn decoding_key(&self, kid: Option<&str>) -> Result<DecodingKey, OidcError> {
+        let find = |jwks: &JwkSet| match kid {
+            Some(kid) => jwks.find(kid).cloned(),
+            None => jwks.keys.first().cloned(),
+        };
+
+        if let Some(jwk) = self.jwks.read().unwrap().as_ref().and_then(find) {
+            return DecodingKey::from_jwk(&jwk).map_err(|e| OidcError::InvalidIdToken(e.to_string()));
+        }
+
+        // Chave desconhecida: o provedor pode ter rotacionado as chaves, então o JWKS é recarregado
+        let metadata = self.metadata().await?;
+        let jwks: JwkSet = self.http.get(metadata.jwks_uri).send().await?.error_for_status()?.json().await?;
+        let jwk = find(&jwks).ok_or_else(|| OidcError::InvalidIdToken("signing key not found".into()))?;
+        *self.jwks.write().unwrap() = Some(jwks);
+
+        DecodingKey::from_jwk(&jwk).map_err(|e| OidcError::InvalidIdToken(e.to_string()))
+    }
+}
+
+#[async_trait]
+impl OidcProviderPort for HttpOidcProvider {
+    fn issuer(&self) -> &str {
+        &self.issuer
+    }
+
+    async fn authorization_url(&self, state: &str, nonce: &str, code_challenge: &str) -> Result<String, OidcError> {
+        let metadata = self.metadata().await?;
+        let query = serde_urlencoded::to_string([
+            ("response_type", "code"),
+            ("client_id", self.client_id.as_str()),
+            ("redirect_uri", self.redirect_uri.as_str()),
+            ("scope", "openid email profile"),
+            ("state", state),
+            ("nonce", nonce),
+            ("code_challenge", code_challenge),
+            ("code_challenge_method", "S256"),
+        ])
+        .map_err(|e| OidcError::Provider(e.to_string()))?;
+
+        let separator = if metadata.authorization_endpoint.contains('?') { '&' } else { '?' };
+        Ok(format!("{}{}{}", metadata.authorization_endpoint, separator, query))
+    }
+
+    async fn exchange_code(&self, code: &str, code_verifier: &str) -> Result<String, OidcError> {
+        let metadata = self.metadata().await?;
+        let response: TokenEndpointResponse = self.http
+            .post(metadata.token_endpoint)
+            .form(&[
+                ("grant_type", "authorization_code"),
+                ("code", code),
+                ("redirect_uri", self.redirect_uri.as_str()),
+                ("client_id", self.client_id.as_str()),
+                ("client_secret", self.client_secret.as_str()),
+                ("code_verifier", code_verifier),
+            ])
+            .send()
+            .await?
+            .json()
+            .await?;
+
+        match response {
+            TokenEndpointResponse { id_token: Some(id_token), .. } => Ok(id_token),
+            TokenEndpointResponse { error, error_description, .. } => Err(OidcError::Provider(
+                format!("{}: {}", error.unwrap_or_default(), error_description.unwrap_or_default()),
+            )),
+        }
+    }
+
+    async fn validate_id_token(&self, id_token: &str, nonce: &str) -> Result<OidcIdentity, OidcError> {
+        let header = decode_header(id_token).map_err(|e| OidcError::InvalidIdToken(e.to_string()))?;
+        let key = self.decoding_key(header.kid.as_deref()).await?;
+
+        let mut validation = Validation::new(header.alg);
+        validation.set_issuer(&[&self.issuer, &format!("{}/", self.issuer)]);
+        validation.set_audience(&[&self.client_id]);
+
+        let claims = decode::<IdTokenClaims>(id_token, &key, &validation)
+            .map_err(|e| OidcError::InvalidIdToken(e.to_string()))?
+            .claims;
+
+        if claims.nonce.as_deref() != Some(nonce) {
+            return Err(OidcError::InvalidIdToken("nonce mismatch".into()));
+        }
+
+        Ok(OidcIdentity {
+            subject: claims.sub,
+            email: claims.email,
+            email_verified: claims.email_verified,
+            name: claims.name,
+        })
+    }
+}
@@ -0,0 +1,232 @@
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use ciborium::value::Value;
+use ring::digest::{digest, SHA256};
+use ring::rand::{SecureRandom, SystemRandom};
+use ring::signature::{UnparsedPublicKey, ECDSA_P256_SHA256_ASN1};
+use serde::Deserialize;
+use thiserror::Error;
+
+const FLAG_USER_PRESENT: u8 = 0x01;
+const FLAG_USER_VERIFIED: u8 = 0x04;
+const FLAG_ATTESTED_CREDENTIAL_DATA: u8 = 0x40;
+const COSE_ALG_ES256: i64 = -7;
+
+#[derive(Debug, Error)]
+pub enum PasskeyError {
+    #[error("malformed {0}")]
+    Malformed(&'static str),
+    #[error("client data mismatch: {0}")]
+    ClientDataMismatch(&'static str),
+    #[error("relying party id hash mismatch")]
+    RpIdMismatch,
+    #[error("user presence or verification flag missing")]
+    UserNotVerified,
+    #[error("unsupported public key algorithm")]
+    UnsupportedAlgorithm,
+    #[error("invalid signature")]
+    InvalidSignature,
+    #[error("signature counter did not increase, authenticator may be cloned")]
+    CounterRegression,
+    #[error("random generator failure")]
+    Random,
+}
+
+pub struct VerifiedPasskey {
+    pub credential_id: Vec<u8>,
+    pub public_key: Vec<u8>,
+    pub sign_count: u32,
+}
+
+// Interface para as cerimônias WebAuthn (registro e autenticação)
+pub trait PasskeyVerifierPort: Send + Sync {
+    fn generate_challenge(&self) -> Result<String, PasskeyError>;
+    fn verify_registration(
+        &self,
+        expected_challenge: &str,
+        client_data_json: &[u8],
+        attestation_object: &[u8],
+    ) -> Result<VerifiedPasskey, PasskeyError>;
+    fn verify_assertion(
+        &self,
+        expected_challenge: &str,
+        public_key: &[u8],
+        stored_sign_count: u32,
+        client_data_json: &[u8],
+        authenticator_data: &[u8],
+        signature: &[u8],
+    ) -> Result<u32, PasskeyError>;
+}
+
+pub fn encode_base64url(bytes: &[u8]) -> String {
+    URL_SAFE_NO_PAD.encode(bytes)
+}
+
+pub fn decode_base64url(value: &str) -> Result<Vec<u8>, PasskeyError> {
+    URL_SAFE_NO_PAD
+        .decode(value.trim_end_matches('='))
+        .map_err(|_| PasskeyError::Malformed("base64url value"))
+}
+
+#[derive(Deserialize)]
+struct CollectedClientData {
+    #[serde(rename = "type")]
+    kind: String,
+    challenge: String,
+    origin: String,
+}
+
+struct ParsedAuthenticatorData<'a> {
+    flags: u8,
+    sign_count: u32,
+    attested_credential: &'a [u8],
+}
+
+// Implementação ES256 sem verificação de attestation (conveyance "none")
+#[derive(Clone)]
+pub struct WebAuthnVerifier {
+    rp_id: String,
+    origin: String,
+}
+
+impl WebAuthnVerifier {
+    pub fn new(rp_id: String, origin: String) -> Self {
+        Self { rp_id, origin }
+    }
+
+    fn verify_client_data(&self, client_data_json: &[u8], expected_type: &str, expected_challenge: &str) -> Result<(), PasskeyError> {
+        let client_data: CollectedClientData = serde_json::from_slice(client_data_json)
+            .map_err(|_| PasskeyError::Malformed("clientDataJSON"))?;
+
+        if client_data.kind != expected_type {
+            return Err(PasskeyError::ClientDataMismatch("type"));
+        }
+        if client_data.challenge.trim_end_matches('=') != expected_challenge {
+            return Err(PasskeyError::ClientDataMismatch("challenge"));
+        }
+        if client_data.origin != self.origin {
+            return Err(PasskeyError::ClientDataMismatch("origin"));
+        }
+        Ok(())
+    }
+
+    fn parse_authenticator_data<'a>(&self, data: &'a [u8]) -> Result<ParsedAuthenticatorData<'a>, PasskeyError> {
+        if data.len() < 37 {
+            return Err(PasskeyError::Malformed("authenticatorData"));
+        }
+
+        if data[..32] != *digest(&SHA256, self.rp_id.as_bytes()).as_ref() {
+            return Err(PasskeyError::RpIdMismatch);
+        }
+
+        let flags = data[32];
+        if flags & FLAG_USER_PRESENT == 0 || flags & FLAG_USER_VERIFIED == 0 {
+            return Err(PasskeyError::UserNotVerified);
+        }
+
+        let sign_count = u32::from_be_bytes([data[33], data[34], data[35], data[36]]);
+        Ok(ParsedAuthenticatorData { flags, sign_count, attested_credential: &data[37..] })
+    }
+
+    // aaguid (16) | credentialIdLength (2) | credentialId | credentialPublicKey (COSE)
+    fn parse_attested_credential(&self, data: &[u8]) -> Result<(Vec<u8>, Vec<u8>), PasskeyError> {
+        if data.len() < 18 {
+            return Err(PasskeyError::Malformed("attested credential data"));
+        }
+        let id_len = u16::from_be_bytes([data[16], data[17]]) as usize;
+        let id_end = 18 + id_len;
+        if data.len() < id_end {
+            return Err(PasskeyError::Malformed("credential id"));
+        }
+
+        let credential_id = data[18..id_end].to_vec();
+        let cose_key: Value = ciborium::de::from_reader(&data[id_end..])
+            .map_err(|_| PasskeyError::Malformed("credential public key"))?;
+
+        Ok((credential_id, cose_key_to_sec1(&cose_key)?))
+    }
+}
+
+impl PasskeyVerifierPort for WebAuthnVerifier {
+    fn generate_challenge(&self) -> Result<String, PasskeyError> {
+        let mut bytes = [0u8; 32];
+        SystemRandom::new().fill(&mut bytes).map_err(|_| PasskeyError::Random)?;
+        Ok(encode_base64url(&bytes))
+    }
+
+    fn verify_registration(
+        &self,
+        expected_challenge: &str,
+        client_data_json: &[u8],
+        attestation_object: &[u8],
+    ) -> Result<VerifiedPasskey, PasskeyError> {
+        self.verify_client_data(client_data_json, "webauthn.create", expected_challenge)?;
+
+        let attestation: Value = ciborium::de::from_reader(attestation_object)
+            .map_err(|_| PasskeyError::Malformed("attestationObject"))?;
+        let auth_data = map_get(&attestation, &Value::Text("authData".into()))
+            .and_then(|v| v.as_bytes())
+            .ok_or(PasskeyError::Malformed("attestationObject"))?;
+
+        let parsed = self.parse_authenticator_data(auth_data)?;
+        if parsed.flags & FLAG_ATTESTED_CREDENTIAL_DATA == 0 {
+            return Err(PasskeyError::Malformed("attested credential data"));
+        }
+
+        let (credential_id, public_key) = self.parse_attested_credential(parsed.attested_credential)?;
+        Ok(VerifiedPasskey { credential_id, public_key, sign_count: parsed.sign_count })
+    }
+
+    fn verify_assertion(
+        &self,
+        expected_challenge: &str,
+        public_key: &[u8],
+        stored_sign_count: u32,
+        client_data_json: &[u8],
+        authenticator_data: &[u8],
+        signature: &[u8],
+    ) -> Result<u32, PasskeyError> {
+        self.verify_client_data(client_data_json, "webauthn.get", expected_challenge)?;
+        let parsed = self.parse_authenticator_data(authenticator_data)?;
+
+        let mut signed = authenticator_data.to_vec();
+        signed.extend_from_slice(digest(&SHA256, client_data_json).as_ref());
+        UnparsedPublicKey::new(&ECDSA_P256_SHA256_ASN1, public_key)
+            .verify(&signed, signature)
+            .map_err(|_| PasskeyError::InvalidSignature)?;
+
+        // Autenticadores que não implementam contador sempre enviam zero
+        if (parsed.sign_count != 0 || stored_sign_count != 0) && parsed.sign_count <= stored_sign_count {
+            return Err(PasskeyError::CounterRegression);
+        }
+
+        Ok(parsed.sign_count)
+    }
+}
+
+fn map_get<'a>(value: &'a Value, key: &Value) -> Option<&'a Value> {
+    value.as_map()?.iter().find(|(k, _)| k == key).map(|(_, v)| v)
+}
+
+// Converte uma chave COSE EC2/P-256 para o ponto SEC1 não comprimido (0x04 | x | y)
+fn cose_key_to_sec1(cose_key: &Value) -> Result<Vec<u8>, PasskeyError> {
+    let int = |key: i64| map_get(cose_key, &Value::Integer(key.into()));
+
+    let alg = int(3)
+        .and_then(|v| v.as_integer())
+        .and_then(|v| i64::try_from(v).ok());
+    if alg != Some(COSE_ALG_ES256) {
+        return Err(PasskeyError::UnsupportedAlgorithm);
+    }
+
+    let x = int(-2).and_then(|v| v.as_bytes()).ok_or(PasskeyError::Malformed("COSE key"))?;
+    let y = int(-3).and_then(|v| v.as_bytes()).ok_or(PasskeyError::Malformed("COSE key"))?;
+    if x.len() != 32 || y.len() != 32 {
+        return Err(PasskeyError::Malformed("COSE key"));
+    }
+
+    let mut point = Vec::with_capacity(65);
+    point.push(0x04);
+    point.extend_from_slice(x);
+    point.extend_from_slice(y);
+    Ok(point)
+}
@@ -12,6 +12,20 @@ pub trait PasswordEncryptorPort: Send + Sync {
     fn verify_password(&self, hash: &str, password: &str) -> Result<bool, argon2::password_hash::Error>;
 }
 
+// Confere a senha contra vários hashes, ex.: a senha atual e as anteriores do usuário
+pub fn matches_any(
+    encryptor: &dyn PasswordEncryptorPort,
+    password: &str,
+    hashes: &[String],
+) -> Result<bool, argon2::password_hash::Error> {
+    for hash in hashes {
+        if encryptor.verify_password(hash, password)? {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
 // Implementação usando Argon2
 #[derive(Clone)]
 pub struct Argon2PasswordEncryptor;
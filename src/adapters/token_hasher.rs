@@ -1,10 +1,28 @@
-use jsonwebtoken::{encode, EncodingKey, Header, errors::Error as JwtError};
+use jsonwebtoken::{decode, encode, DecodingKey, EncodingKey, Header, Validation, errors::Error as JwtError};
 use std::time::{SystemTime, UNIX_EPOCH};
+use uuid::Uuid;
 use crate::core::entities::auth::ClaimsToUserToken;
 
 
 pub trait TokenGeneratorPort: Send + Sync {
-    fn generate_token(&self, id: String, full_name: String, email: String, secret: &str) -> Result<String, JwtError>;
+    fn generate_token(
+        &self,
+        id: String,
+        full_name: String,
+        email: String,
+        role: String,
+        organization_id: Option<Uuid>,
+        secret: &str,
+    ) -> Result<String, JwtError>;
+    fn generate_client_token(&self, claims: &ClaimsToUserToken, secret: &str) -> Result<String, JwtError>;
+    fn decode_token(&self, token: &str, secret: &str) -> Result<ClaimsToUserToken, JwtError>;
+}
+
+pub fn unix_timestamp() -> usize {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs() as usize
 }
 
 #[derive(Clone)]
@@ -23,19 +41,40 @@ impl JwtTokenGenerator {
 }
 
 impl TokenGeneratorPort for JwtTokenGenerator {
-    fn generate_token(&self, id: String, full_name: String, email: String, secret: &str) -> Result<String, JwtError> {
-        let expiration: usize = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .unwrap()
-            .as_secs() as usize + 24 * 3600;
+    fn generate_token(
+        &self,
+        id: String,
+        full_name: String,
+        email: String,
+        role: String,
+        organization_id: Option<Uuid>,
+        secret: &str,
+    ) -> Result<String, JwtError> {
+        let issued_at = unix_timestamp();
+        let expiration: usize = issued_at + 24 * 3600;
 
         let claims = ClaimsToUserToken {
             id,
             exp: expiration,
+            iat: issued_at,
             full_name,
             email,
+            role,
+            client_id: None,
+            scope: None,
+            organization_id,
+            password_expired: false,
         };
 
-        encode(&Header::default(), &claims, &EncodingKey::from_secret(secret.as_bytes()))
+        self.generate_client_token(&claims, secret)
+    }
+
+    fn generate_client_token(&self, claims: &ClaimsToUserToken, secret: &str) -> Result<String, JwtError> {
+        encode(&Header::default(), claims, &EncodingKey::from_secret(secret.as_bytes()))
+    }
+
+    fn decode_token(&self, token: &str, secret: &str) -> Result<ClaimsToUserToken, JwtError> {
+        decode::<ClaimsToUserToken>(token, &DecodingKey::from_secret(secret.as_bytes()), &Validation::default())
+            .map(|data| data.claims)
     }
 }
@@ -6,6 +6,37 @@ pub struct Config {
     pub server_addr: String,
     pub jwt_secret: String,
     pub api_key: String,
+    pub webauthn_rp_id: String,
+    pub webauthn_rp_name: String,
+    pub webauthn_origin: String,
+    pub oidc_issuer: Option<String>,
+    pub oidc_client_id: String,
+    pub oidc_client_secret: String,
+    pub oidc_redirect_uri: String,
+    pub user_purge_retention_days: i64,
+    pub user_purge_interval_seconds: u64,
+    pub require_if_match: bool,
+    pub export_storage_dir: String,
+    pub export_retention_hours: i64,
+    pub export_cleanup_interval_seconds: u64,
+    pub avatar_storage: String,
+    pub avatar_storage_dir: String,
+    pub avatar_max_bytes: usize,
+    pub mailer: String,
+    pub smtp_url: String,
+    pub mail_from: String,
+    pub invitation_ttl_hours: i64,
+    pub invitation_accept_url: String,
+    pub email_change_ttl_hours: i64,
+    pub email_change_revert_days: i64,
+    pub email_change_confirm_url: String,
+    pub email_change_revert_url: String,
+    pub login_attempt_retention_days: i64,
+    pub login_attempt_purge_interval_seconds: u64,
+    pub device_alert_ttl_hours: i64,
+    pub device_alert_report_url: String,
+    pub password_history_size: i64,
+    pub password_max_age_days: i64,
 }
 
 impl Config {
@@ -15,6 +46,82 @@ impl Config {
             server_addr: env::var("SERVER_ADDR").expect("SERVER_ADDR must be set"),
             jwt_secret: env::var("JWT_SECRET").expect("JWT_SECRET must be set"),
             api_key: env::var("API_KEY").expect("API_KEY must be set"),
+            webauthn_rp_id: env::var("WEBAUTHN_RP_ID").unwrap_or_else(|_| "localhost".to_string()),
+            webauthn_rp_name: env::var("WEBAUTHN_RP_NAME").unwrap_or_else(|_| "Rust API".to_string()),
+            webauthn_origin: env::var("WEBAUTHN_ORIGIN").unwrap_or_else(|_| "http://localhost:8080".to_string()),
+            oidc_issuer: env::var("OIDC_ISSUER").ok(),
+            oidc_client_id: env::var("OIDC_CLIENT_ID").unwrap_or_default(),
+            oidc_client_secret: env::var("OIDC_CLIENT_SECRET").unwrap_or_default(),
+            oidc_redirect_uri: env::var("OIDC_REDIRECT_URI").unwrap_or_default(),
+            user_purge_retention_days: env::var("USER_PURGE_RETENTION_DAYS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(30),
+            user_purge_interval_seconds: env::var("USER_PURGE_INTERVAL_SECONDS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(3600),
+            require_if_match: env::var("REQUIRE_IF_MATCH")
+                .map(|v| v == "true")
+                .unwrap_or(false),
+            export_storage_dir: env::var("EXPORT_STORAGE_DIR").unwrap_or_else(|_| "storage/exports".to_string()),
+            export_retention_hours: env::var("EXPORT_RETENTION_HOURS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(24),
+            export_cleanup_interval_seconds: env::var("EXPORT_CLEANUP_INTERVAL_SECONDS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(600),
+            avatar_storage: env::var("AVATAR_STORAGE").unwrap_or_else(|_| "local".to_string()),
+            avatar_storage_dir: env::var("AVATAR_STORAGE_DIR").unwrap_or_else(|_| "storage/avatars".to_string()),
+            avatar_max_bytes: env::var("AVATAR_MAX_BYTES")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(5 * 1024 * 1024),
+            mailer: env::var("MAILER").unwrap_or_else(|_| "log".to_string()),
+            smtp_url: env::var("SMTP_URL").unwrap_or_default(),
+            mail_from: env::var("MAIL_FROM").unwrap_or_else(|_| "no-reply@localhost".to_string()),
+            invitation_ttl_hours: env::var("INVITATION_TTL_HOURS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(72),
+            invitation_accept_url: env::var("INVITATION_ACCEPT_URL")
+                .unwrap_or_else(|_| "http://localhost:3000/invitations/accept".to_string()),
+            email_change_ttl_hours: env::var("EMAIL_CHANGE_TTL_HOURS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(24),
+            email_change_revert_days: env::var("EMAIL_CHANGE_REVERT_DAYS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(7),
+            email_change_confirm_url: env::var("EMAIL_CHANGE_CONFIRM_URL")
+                .unwrap_or_else(|_| "http://localhost:3000/email-change/confirm".to_string()),
+            email_change_revert_url: env::var("EMAIL_CHANGE_REVERT_URL")
+                .unwrap_or_else(|_| "http://localhost:3000/email-change/revert".to_string()),
+            login_attempt_retention_days: env::var("LOGIN_ATTEMPT_RETENTION_DAYS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(90),
+            login_attempt_purge_interval_seconds: env::var("LOGIN_ATTEMPT_PURGE_INTERVAL_SECONDS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(3600),
+            device_alert_ttl_hours: env::var("DEVICE_ALERT_TTL_HOURS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(72),
+            device_alert_report_url: env::var("DEVICE_ALERT_REPORT_URL")
+                .unwrap_or_else(|_| "http://localhost:3000/security/not-me".to_string()),
+            password_history_size: env::var("PASSWORD_HISTORY_SIZE")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(5),
+            password_max_age_days: env::var("PASSWORD_MAX_AGE_DAYS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(90),
         }
     }
 }
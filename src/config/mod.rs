@@ -1,3 +1,5 @@
 pub mod database;
 pub mod config_env;
 pub mod querys;
+pub mod tenant;
+pub mod request_context;
@@ -0,0 +1,32 @@
+pub struct AuditQueries;
+
+impl AuditQueries {
+    // `seq`, `prev_hash` e `hash` são definidos pelo trigger que encadeia as entradas
+    pub const CREATE_ENTRY: &'static str = r#"
+        INSERT INTO audit_log (id, occurred_at, actor_id, organization_id, request_id, ip, action, resource_type, resource_id, changes, prev_hash, hash)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, '', '')
+    "#;
+
+    // Cria a chave do titular no primeiro uso; o update vazio faz o RETURNING devolver a chave existente
+    pub const FIND_OR_CREATE_SUBJECT_KEY: &'static str = r#"
+        INSERT INTO user_audit_keys (user_id, key, created_at)
+        VALUES ($1, $2, $3)
+        ON CONFLICT (user_id) DO UPDATE SET user_id = EXCLUDED.user_id
+        RETURNING key
+    "#;
+
+    pub const FIND_CHAIN_LINKS: &'static str = r#"
+        SELECT seq, prev_hash, hash, audit_entry_digest(a) AS digest
+        FROM audit_log a
+        WHERE seq > $1
+        ORDER BY seq ASC
+        LIMIT $2
+    "#;
+
+    // Os filtros são acrescentados pelo repositório
+    pub const FIND_ENTRIES: &'static str = r#"
+        SELECT seq, id, occurred_at, actor_id, organization_id, request_id, ip, action, resource_type, resource_id, changes, prev_hash, hash
+        FROM audit_log
+        WHERE TRUE
+    "#;
+}
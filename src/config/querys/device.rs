@@ -0,0 +1,64 @@
+pub struct DeviceQueries;
+
+impl DeviceQueries {
+    // `xmax = 0` só na linha recém-inserida; num conflito a linha existente é atualizada
+    pub const UPSERT_DEVICE: &'static str = r#"
+        INSERT INTO known_devices (id, user_id, fingerprint, user_agent, ip_network, first_seen_at, last_seen_at)
+        VALUES ($1, $2, $3, $4, $5, $6, $6)
+        ON CONFLICT (user_id, fingerprint) DO UPDATE SET last_seen_at = EXCLUDED.last_seen_at
+        RETURNING id, user_id, fingerprint, user_agent, ip_network, first_seen_at, last_seen_at, (xmax = 0) AS inserted
+    "#;
+
+    pub const HAS_OTHER_DEVICES: &'static str = r#"
+        SELECT EXISTS(SELECT 1 FROM known_devices WHERE user_id = $1 AND id <> $2)
+    "#;
+
+    pub const CREATE_ALERT: &'static str = r#"
+        INSERT INTO device_alerts (id, user_id, device_id, token_hash, ip, user_agent, expires_at, created_at)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+        RETURNING id, user_id, device_id, ip, user_agent, expires_at, reported_at, password_reset_at, created_at
+    "#;
+
+    pub const FIND_ALERT_BY_TOKEN: &'static str = r#"
+        SELECT id, user_id, device_id, ip, user_agent, expires_at, reported_at, password_reset_at, created_at
+        FROM device_alerts
+        WHERE token_hash = $1
+    "#;
+
+    pub const MARK_ALERT_REPORTED: &'static str = r#"
+        UPDATE device_alerts SET reported_at = $2 WHERE id = $1 AND reported_at IS NULL
+    "#;
+
+    pub const FORGET_DEVICE: &'static str = r#"
+        DELETE FROM known_devices WHERE id = $1
+    "#;
+
+    pub const REVOKE_USER_SESSIONS: &'static str = r#"
+        UPDATE users
+        SET sessions_revoked_at = $2, password_reset_required = TRUE, updated_at = $2, version = version + 1
+        WHERE id = $1 AND deleted_at IS NULL
+    "#;
+
+    pub const REVOKE_REFRESH_TOKENS: &'static str = r#"
+        UPDATE oauth_refresh_tokens SET revoked_at = $2 WHERE user_id = $1 AND revoked_at IS NULL
+    "#;
+
+    // Credenciais criadas a partir do login denunciado podem ter sido cadastradas por terceiros
+    pub const DELETE_PASSKEYS_SINCE: &'static str = r#"
+        DELETE FROM webauthn_credentials WHERE user_id = $1 AND created_at >= $2
+    "#;
+
+    pub const DELETE_IDENTITIES_SINCE: &'static str = r#"
+        DELETE FROM user_identities WHERE user_id = $1 AND created_at >= $2
+    "#;
+
+    pub const MARK_ALERT_PASSWORD_RESET: &'static str = r#"
+        UPDATE device_alerts SET password_reset_at = $2 WHERE id = $1 AND reported_at IS NOT NULL AND password_reset_at IS NULL
+    "#;
+
+    pub const RESET_PASSWORD: &'static str = r#"
+        UPDATE users
+        SET password = $2, password_reset_required = FALSE, password_changed_at = $3, updated_at = $3, version = version + 1
+        WHERE id = $1 AND deleted_at IS NULL
+    "#;
+}
@@ -0,0 +1,47 @@
+pub struct EmailChangeQueries;
+
+impl EmailChangeQueries {
+    pub const CANCEL_PENDING_FOR_USER: &'static str = r#"
+        UPDATE email_changes
+        SET cancelled_at = $2
+        WHERE user_id = $1 AND confirmed_at IS NULL AND reverted_at IS NULL AND cancelled_at IS NULL
+    "#;
+
+    pub const CREATE_EMAIL_CHANGE: &'static str = r#"
+        INSERT INTO email_changes (id, user_id, old_email, new_email, confirm_token_hash, revert_token_hash, confirm_expires_at, revert_expires_at, created_at)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+        RETURNING id, user_id, old_email, new_email, confirm_expires_at, revert_expires_at, confirmed_at, reverted_at, cancelled_at, created_at
+    "#;
+
+    pub const FIND_BY_CONFIRM_TOKEN: &'static str = r#"
+        SELECT id, user_id, old_email, new_email, confirm_expires_at, revert_expires_at, confirmed_at, reverted_at, cancelled_at, created_at
+        FROM email_changes
+        WHERE confirm_token_hash = $1
+    "#;
+
+    pub const FIND_BY_REVERT_TOKEN: &'static str = r#"
+        SELECT id, user_id, old_email, new_email, confirm_expires_at, revert_expires_at, confirmed_at, reverted_at, cancelled_at, created_at
+        FROM email_changes
+        WHERE revert_token_hash = $1
+    "#;
+
+    pub const CONFIRM_EMAIL_CHANGE: &'static str = r#"
+        UPDATE email_changes
+        SET confirmed_at = $2
+        WHERE id = $1 AND confirmed_at IS NULL AND reverted_at IS NULL AND cancelled_at IS NULL AND confirm_expires_at > $2
+    "#;
+
+    pub const REVERT_EMAIL_CHANGE: &'static str = r#"
+        UPDATE email_changes
+        SET reverted_at = $2
+        WHERE id = $1 AND reverted_at IS NULL AND cancelled_at IS NULL AND revert_expires_at > $2
+        RETURNING confirmed_at
+    "#;
+
+    // Só troca se o email atual ainda for o esperado, para não sobrescrever outra alteração
+    pub const SWAP_USER_EMAIL: &'static str = r#"
+        UPDATE users
+        SET email = $3, updated_at = $4, version = version + 1
+        WHERE id = $1 AND LOWER(email) = LOWER($2) AND deleted_at IS NULL
+    "#;
+}
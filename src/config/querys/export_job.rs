@@ -0,0 +1,59 @@
+pub struct ExportJobQueries;
+
+impl ExportJobQueries {
+    pub const CREATE_JOB: &'static str = r#"
+        INSERT INTO export_jobs (id, requested_by, organization_id, format, filters, status, created_at)
+        VALUES ($1, $2, $3, $4, $5, 'pending', $6)
+        RETURNING id, requested_by, format, filters, status, processed_rows, total_rows, file_path, error,
+                  created_at, started_at, completed_at, expires_at
+    "#;
+
+    pub const FIND_BY_ID: &'static str = r#"
+        SELECT id, requested_by, format, filters, status, processed_rows, total_rows, file_path, error,
+               created_at, started_at, completed_at, expires_at
+        FROM export_jobs
+        WHERE id = $1
+    "#;
+
+    pub const START_JOB: &'static str = r#"
+        UPDATE export_jobs
+        SET status = 'running', total_rows = $2, started_at = $3
+        WHERE id = $1
+    "#;
+
+    pub const UPDATE_PROGRESS: &'static str = r#"
+        UPDATE export_jobs SET processed_rows = $2 WHERE id = $1
+    "#;
+
+    pub const COMPLETE_JOB: &'static str = r#"
+        UPDATE export_jobs
+        SET status = 'completed', processed_rows = $2, file_path = $3, completed_at = $4, expires_at = $5
+        WHERE id = $1
+    "#;
+
+    pub const FAIL_JOB: &'static str = r#"
+        UPDATE export_jobs
+        SET status = 'failed', error = $2, completed_at = $3
+        WHERE id = $1
+    "#;
+
+    // Jobs em andamento quando o servidor parou não serão retomados
+    pub const FAIL_INTERRUPTED_JOBS: &'static str = r#"
+        UPDATE export_jobs
+        SET status = 'failed', error = 'Export interrupted by a server restart', completed_at = $1
+        WHERE status IN ('pending', 'running')
+        RETURNING id, requested_by, format, filters, status, processed_rows, total_rows, file_path, error,
+                  created_at, started_at, completed_at, expires_at
+    "#;
+
+    pub const FIND_EXPIRED_JOBS: &'static str = r#"
+        SELECT id, requested_by, format, filters, status, processed_rows, total_rows, file_path, error,
+               created_at, started_at, completed_at, expires_at
+        FROM export_jobs
+        WHERE file_path IS NOT NULL AND expires_at < $1
+    "#;
+
+    pub const EXPIRE_JOB: &'static str = r#"
+        UPDATE export_jobs SET status = 'expired', file_path = NULL WHERE id = $1
+    "#;
+}
@@ -0,0 +1,41 @@
+pub struct IdentityQueries;
+
+impl IdentityQueries {
+    pub const CREATE_IDENTITY: &'static str = r#"
+        INSERT INTO user_identities (id, user_id, issuer, subject, email, created_at)
+        VALUES ($1, $2, $3, $4, $5, $6)
+        RETURNING id, user_id, issuer, subject, email, created_at
+    "#;
+
+    pub const FIND_BY_SUBJECT: &'static str = r#"
+        SELECT id, user_id, issuer, subject, email, created_at
+        FROM user_identities
+        WHERE issuer = $1 AND subject = $2
+    "#;
+
+    pub const FIND_BY_USER: &'static str = r#"
+        SELECT id, user_id, issuer, subject, email, created_at
+        FROM user_identities
+        WHERE user_id = $1
+        ORDER BY created_at DESC
+    "#;
+
+    pub const DELETE_IDENTITY: &'static str = r#"
+        DELETE FROM user_identities WHERE id = $1 AND user_id = $2
+    "#;
+
+    pub const CREATE_LOGIN_STATE: &'static str = r#"
+        INSERT INTO oidc_login_states (state_hash, nonce, code_verifier, link_user_id, expires_at)
+        VALUES ($1, $2, $3, $4, $5)
+    "#;
+
+    pub const TAKE_LOGIN_STATE: &'static str = r#"
+        DELETE FROM oidc_login_states
+        WHERE state_hash = $1 AND expires_at > $2
+        RETURNING state_hash, nonce, code_verifier, link_user_id, expires_at
+    "#;
+
+    pub const DELETE_EXPIRED_LOGIN_STATES: &'static str = r#"
+        DELETE FROM oidc_login_states WHERE expires_at <= $1
+    "#;
+}
@@ -0,0 +1,21 @@
+pub struct LoginAttemptQueries;
+
+impl LoginAttemptQueries {
+    pub const CREATE: &'static str = r#"
+        INSERT INTO login_attempts (id, user_id, email, succeeded, failure_reason, ip, user_agent, request_id, created_at)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+    "#;
+
+    // Mais recentes primeiro; o cursor é a última tentativa entregue
+    pub const FIND_BY_USER: &'static str = r#"
+        SELECT id, user_id, email, succeeded, failure_reason, ip, user_agent, request_id, created_at
+        FROM login_attempts
+        WHERE user_id = $1 AND ($2::timestamp IS NULL OR (created_at, id) < ($2, $3))
+        ORDER BY created_at DESC, id DESC
+        LIMIT $4
+    "#;
+
+    pub const DELETE_OLDER_THAN: &'static str = r#"
+        DELETE FROM login_attempts WHERE created_at < $1
+    "#;
+}
@@ -1 +1,14 @@
 pub mod user;
+pub mod passkey;
+pub mod oauth;
+pub mod identity;
+pub mod export_job;
+pub mod organization;
+pub mod tenant;
+pub mod user_attribute;
+pub mod user_invitation;
+pub mod privacy;
+pub mod email_change;
+pub mod audit;
+pub mod login_attempt;
+pub mod device;
@@ -0,0 +1,54 @@
+pub struct OAuthQueries;
+
+impl OAuthQueries {
+    pub const CREATE_CLIENT: &'static str = r#"
+        INSERT INTO oauth_clients (id, client_id, client_secret_hash, name, redirect_uris, owner_id, created_at)
+        VALUES ($1, $2, $3, $4, $5, $6, $7)
+        RETURNING id, client_id, client_secret_hash, name, redirect_uris, owner_id, created_at
+    "#;
+
+    pub const FIND_CLIENT_BY_CLIENT_ID: &'static str = r#"
+        SELECT id, client_id, client_secret_hash, name, redirect_uris, owner_id, created_at
+        FROM oauth_clients
+        WHERE client_id = $1
+    "#;
+
+    pub const FIND_CLIENTS_BY_OWNER: &'static str = r#"
+        SELECT id, client_id, client_secret_hash, name, redirect_uris, owner_id, created_at
+        FROM oauth_clients
+        WHERE owner_id = $1
+        ORDER BY created_at DESC
+    "#;
+
+    pub const DELETE_CLIENT: &'static str = r#"
+        DELETE FROM oauth_clients WHERE id = $1 AND owner_id = $2
+    "#;
+
+    pub const CREATE_AUTHORIZATION_CODE: &'static str = r#"
+        INSERT INTO oauth_authorization_codes (code_hash, client_id, user_id, redirect_uri, scope, code_challenge, expires_at)
+        VALUES ($1, $2, $3, $4, $5, $6, $7)
+    "#;
+
+    pub const TAKE_AUTHORIZATION_CODE: &'static str = r#"
+        DELETE FROM oauth_authorization_codes
+        WHERE code_hash = $1 AND expires_at > $2
+        RETURNING code_hash, client_id, user_id, redirect_uri, scope, code_challenge, expires_at
+    "#;
+
+    pub const CREATE_REFRESH_TOKEN: &'static str = r#"
+        INSERT INTO oauth_refresh_tokens (token_hash, client_id, user_id, scope, expires_at, created_at)
+        VALUES ($1, $2, $3, $4, $5, $6)
+    "#;
+
+    pub const FIND_REFRESH_TOKEN: &'static str = r#"
+        SELECT token_hash, client_id, user_id, scope, expires_at, revoked_at, created_at
+        FROM oauth_refresh_tokens
+        WHERE token_hash = $1
+    "#;
+
+    pub const REVOKE_REFRESH_TOKEN: &'static str = r#"
+        UPDATE oauth_refresh_tokens
+        SET revoked_at = $3
+        WHERE token_hash = $1 AND client_id = $2 AND revoked_at IS NULL
+    "#;
+}
@@ -0,0 +1,117 @@
+pub struct OrganizationQueries;
+
+impl OrganizationQueries {
+    pub const CREATE_ORGANIZATION: &'static str = r#"
+        INSERT INTO organizations (id, name, slug, created_at, updated_at)
+        VALUES ($1, $2, $3, $4, $4)
+        RETURNING id, name, slug, created_at, updated_at
+    "#;
+
+    pub const FIND_BY_ID: &'static str = r#"
+        SELECT id, name, slug, created_at, updated_at
+        FROM organizations
+        WHERE id = $1
+    "#;
+
+    pub const FIND_BY_USER: &'static str = r#"
+        SELECT o.id, o.name, o.slug, m.role, o.created_at, o.updated_at
+        FROM organizations o
+        JOIN organization_memberships m ON m.organization_id = o.id
+        WHERE m.user_id = $1
+        ORDER BY m.created_at ASC, o.id ASC
+    "#;
+
+    pub const UPDATE_ORGANIZATION: &'static str = r#"
+        UPDATE organizations
+        SET name = $2, updated_at = $3
+        WHERE id = $1
+        RETURNING id, name, slug, created_at, updated_at
+    "#;
+
+    pub const DELETE_ORGANIZATION: &'static str = r#"
+        DELETE FROM organizations WHERE id = $1
+    "#;
+
+    pub const CREATE_MEMBERSHIP: &'static str = r#"
+        INSERT INTO organization_memberships (organization_id, user_id, role, created_at)
+        VALUES ($1, $2, $3, $4)
+        RETURNING organization_id, user_id, role, created_at
+    "#;
+
+    pub const FIND_MEMBERSHIP: &'static str = r#"
+        SELECT organization_id, user_id, role, created_at
+        FROM organization_memberships
+        WHERE organization_id = $1 AND user_id = $2
+    "#;
+
+    // Organização padrão do usuário: a primeira em que entrou
+    pub const FIND_DEFAULT_ORGANIZATION: &'static str = r#"
+        SELECT organization_id
+        FROM organization_memberships
+        WHERE user_id = $1
+        ORDER BY created_at ASC, organization_id ASC
+        LIMIT 1
+    "#;
+
+    pub const FIND_MEMBERS: &'static str = r#"
+        SELECT u.id AS user_id, u.full_name, u.email, m.role, m.created_at
+        FROM organization_memberships m
+        JOIN users u ON u.id = m.user_id
+        WHERE m.organization_id = $1 AND u.deleted_at IS NULL
+        ORDER BY m.created_at ASC, u.id ASC
+    "#;
+
+    pub const UPDATE_MEMBERSHIP_ROLE: &'static str = r#"
+        UPDATE organization_memberships
+        SET role = $3
+        WHERE organization_id = $1 AND user_id = $2
+        RETURNING organization_id, user_id, role, created_at
+    "#;
+
+    pub const DELETE_MEMBERSHIP: &'static str = r#"
+        DELETE FROM organization_memberships WHERE organization_id = $1 AND user_id = $2
+    "#;
+
+    // Trava as linhas de proprietários para que duas remoções simultâneas não deixem a organização sem dono
+    pub const LOCK_OWNERS: &'static str = r#"
+        SELECT user_id FROM organization_memberships
+        WHERE organization_id = $1 AND role = 'owner'
+        FOR UPDATE
+    "#;
+
+    pub const CREATE_INVITATION: &'static str = r#"
+        INSERT INTO organization_invitations (id, organization_id, email, role, token_hash, invited_by, expires_at, created_at)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+        RETURNING id, organization_id, email, role, token_hash, invited_by, expires_at, accepted_at, created_at
+    "#;
+
+    // Convites vencidos e não aceitos liberam o email para um novo convite
+    pub const DELETE_EXPIRED_INVITATION: &'static str = r#"
+        DELETE FROM organization_invitations
+        WHERE organization_id = $1 AND LOWER(email) = LOWER($2) AND accepted_at IS NULL AND expires_at < $3
+    "#;
+
+    pub const FIND_PENDING_INVITATIONS: &'static str = r#"
+        SELECT id, organization_id, email, role, token_hash, invited_by, expires_at, accepted_at, created_at
+        FROM organization_invitations
+        WHERE organization_id = $1 AND accepted_at IS NULL
+        ORDER BY created_at DESC
+    "#;
+
+    pub const FIND_INVITATION_BY_TOKEN: &'static str = r#"
+        SELECT id, organization_id, email, role, token_hash, invited_by, expires_at, accepted_at, created_at
+        FROM organization_invitations
+        WHERE token_hash = $1
+    "#;
+
+    pub const ACCEPT_INVITATION: &'static str = r#"
+        UPDATE organization_invitations
+        SET accepted_at = $2
+        WHERE id = $1 AND accepted_at IS NULL
+    "#;
+
+    pub const DELETE_INVITATION: &'static str = r#"
+        DELETE FROM organization_invitations
+        WHERE id = $1 AND organization_id = $2 AND accepted_at IS NULL
+    "#;
+}
@@ -0,0 +1,48 @@
+pub struct PasskeyQueries;
+
+impl PasskeyQueries {
+    pub const CREATE_CHALLENGE: &'static str = r#"
+        INSERT INTO webauthn_challenges (id, user_id, challenge, ceremony, expires_at)
+        VALUES ($1, $2, $3, $4, $5)
+        RETURNING id, user_id, challenge, ceremony, expires_at
+    "#;
+
+    pub const TAKE_CHALLENGE: &'static str = r#"
+        DELETE FROM webauthn_challenges
+        WHERE id = $1 AND ceremony = $2 AND expires_at > $3
+        RETURNING id, user_id, challenge, ceremony, expires_at
+    "#;
+
+    pub const DELETE_EXPIRED_CHALLENGES: &'static str = r#"
+        DELETE FROM webauthn_challenges WHERE expires_at <= $1
+    "#;
+
+    pub const CREATE_CREDENTIAL: &'static str = r#"
+        INSERT INTO webauthn_credentials (id, user_id, credential_id, public_key, sign_count, transports, created_at)
+        VALUES ($1, $2, $3, $4, $5, $6, $7)
+        RETURNING id, user_id, credential_id, public_key, sign_count, transports, created_at, last_used_at
+    "#;
+
+    pub const FIND_BY_USER: &'static str = r#"
+        SELECT id, user_id, credential_id, public_key, sign_count, transports, created_at, last_used_at
+        FROM webauthn_credentials
+        WHERE user_id = $1
+        ORDER BY created_at DESC
+    "#;
+
+    pub const FIND_BY_CREDENTIAL_ID: &'static str = r#"
+        SELECT id, user_id, credential_id, public_key, sign_count, transports, created_at, last_used_at
+        FROM webauthn_credentials
+        WHERE credential_id = $1
+    "#;
+
+    pub const UPDATE_SIGN_COUNT: &'static str = r#"
+        UPDATE webauthn_credentials
+        SET sign_count = $2, last_used_at = $3
+        WHERE id = $1
+    "#;
+
+    pub const DELETE_CREDENTIAL: &'static str = r#"
+        DELETE FROM webauthn_credentials WHERE id = $1 AND user_id = $2
+    "#;
+}
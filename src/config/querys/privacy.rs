@@ -0,0 +1,176 @@
+pub struct PrivacyQueries;
+
+impl PrivacyQueries {
+    pub const FIND_PROFILE: &'static str = r#"
+        SELECT id, full_name, email, role, status, attributes, avatar_updated_at, created_at, updated_at, deleted_at, erased_at
+        FROM users
+        WHERE id = $1
+    "#;
+
+    pub const FIND_SESSIONS: &'static str = r#"
+        SELECT t.client_id, c.name AS client_name, t.scope, t.created_at, t.expires_at, t.revoked_at
+        FROM oauth_refresh_tokens t
+        JOIN oauth_clients c ON c.client_id = t.client_id
+        WHERE t.user_id = $1
+        ORDER BY t.created_at DESC
+    "#;
+
+    pub const FIND_CONSENTS: &'static str = r#"
+        SELECT t.client_id, c.name AS client_name,
+               ARRAY_REMOVE(ARRAY_AGG(DISTINCT s.scope), NULL) AS scopes,
+               MIN(t.created_at) AS first_granted_at,
+               MAX(t.created_at) AS last_granted_at,
+               BOOL_OR(t.revoked_at IS NULL AND t.expires_at > $2) AS active
+        FROM oauth_refresh_tokens t
+        JOIN oauth_clients c ON c.client_id = t.client_id
+        LEFT JOIN LATERAL UNNEST(STRING_TO_ARRAY(t.scope, ' ')) AS s(scope) ON TRUE
+        WHERE t.user_id = $1
+        GROUP BY t.client_id, c.name
+        ORDER BY MIN(t.created_at) ASC
+    "#;
+
+    pub const FIND_PASSKEYS: &'static str = r#"
+        SELECT id, transports, created_at, last_used_at
+        FROM webauthn_credentials
+        WHERE user_id = $1
+        ORDER BY created_at ASC
+    "#;
+
+    pub const FIND_IDENTITIES: &'static str = r#"
+        SELECT issuer, subject, email, created_at
+        FROM user_identities
+        WHERE user_id = $1
+        ORDER BY created_at ASC
+    "#;
+
+    pub const FIND_OAUTH_CLIENTS: &'static str = r#"
+        SELECT client_id, name, redirect_uris, created_at
+        FROM oauth_clients
+        WHERE owner_id = $1
+        ORDER BY created_at ASC
+    "#;
+
+    pub const FIND_INVITATIONS: &'static str = r#"
+        SELECT id, 'user'::TEXT AS kind, organization_id, role, invited_by, created_at, accepted_at
+        FROM user_invitations
+        WHERE accepted_user_id = $1 OR LOWER(email) = LOWER($2)
+        UNION ALL
+        SELECT id, 'organization'::TEXT AS kind, organization_id, role, invited_by, created_at, accepted_at
+        FROM organization_invitations
+        WHERE LOWER(email) = LOWER($2)
+        ORDER BY created_at ASC
+    "#;
+
+    pub const FIND_EMAIL_CHANGES: &'static str = r#"
+        SELECT id, user_id, old_email, new_email, confirm_expires_at, revert_expires_at, confirmed_at, reverted_at, cancelled_at, created_at
+        FROM email_changes
+        WHERE user_id = $1
+        ORDER BY created_at ASC
+    "#;
+
+    pub const FIND_EXPORT_JOBS: &'static str = r#"
+        SELECT id, format, status, created_at, completed_at
+        FROM export_jobs
+        WHERE requested_by = $1
+        ORDER BY created_at ASC
+    "#;
+
+    pub const FIND_STATUS_HISTORY: &'static str = r#"
+        SELECT id, user_id, from_status, to_status, reason, changed_by, created_at
+        FROM user_status_history
+        WHERE user_id = $1 OR changed_by = $1
+        ORDER BY created_at ASC
+    "#;
+
+    pub const FIND_AUDIT: &'static str = r#"
+        SELECT seq, id, occurred_at, actor_id, organization_id, request_id, ip, action, resource_type, resource_id, changes, prev_hash, hash
+        FROM audit_log
+        WHERE actor_id = $1 OR (resource_type = 'user' AND resource_id = $1)
+        ORDER BY seq ASC
+    "#;
+
+    pub const FIND_LOGIN_ATTEMPTS: &'static str = r#"
+        SELECT id, user_id, email, succeeded, failure_reason, ip, user_agent, request_id, created_at
+        FROM login_attempts
+        WHERE user_id = $1
+        ORDER BY created_at ASC
+    "#;
+
+    pub const FIND_KNOWN_DEVICES: &'static str = r#"
+        SELECT id, user_id, fingerprint, user_agent, ip_network, first_seen_at, last_seen_at
+        FROM known_devices
+        WHERE user_id = $1
+        ORDER BY first_seen_at ASC
+    "#;
+
+    pub const LOCK_USER_FOR_ERASURE: &'static str = r#"
+        SELECT email, status, attributes <> '{}'::JSONB AS has_attributes, avatar_updated_at IS NOT NULL AS has_avatar
+        FROM users
+        WHERE id = $1 AND erased_at IS NULL
+        FOR UPDATE
+    "#;
+
+    pub const ERASE_USER: &'static str = r#"
+        UPDATE users
+        SET full_name = $2, email = $3, password = $4, attributes = '{}', avatar_updated_at = NULL,
+            status = $5, erased_at = $6, updated_at = $6, version = version + 1
+        WHERE id = $1
+    "#;
+
+    pub const DELETE_IDENTITIES: &'static str = r#"
+        DELETE FROM user_identities WHERE user_id = $1
+    "#;
+
+    pub const DELETE_OIDC_LOGIN_STATES: &'static str = r#"
+        DELETE FROM oidc_login_states WHERE link_user_id = $1
+    "#;
+
+    pub const DELETE_PASSKEYS: &'static str = r#"
+        DELETE FROM webauthn_credentials WHERE user_id = $1
+    "#;
+
+    pub const DELETE_PASSKEY_CHALLENGES: &'static str = r#"
+        DELETE FROM webauthn_challenges WHERE user_id = $1
+    "#;
+
+    pub const DELETE_EMAIL_CHANGES: &'static str = r#"
+        DELETE FROM email_changes WHERE user_id = $1
+    "#;
+
+    pub const DELETE_LOGIN_ATTEMPTS: &'static str = r#"
+        DELETE FROM login_attempts WHERE user_id = $1
+    "#;
+
+    pub const DELETE_DEVICE_ALERTS: &'static str = r#"
+        DELETE FROM device_alerts WHERE user_id = $1
+    "#;
+
+    pub const DELETE_KNOWN_DEVICES: &'static str = r#"
+        DELETE FROM known_devices WHERE user_id = $1
+    "#;
+
+    pub const DELETE_PASSWORD_HISTORY: &'static str = r#"
+        DELETE FROM password_history WHERE user_id = $1
+    "#;
+
+    // Sem a chave os dados pessoais pseudonimizados na trilha de auditoria não podem mais ser confirmados
+    pub const DELETE_AUDIT_KEY: &'static str = r#"
+        DELETE FROM user_audit_keys WHERE user_id = $1
+    "#;
+
+    pub const REVOKE_SESSIONS: &'static str = r#"
+        UPDATE oauth_refresh_tokens SET revoked_at = $2 WHERE user_id = $1 AND revoked_at IS NULL
+    "#;
+
+    pub const DELETE_AUTHORIZATION_CODES: &'static str = r#"
+        DELETE FROM oauth_authorization_codes WHERE user_id = $1
+    "#;
+
+    pub const ANONYMIZE_USER_INVITATIONS: &'static str = r#"
+        UPDATE user_invitations SET email = $3 WHERE accepted_user_id = $1 OR LOWER(email) = LOWER($2)
+    "#;
+
+    pub const ANONYMIZE_ORGANIZATION_INVITATIONS: &'static str = r#"
+        UPDATE organization_invitations SET email = $2 WHERE LOWER(email) = LOWER($1)
+    "#;
+}
@@ -0,0 +1,15 @@
+pub struct TenantQueries;
+
+impl TenantQueries {
+    pub const SET_CURRENT_TENANT: &'static str = r#"
+        SELECT set_config('app.current_tenant', $1, true)
+    "#;
+
+    pub const SET_CURRENT_USER: &'static str = r#"
+        SELECT set_config('app.current_user', $1, true)
+    "#;
+
+    pub const BYPASS_RLS: &'static str = r#"
+        SELECT set_config('app.bypass_rls', 'on', true)
+    "#;
+}
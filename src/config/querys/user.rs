@@ -2,48 +2,189 @@ pub struct UserQueries;
 
 impl UserQueries {
     pub const CREATE_USER: &'static str = r#"
-        INSERT INTO users (id, full_name, email, password, created_at, updated_at)
-        VALUES ($1, $2, $3, $4, $5, $6)
-        RETURNING id, full_name, email, created_at, updated_at
+        INSERT INTO users (id, full_name, email, password, attributes, created_at, updated_at)
+        VALUES ($1, $2, $3, $4, $5, $6, $7)
+        RETURNING id, full_name, email, role, status, version, avatar_updated_at, attributes, created_at, updated_at, deleted_at
     "#;
 
     pub const FIND_BY_ID: &'static str = r#"
-        SELECT id, full_name, email, created_at, updated_at
+        SELECT id, full_name, email, role, status, version, avatar_updated_at, attributes, created_at, updated_at, deleted_at
         FROM users
-        WHERE id = $1
+        WHERE id = $1 AND deleted_at IS NULL
     "#;
 
     pub const FIND_BY_EMAIL: &'static str = r#"
-        SELECT id, full_name, email, password, created_at, updated_at
+        SELECT id, full_name, email, password, role, status, password_reset_required, password_changed_at, created_at, updated_at
         FROM users
-        WHERE email = $1
+        WHERE LOWER(email) = LOWER($1) AND deleted_at IS NULL
     "#;
 
+    pub const FIND_COMPLETE_BY_ID: &'static str = r#"
+        SELECT id, full_name, email, password, role, status, password_reset_required, password_changed_at, created_at, updated_at
+        FROM users
+        WHERE id = $1 AND deleted_at IS NULL
+    "#;
+
+    // Base da listagem; filtros, ordenação e cursor são anexados pelo repositório
     pub const FIND_ALL: &'static str = r#"
-        SELECT id, full_name, email, created_at, updated_at
+        SELECT id, full_name, email, role, status, version, avatar_updated_at, attributes, created_at, updated_at, deleted_at
         FROM users
-        ORDER BY created_at DESC
+        WHERE deleted_at IS NULL
+    "#;
+
+    pub const FIND_ALL_DELETED: &'static str = r#"
+        SELECT id, full_name, email, role, status, version, avatar_updated_at, attributes, created_at, updated_at, deleted_at
+        FROM users
+        WHERE deleted_at IS NOT NULL
+    "#;
+
+    pub const COUNT_ALL: &'static str = r#"
+        SELECT COUNT(*) AS total FROM users WHERE deleted_at IS NULL
+    "#;
+
+    pub const COUNT_ALL_DELETED: &'static str = r#"
+        SELECT COUNT(*) AS total FROM users WHERE deleted_at IS NOT NULL
+    "#;
+
+    // Limiar menor que o padrão (0.6) para tolerar erros de digitação
+    pub const SET_SEARCH_THRESHOLD: &'static str = r#"
+        SET LOCAL pg_trgm.word_similarity_threshold = 0.3
+    "#;
+
+    pub const SEARCH_USERS: &'static str = r#"
+        SELECT id, full_name, email, role, status, version, avatar_updated_at, attributes, created_at, updated_at, deleted_at,
+               GREATEST(word_similarity($1, full_name), word_similarity($1, email)) AS rank
+        FROM users
+        WHERE ($1 <% full_name OR $1 <% email) AND deleted_at IS NULL
+          AND ($3::uuid IS NULL OR id IN (SELECT user_id FROM organization_memberships WHERE organization_id = $3))
+        ORDER BY rank DESC, full_name ASC
+        LIMIT $2
     "#;
 
     pub const UPDATE_USER: &'static str = r#"
         UPDATE users
-        SET full_name = $2, email = $3, updated_at = $4
-        WHERE id = $1
-        RETURNING id, full_name, email, created_at, updated_at
+        SET full_name = $2, email = $3, attributes = $4, updated_at = $5, version = version + 1
+        WHERE id = $1 AND deleted_at IS NULL AND ($6::bigint[] IS NULL OR version = ANY($6))
+        RETURNING id, full_name, email, role, status, version, avatar_updated_at, attributes, created_at, updated_at, deleted_at
+    "#;
+
+    // Atualização parcial: as colunas presentes no patch são anexadas pelo repositório
+    pub const PATCH_USER: &'static str = r#"
+        UPDATE users
+        SET version = version + 1, updated_at =
+    "#;
+
+    pub const USER_RETURNING: &'static str = r#"
+        RETURNING id, full_name, email, role, status, version, avatar_updated_at, attributes, created_at, updated_at, deleted_at
+    "#;
+
+    // Inserção em lote: as linhas (VALUES) são anexadas pelo repositório
+    pub const IMPORT_USERS: &'static str = r#"
+        INSERT INTO users (id, full_name, email, password, attributes, created_at, updated_at)
+    "#;
+
+    pub const IMPORT_USERS_SKIP_CONFLICTS: &'static str = r#"
+        ON CONFLICT (LOWER(email)) DO NOTHING
+    "#;
+
+    pub const ADD_USERS_TO_ORGANIZATION: &'static str = r#"
+        INSERT INTO organization_memberships (organization_id, user_id, role, created_at)
+        SELECT $1, user_id, 'member', $3 FROM UNNEST($2::uuid[]) AS user_id
+    "#;
+
+    pub const FIND_EXISTING_EMAILS: &'static str = r#"
+        SELECT LOWER(email) AS email FROM users WHERE LOWER(email) = ANY($1)
     "#;
 
     pub const UPDATE_PASSWORD: &'static str = r#"
-            UPDATE users
-            SET password = $2, updated_at = $3
-            WHERE id = $1
-            RETURNING id, full_name, email, created_at, updated_at
-        "#;
+        UPDATE users
+        SET password = $2, password_changed_at = $3, updated_at = $3
+        WHERE id = $1 AND deleted_at IS NULL
+        RETURNING id, full_name, email, role, status, version, avatar_updated_at, attributes, created_at, updated_at, deleted_at
+    "#;
+
+    pub const SET_AVATAR: &'static str = r#"
+        UPDATE users
+        SET avatar_updated_at = $2, updated_at = $2, version = version + 1
+        WHERE id = $1 AND deleted_at IS NULL
+        RETURNING id, full_name, email, role, status, version, avatar_updated_at, attributes, created_at, updated_at, deleted_at
+    "#;
+
+    pub const FIND_STATUS: &'static str = r#"
+        SELECT status FROM users WHERE id = $1 AND deleted_at IS NULL
+    "#;
+
+    // Move a senha atual para o histórico antes de ela ser substituída
+    pub const ARCHIVE_PASSWORD: &'static str = r#"
+        INSERT INTO password_history (id, user_id, password_hash, created_at)
+        SELECT $2, id, password, $3 FROM users WHERE id = $1 AND deleted_at IS NULL
+    "#;
+
+    pub const PRUNE_PASSWORD_HISTORY: &'static str = r#"
+        DELETE FROM password_history
+        WHERE user_id = $1 AND id NOT IN (
+            SELECT id FROM password_history WHERE user_id = $1 ORDER BY created_at DESC, id DESC LIMIT $2
+        )
+    "#;
+
+    pub const FIND_PASSWORD_HISTORY: &'static str = r#"
+        SELECT password_hash FROM password_history WHERE user_id = $1 ORDER BY created_at DESC, id DESC LIMIT $2
+    "#;
+
+    pub const FIND_SESSION_STATE: &'static str = r#"
+        SELECT status, sessions_revoked_at FROM users WHERE id = $1 AND deleted_at IS NULL
+    "#;
+
+    pub const FIND_PASSWORD: &'static str = r#"
+        SELECT password FROM users WHERE id = $1 AND deleted_at IS NULL
+    "#;
+
+    pub const CHANGE_ROLE: &'static str = r#"
+        UPDATE users
+        SET role = $2, updated_at = $3, version = version + 1
+        WHERE id = $1 AND deleted_at IS NULL
+        RETURNING id, full_name, email, role, status, version, avatar_updated_at, attributes, created_at, updated_at, deleted_at
+    "#;
+
+    // Só altera se o status ainda for o lido pelo serviço, evitando transições concorrentes
+    pub const CHANGE_STATUS: &'static str = r#"
+        UPDATE users
+        SET status = $3, updated_at = $4, version = version + 1
+        WHERE id = $1 AND status = $2 AND deleted_at IS NULL
+        RETURNING id, full_name, email, role, status, version, avatar_updated_at, attributes, created_at, updated_at, deleted_at
+    "#;
+
+    pub const CREATE_STATUS_HISTORY: &'static str = r#"
+        INSERT INTO user_status_history (id, user_id, from_status, to_status, reason, changed_by, created_at)
+        VALUES ($1, $2, $3, $4, $5, $6, $7)
+    "#;
+
+    pub const FIND_STATUS_HISTORY: &'static str = r#"
+        SELECT id, user_id, from_status, to_status, reason, changed_by, created_at
+        FROM user_status_history
+        WHERE user_id = $1
+        ORDER BY created_at DESC
+    "#;
 
     pub const DELETE_USER: &'static str = r#"
-        DELETE FROM users WHERE id = $1
+        UPDATE users
+        SET deleted_at = $2, updated_at = $2, version = version + 1
+        WHERE id = $1 AND deleted_at IS NULL AND ($3::bigint[] IS NULL OR version = ANY($3))
+    "#;
+
+    pub const RESTORE_USER: &'static str = r#"
+        UPDATE users
+        SET deleted_at = NULL, updated_at = $2, version = version + 1
+        WHERE id = $1 AND deleted_at IS NOT NULL
+        RETURNING id, full_name, email, role, status, version, avatar_updated_at, attributes, created_at, updated_at, deleted_at
+    "#;
+
+    pub const PURGE_DELETED_USERS: &'static str = r#"
+        DELETE FROM users WHERE deleted_at IS NOT NULL AND deleted_at < $1
     "#;
 
+    // Considera também usuários excluídos: o email só volta a ficar livre após o expurgo
     pub const EMAIL_EXIST_FOR_OTHER_USER: &'static str = r#"
-        SELECT EXISTS(SELECT 1 FROM users WHERE email = $1 AND id != $2) as exists
+        SELECT EXISTS(SELECT 1 FROM users WHERE LOWER(email) = LOWER($1) AND id != $2) as exists
     "#;
 }
@@ -0,0 +1,42 @@
+pub struct UserAttributeQueries;
+
+impl UserAttributeQueries {
+    pub const CREATE_DEFINITION: &'static str = r#"
+        INSERT INTO user_attribute_definitions (key, label, attribute_type, required, allowed_values, created_at, updated_at)
+        VALUES ($1, $2, $3, $4, $5, $6, $6)
+        RETURNING key, label, attribute_type, required, allowed_values, created_at, updated_at
+    "#;
+
+    pub const FIND_ALL: &'static str = r#"
+        SELECT key, label, attribute_type, required, allowed_values, created_at, updated_at
+        FROM user_attribute_definitions
+        ORDER BY key
+    "#;
+
+    pub const FIND_BY_KEY: &'static str = r#"
+        SELECT key, label, attribute_type, required, allowed_values, created_at, updated_at
+        FROM user_attribute_definitions
+        WHERE key = $1
+    "#;
+
+    pub const UPDATE_DEFINITION: &'static str = r#"
+        UPDATE user_attribute_definitions
+        SET label = $2, required = $3, allowed_values = $4, updated_at = $5
+        WHERE key = $1
+        RETURNING key, label, attribute_type, required, allowed_values, created_at, updated_at
+    "#;
+
+    pub const DELETE_DEFINITION: &'static str = r#"
+        DELETE FROM user_attribute_definitions WHERE key = $1
+    "#;
+
+    // Remove dos usuários o valor do atributo excluído, para que as próximas escritas não o recusem.
+    // É uma alteração dos usuários como outra qualquer: muda a versão e devolve o antes e o depois para a auditoria
+    pub const REMOVE_FROM_USERS: &'static str = r#"
+        UPDATE users u
+        SET attributes = u.attributes - $1, updated_at = $2, version = u.version + 1
+        FROM (SELECT id, attributes FROM users WHERE attributes ? $1 FOR UPDATE) previous
+        WHERE u.id = previous.id
+        RETURNING u.id AS user_id, previous.attributes AS before, u.attributes AS after
+    "#;
+}
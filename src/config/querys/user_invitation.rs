@@ -0,0 +1,76 @@
+pub struct UserInvitationQueries;
+
+impl UserInvitationQueries {
+    // Convites vencidos liberam o email para um novo convite
+    pub const REVOKE_EXPIRED_FOR_EMAIL: &'static str = r#"
+        UPDATE user_invitations
+        SET revoked_at = $2
+        WHERE LOWER(email) = LOWER($1) AND accepted_at IS NULL AND revoked_at IS NULL AND expires_at <= $2
+    "#;
+
+    pub const CREATE_INVITATION: &'static str = r#"
+        INSERT INTO user_invitations (id, email, role, organization_id, organization_role, invited_by, last_sent_at, expires_at, created_at)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $7)
+        RETURNING id, email, role, organization_id, organization_role, invited_by, send_count, last_sent_at, expires_at,
+                  accepted_at, accepted_user_id, revoked_at, created_at
+    "#;
+
+    pub const FIND_ALL: &'static str = r#"
+        SELECT id, email, role, organization_id, organization_role, invited_by, send_count, last_sent_at, expires_at,
+               accepted_at, accepted_user_id, revoked_at, created_at
+        FROM user_invitations
+        ORDER BY created_at DESC
+    "#;
+
+    pub const FIND_BY_ID: &'static str = r#"
+        SELECT id, email, role, organization_id, organization_role, invited_by, send_count, last_sent_at, expires_at,
+               accepted_at, accepted_user_id, revoked_at, created_at
+        FROM user_invitations
+        WHERE id = $1
+    "#;
+
+    // O novo envio renova o prazo e invalida os links anteriores
+    pub const RESEND_INVITATION: &'static str = r#"
+        UPDATE user_invitations
+        SET send_count = send_count + 1, last_sent_at = $2, expires_at = $3
+        WHERE id = $1 AND accepted_at IS NULL AND revoked_at IS NULL
+        RETURNING id, email, role, organization_id, organization_role, invited_by, send_count, last_sent_at, expires_at,
+                  accepted_at, accepted_user_id, revoked_at, created_at
+    "#;
+
+    pub const REVOKE_INVITATION: &'static str = r#"
+        UPDATE user_invitations
+        SET revoked_at = $2
+        WHERE id = $1 AND accepted_at IS NULL AND revoked_at IS NULL
+        RETURNING id, email, role, organization_id, organization_role, invited_by, send_count, last_sent_at, expires_at,
+                  accepted_at, accepted_user_id, revoked_at, created_at
+    "#;
+
+    // Reserva o convite antes de criar a conta; só o link do envio mais recente é aceito
+    pub const CLAIM_INVITATION: &'static str = r#"
+        UPDATE user_invitations
+        SET accepted_at = $3
+        WHERE id = $1 AND send_count = $2 AND accepted_at IS NULL AND revoked_at IS NULL AND expires_at > $3
+    "#;
+
+    pub const RELEASE_INVITATION: &'static str = r#"
+        UPDATE user_invitations SET accepted_at = NULL WHERE id = $1 AND accepted_user_id IS NULL
+    "#;
+
+    pub const COMPLETE_INVITATION: &'static str = r#"
+        UPDATE user_invitations SET accepted_user_id = $2 WHERE id = $1
+    "#;
+
+    // Mesma alteração de `change_role`; sem linha quando o convite mantém o papel padrão
+    pub const ASSIGN_USER_ROLE: &'static str = r#"
+        UPDATE users
+        SET role = $2, updated_at = $3, version = version + 1
+        WHERE id = $1 AND role <> $2
+        RETURNING id, full_name, email, role, status, version, avatar_updated_at, attributes, created_at, updated_at, deleted_at
+    "#;
+
+    pub const ADD_ORGANIZATION_MEMBERSHIP: &'static str = r#"
+        INSERT INTO organization_memberships (organization_id, user_id, role, created_at)
+        VALUES ($1, $2, $3, $4)
+    "#;
+}
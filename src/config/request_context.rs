@@ -0,0 +1,47 @@
+use actix_web::dev::ServiceRequest;
+use actix_web::http::header;
+use uuid::Uuid;
+
+pub const REQUEST_ID_HEADER: &str = "x-request-id";
+
+#[derive(Debug, Clone, Default)]
+pub struct RequestContext {
+    pub request_id: Option<String>,
+    pub actor_id: Option<Uuid>,
+    pub ip: Option<String>,
+    pub user_agent: Option<String>,
+}
+
+tokio::task_local! {
+    // Origem da requisição em andamento, usada pela trilha de auditoria; definida pelo AuthMiddleware
+    pub static CURRENT_REQUEST: RequestContext;
+}
+
+// Fora de uma requisição (jobs) o contexto fica vazio
+pub fn current_request() -> RequestContext {
+    CURRENT_REQUEST.try_with(|context| context.clone()).unwrap_or_default()
+}
+
+// O id recebido do proxy é mantido para correlacionar os logs; valores inválidos são substituídos
+pub fn request_context(req: &ServiceRequest, actor_id: Option<Uuid>) -> RequestContext {
+    let request_id = req.headers()
+        .get(REQUEST_ID_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .filter(|value| is_valid_request_id(value))
+        .map(str::to_string)
+        .unwrap_or_else(|| Uuid::new_v4().to_string());
+    let ip = req.connection_info().realip_remote_addr().map(|ip| ip.chars().take(64).collect());
+    let user_agent = req.headers()
+        .get(header::USER_AGENT)
+        .and_then(|value| value.to_str().ok())
+        .filter(|value| !value.is_empty())
+        .map(|value| value.chars().take(512).collect());
+
+    RequestContext { request_id: Some(request_id), actor_id, ip, user_agent }
+}
+
+fn is_valid_request_id(value: &str) -> bool {
+    !value.is_empty()
+        && value.len() <= 128
+        && value.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.' | ':'))
+}
@@ -0,0 +1,55 @@
+use sqlx::{PgPool, Postgres, Transaction};
+use uuid::Uuid;
+use crate::config::querys::tenant::TenantQueries;
+
+tokio::task_local! {
+    // Escopo da requisição em andamento, definido pelo AuthMiddleware e pelos jobs
+    pub static CURRENT_TENANT: TenantScope;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TenantScope {
+    // Somente as linhas da organização
+    Organization(Uuid),
+    // Usuário sem organização ativa: nenhuma linha de organização, apenas a própria conta
+    User(Uuid),
+    // Todas as organizações, liberado explicitamente por `app.bypass_rls`
+    All,
+    // Nenhum escopo declarado: as políticas não liberam nenhuma linha
+    Undeclared,
+}
+
+pub fn current_scope() -> TenantScope {
+    CURRENT_TENANT.try_with(|scope| *scope).unwrap_or(TenantScope::Undeclared)
+}
+
+pub fn current_tenant() -> Option<Uuid> {
+    match current_scope() {
+        TenantScope::Organization(tenant) => Some(tenant),
+        _ => None,
+    }
+}
+
+// As configurações valem só até o fim da transação, então a conexão volta limpa para o pool
+pub async fn begin_tenant_transaction(pool: &PgPool, scope: TenantScope) -> Result<Transaction<'static, Postgres>, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+    match scope {
+        TenantScope::Organization(tenant) => {
+            sqlx::query(TenantQueries::SET_CURRENT_TENANT)
+                .bind(tenant.to_string())
+                .execute(&mut *tx)
+                .await?;
+        }
+        TenantScope::User(user_id) => {
+            sqlx::query(TenantQueries::SET_CURRENT_USER)
+                .bind(user_id.to_string())
+                .execute(&mut *tx)
+                .await?;
+        }
+        TenantScope::All => {
+            sqlx::query(TenantQueries::BYPASS_RLS).execute(&mut *tx).await?;
+        }
+        TenantScope::Undeclared => {}
+    }
+    Ok(tx)
+}
@@ -0,0 +1,31 @@
+use actix_web::{web, HttpResponse};
+use log::info;
+use crate::core::entities::audit::AuditQuery;
+use crate::core::entities::auth::ClaimsToUserToken;
+use crate::core::entities::user::{ROLE_ADMIN, ROLE_AUDITOR};
+use crate::middleware::auth::require_role;
+use crate::services::audit_service::AuditService;
+use crate::utils::errors::AppError;
+
+// Administradores e auditores sem organização ativa veem as entradas de todas as organizações
+pub async fn find_audit_entries(query: web::Query<AuditQuery>, claims: ClaimsToUserToken, service: web::Data<AuditService>) -> Result<HttpResponse, AppError> {
+    info!("[Controller] Received request to find audit entries");
+    require_role(&claims, &[ROLE_ADMIN, ROLE_AUDITOR])?;
+    let result = service.find_entries(query.into_inner(), claims.organization_id).await;
+    match &result {
+        Ok(_) => info!("[Controller] Find audit entries request completed successfully"),
+        Err(e) => info!("[Controller] Find audit entries request failed: {:?}", e)
+    }
+    result
+}
+
+pub async fn verify_audit_chain(claims: ClaimsToUserToken, service: web::Data<AuditService>) -> Result<HttpResponse, AppError> {
+    info!("[Controller] Received request to verify audit chain");
+    require_role(&claims, &[ROLE_ADMIN, ROLE_AUDITOR])?;
+    let result = service.verify_chain_response().await;
+    match &result {
+        Ok(_) => info!("[Controller] Verify audit chain request completed successfully"),
+        Err(e) => info!("[Controller] Verify audit chain request failed: {:?}", e)
+    }
+    result
+}
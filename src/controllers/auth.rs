@@ -1,16 +1,98 @@
 use actix_web::{web, HttpResponse};
 use log::info;
+use uuid::Uuid;
 use crate::services::auth_service::AuthService;
-use crate::core::entities::auth::Login;
+use crate::services::login_activity_service::LoginActivityService;
+use crate::middleware::auth::{authenticated_user_id, require_role};
+use crate::core::entities::auth::{ClaimsToUserToken, Login};
+use crate::core::entities::login_attempt::LoginActivityQuery;
+use crate::core::entities::user::ROLE_ADMIN;
+use crate::core::entities::passkey::{PasskeyLogin, PasskeyLoginOptionsRequest, PasskeyRegistration};
 use crate::utils::errors::AppError;
 
 
 pub async fn login(data: web::Json<Login>, service: web::Data<AuthService>) -> Result<HttpResponse, AppError> {
     info!("[Controller] Received request to login user with email: {}", data.email);
     let result = service.login(data.into_inner()).await;
-    match &result { 
+    match &result {
         Ok(_) => info!("[Controller] Login request completed successfully"),
         Err(e) =>  info!("[Controller] User creation request failed: {:?}", e)
     }
     result
-}
\ No newline at end of file
+}
+
+pub async fn passkey_login_options(data: web::Json<PasskeyLoginOptionsRequest>, service: web::Data<AuthService>) -> Result<HttpResponse, AppError> {
+    info!("[Controller] Received request for passkey login options");
+    let result = service.passkey_login_options(data.into_inner()).await;
+    match &result {
+        Ok(_) => info!("[Controller] Passkey login options request completed successfully"),
+        Err(e) => info!("[Controller] Passkey login options request failed: {:?}", e)
+    }
+    result
+}
+
+pub async fn login_with_passkey(data: web::Json<PasskeyLogin>, service: web::Data<AuthService>) -> Result<HttpResponse, AppError> {
+    info!("[Controller] Received request to login with passkey: {}", data.credential_id);
+    let result = service.login_with_passkey(data.into_inner()).await;
+    match &result {
+        Ok(_) => info!("[Controller] Passkey login request completed successfully"),
+        Err(e) => info!("[Controller] Passkey login request failed: {:?}", e)
+    }
+    result
+}
+
+pub async fn passkey_registration_options(claims: ClaimsToUserToken, service: web::Data<AuthService>) -> Result<HttpResponse, AppError> {
+    info!("[Controller] Received request for passkey registration options for user: {}", claims.id);
+    let result = service.passkey_registration_options(authenticated_user_id(&claims)?).await;
+    match &result {
+        Ok(_) => info!("[Controller] Passkey registration options request completed successfully"),
+        Err(e) => info!("[Controller] Passkey registration options request failed: {:?}", e)
+    }
+    result
+}
+
+pub async fn register_passkey(data: web::Json<PasskeyRegistration>, claims: ClaimsToUserToken, service: web::Data<AuthService>) -> Result<HttpResponse, AppError> {
+    info!("[Controller] Received request to register passkey for user: {}", claims.id);
+    let result = service.register_passkey(authenticated_user_id(&claims)?, data.into_inner()).await;
+    match &result {
+        Ok(_) => info!("[Controller] Passkey registration request completed successfully"),
+        Err(e) => info!("[Controller] Passkey registration request failed: {:?}", e)
+    }
+    result
+}
+
+pub async fn find_passkeys(claims: ClaimsToUserToken, service: web::Data<AuthService>) -> Result<HttpResponse, AppError> {
+    info!("[Controller] Received request to list passkeys for user: {}", claims.id);
+    let result = service.find_passkeys(authenticated_user_id(&claims)?).await;
+    match &result {
+        Ok(_) => info!("[Controller] Find passkeys request completed successfully"),
+        Err(e) => info!("[Controller] Find passkeys request failed: {:?}", e)
+    }
+    result
+}
+
+pub async fn delete_passkey(id: web::Path<Uuid>, claims: ClaimsToUserToken, service: web::Data<AuthService>) -> Result<HttpResponse, AppError> {
+    info!("[Controller] Received request to delete passkey with id: {}", id);
+    let result = service.delete_passkey(authenticated_user_id(&claims)?, id.into_inner()).await;
+    match &result {
+        Ok(_) => info!("[Controller] Delete passkey request completed successfully"),
+        Err(e) => info!("[Controller] Delete passkey request failed: {:?}", e)
+    }
+    result
+}
+
+pub async fn find_login_activity(query: web::Query<LoginActivityQuery>, claims: ClaimsToUserToken, service: web::Data<LoginActivityService>) -> Result<HttpResponse, AppError> {
+    info!("[Controller] Received request to find login activity for user: {}", claims.id);
+    let current_user_id = authenticated_user_id(&claims)?;
+    let query = query.into_inner();
+    let user_id = query.user_id.unwrap_or(current_user_id);
+    if user_id != current_user_id {
+        require_role(&claims, &[ROLE_ADMIN])?;
+    }
+    let result = service.find_activity(user_id, query).await;
+    match &result {
+        Ok(_) => info!("[Controller] Find login activity request completed successfully"),
+        Err(e) => info!("[Controller] Find login activity request failed: {:?}", e)
+    }
+    result
+}
@@ -0,0 +1,26 @@
+use actix_web::{web, HttpResponse};
+use log::info;
+use crate::core::entities::device::{DeviceAlertPasswordReset, DeviceAlertToken};
+use crate::services::device_alert_service::DeviceAlertService;
+use crate::utils::errors::AppError;
+
+// Rotas públicas: o link "não fui eu" chega por email e o token é a credencial
+pub async fn report_device_alert(data: web::Json<DeviceAlertToken>, service: web::Data<DeviceAlertService>) -> Result<HttpResponse, AppError> {
+    info!("[Controller] Received request to report a new device sign-in");
+    let result = service.report_alert(data.into_inner()).await;
+    match &result {
+        Ok(_) => info!("[Controller] Report device alert request completed successfully"),
+        Err(e) => info!("[Controller] Report device alert request failed: {:?}", e)
+    }
+    result
+}
+
+pub async fn reset_password(data: web::Json<DeviceAlertPasswordReset>, service: web::Data<DeviceAlertService>) -> Result<HttpResponse, AppError> {
+    info!("[Controller] Received request to reset password through device alert");
+    let result = service.reset_password(data.into_inner()).await;
+    match &result {
+        Ok(_) => info!("[Controller] Device alert password reset request completed successfully"),
+        Err(e) => info!("[Controller] Device alert password reset request failed: {:?}", e)
+    }
+    result
+}
@@ -0,0 +1,26 @@
+use actix_web::{web, HttpResponse};
+use log::info;
+use crate::core::entities::email_change::EmailChangeToken;
+use crate::services::email_change_service::EmailChangeService;
+use crate::utils::errors::AppError;
+
+// Rotas públicas: os links chegam por email e o token é a credencial
+pub async fn confirm_email_change(data: web::Json<EmailChangeToken>, service: web::Data<EmailChangeService>) -> Result<HttpResponse, AppError> {
+    info!("[Controller] Received request to confirm email change");
+    let result = service.confirm_change(data.into_inner()).await;
+    match &result {
+        Ok(_) => info!("[Controller] Confirm email change request completed successfully"),
+        Err(e) => info!("[Controller] Confirm email change request failed: {:?}", e)
+    }
+    result
+}
+
+pub async fn revert_email_change(data: web::Json<EmailChangeToken>, service: web::Data<EmailChangeService>) -> Result<HttpResponse, AppError> {
+    info!("[Controller] Received request to revert email change");
+    let result = service.revert_change(data.into_inner()).await;
+    match &result {
+        Ok(_) => info!("[Controller] Revert email change request completed successfully"),
+        Err(e) => info!("[Controller] Revert email change request failed: {:?}", e)
+    }
+    result
+}
@@ -0,0 +1,38 @@
+use actix_web::{web, HttpResponse};
+use log::info;
+use uuid::Uuid;
+use crate::core::entities::auth::ClaimsToUserToken;
+use crate::core::entities::export_job::{CreateExportJob, ExportDownloadQuery};
+use crate::middleware::auth::{authenticated_user_id, organization_scope};
+use crate::services::export_service::ExportService;
+use crate::utils::errors::AppError;
+
+pub async fn create_export(data: web::Json<CreateExportJob>, claims: ClaimsToUserToken, service: web::Data<ExportService>) -> Result<HttpResponse, AppError> {
+    info!("[Controller] Received request to create export job as {:?}", data.format);
+    let result = service.create_job(authenticated_user_id(&claims)?, organization_scope(&claims)?, data.into_inner()).await;
+    match &result {
+        Ok(_) => info!("[Controller] Create export job request completed successfully"),
+        Err(e) => info!("[Controller] Create export job request failed: {:?}", e)
+    }
+    result
+}
+
+pub async fn find_export(id: web::Path<Uuid>, claims: ClaimsToUserToken, service: web::Data<ExportService>) -> Result<HttpResponse, AppError> {
+    info!("[Controller] Received request to find export job with id: {}", id);
+    let result = service.find_job(id.into_inner(), authenticated_user_id(&claims)?, &claims.role).await;
+    match &result {
+        Ok(_) => info!("[Controller] Find export job request completed successfully"),
+        Err(e) => info!("[Controller] Find export job request failed: {:?}", e)
+    }
+    result
+}
+
+pub async fn download_export(id: web::Path<Uuid>, query: web::Query<ExportDownloadQuery>, service: web::Data<ExportService>) -> Result<HttpResponse, AppError> {
+    info!("[Controller] Received request to download export job with id: {}", id);
+    let result = service.download(id.into_inner(), query.into_inner()).await;
+    match &result {
+        Ok(_) => info!("[Controller] Download export request started streaming"),
+        Err(e) => info!("[Controller] Download export request failed: {:?}", e)
+    }
+    result
+}
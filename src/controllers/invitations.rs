@@ -0,0 +1,64 @@
+use actix_web::{web, HttpResponse};
+use log::info;
+use uuid::Uuid;
+use crate::core::entities::auth::ClaimsToUserToken;
+use crate::core::entities::user::ROLE_ADMIN;
+use crate::core::entities::user_invitation::{AcceptUserInvitation, CreateUserInvitation};
+use crate::middleware::auth::{authenticated_user_id, organization_scope, require_role};
+use crate::services::user_invitation_service::UserInvitationService;
+use crate::utils::errors::AppError;
+
+pub async fn create_invitation(data: web::Json<CreateUserInvitation>, claims: ClaimsToUserToken, service: web::Data<UserInvitationService>) -> Result<HttpResponse, AppError> {
+    info!("[Controller] Received request to invite user with email: {}", data.email);
+    require_role(&claims, &[ROLE_ADMIN])?;
+    let result = service.create_invitation(authenticated_user_id(&claims)?, organization_scope(&claims)?, data.into_inner()).await;
+    match &result {
+        Ok(_) => info!("[Controller] User invitation request completed successfully"),
+        Err(e) => info!("[Controller] User invitation request failed: {:?}", e)
+    }
+    result
+}
+
+pub async fn find_invitations(claims: ClaimsToUserToken, service: web::Data<UserInvitationService>) -> Result<HttpResponse, AppError> {
+    info!("[Controller] Received request to find user invitations");
+    require_role(&claims, &[ROLE_ADMIN])?;
+    let result = service.find_invitations().await;
+    match &result {
+        Ok(_) => info!("[Controller] Find user invitations request completed successfully"),
+        Err(e) => info!("[Controller] Find user invitations request failed: {:?}", e)
+    }
+    result
+}
+
+pub async fn resend_invitation(id: web::Path<Uuid>, claims: ClaimsToUserToken, service: web::Data<UserInvitationService>) -> Result<HttpResponse, AppError> {
+    info!("[Controller] Received request to resend user invitation with id: {}", id);
+    require_role(&claims, &[ROLE_ADMIN])?;
+    let result = service.resend_invitation(id.into_inner()).await;
+    match &result {
+        Ok(_) => info!("[Controller] Resend user invitation request completed successfully"),
+        Err(e) => info!("[Controller] Resend user invitation request failed: {:?}", e)
+    }
+    result
+}
+
+pub async fn revoke_invitation(id: web::Path<Uuid>, claims: ClaimsToUserToken, service: web::Data<UserInvitationService>) -> Result<HttpResponse, AppError> {
+    info!("[Controller] Received request to revoke user invitation with id: {}", id);
+    require_role(&claims, &[ROLE_ADMIN])?;
+    let result = service.revoke_invitation(id.into_inner()).await;
+    match &result {
+        Ok(_) => info!("[Controller] Revoke user invitation request completed successfully"),
+        Err(e) => info!("[Controller] Revoke user invitation request failed: {:?}", e)
+    }
+    result
+}
+
+// Rota pública: o token assinado do convite é a credencial
+pub async fn accept_invitation(data: web::Json<AcceptUserInvitation>, service: web::Data<UserInvitationService>) -> Result<HttpResponse, AppError> {
+    info!("[Controller] Received request to accept user invitation");
+    let result = service.accept_invitation(data.into_inner()).await;
+    match &result {
+        Ok(_) => info!("[Controller] Accept user invitation request completed successfully"),
+        Err(e) => info!("[Controller] Accept user invitation request failed: {:?}", e)
+    }
+    result
+}
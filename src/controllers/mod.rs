@@ -1,2 +1,11 @@
 pub mod auth;
+pub mod oauth;
+pub mod oidc;
 pub mod users;
+pub mod exports;
+pub mod organizations;
+pub mod user_attributes;
+pub mod invitations;
+pub mod email_changes;
+pub mod audit;
+pub mod device_alerts;
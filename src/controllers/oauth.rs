@@ -0,0 +1,115 @@
+use actix_web::{web, HttpRequest, HttpResponse};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use log::info;
+use uuid::Uuid;
+use crate::middleware::auth::authenticated_user_id;
+use crate::core::entities::auth::ClaimsToUserToken;
+use crate::core::entities::oauth::{AuthorizeDecision, AuthorizeRequest, CreateOAuthClient, TokenIntrospectionRequest, TokenRequest};
+use crate::services::oauth_service::{ClientCredentials, OAuthService};
+use crate::utils::errors::{AppError, OAuthError};
+
+pub async fn create_client(data: web::Json<CreateOAuthClient>, claims: ClaimsToUserToken, service: web::Data<OAuthService>) -> Result<HttpResponse, AppError> {
+    info!("[Controller] Received request to register OAuth client: {}", data.name);
+    let result = service.create_client(authenticated_user_id(&claims)?, data.into_inner()).await;
+    match &result {
+        Ok(_) => info!("[Controller] OAuth client registration request completed successfully"),
+        Err(e) => info!("[Controller] OAuth client registration request failed: {:?}", e)
+    }
+    result
+}
+
+pub async fn find_clients(claims: ClaimsToUserToken, service: web::Data<OAuthService>) -> Result<HttpResponse, AppError> {
+    info!("[Controller] Received request to list OAuth clients for user: {}", claims.id);
+    let result = service.find_clients(authenticated_user_id(&claims)?).await;
+    match &result {
+        Ok(_) => info!("[Controller] Find OAuth clients request completed successfully"),
+        Err(e) => info!("[Controller] Find OAuth clients request failed: {:?}", e)
+    }
+    result
+}
+
+pub async fn delete_client(id: web::Path<Uuid>, claims: ClaimsToUserToken, service: web::Data<OAuthService>) -> Result<HttpResponse, AppError> {
+    info!("[Controller] Received request to delete OAuth client with id: {}", id);
+    let result = service.delete_client(authenticated_user_id(&claims)?, id.into_inner()).await;
+    match &result {
+        Ok(_) => info!("[Controller] Delete OAuth client request completed successfully"),
+        Err(e) => info!("[Controller] Delete OAuth client request failed: {:?}", e)
+    }
+    result
+}
+
+pub async fn consent_details(query: web::Query<AuthorizeRequest>, _claims: ClaimsToUserToken, service: web::Data<OAuthService>) -> Result<HttpResponse, AppError> {
+    info!("[Controller] Received authorization request for client: {}", query.client_id);
+    let result = service.consent_details(query.into_inner()).await;
+    match &result {
+        Ok(_) => info!("[Controller] Authorization request completed successfully"),
+        Err(e) => info!("[Controller] Authorization request failed: {:?}", e)
+    }
+    result
+}
+
+pub async fn authorize(data: web::Json<AuthorizeDecision>, claims: ClaimsToUserToken, service: web::Data<OAuthService>) -> Result<HttpResponse, AppError> {
+    info!("[Controller] Received authorization decision for client: {}", data.request.client_id);
+    let result = service.authorize(authenticated_user_id(&claims)?, data.into_inner()).await;
+    match &result {
+        Ok(_) => info!("[Controller] Authorization decision request completed successfully"),
+        Err(e) => info!("[Controller] Authorization decision request failed: {:?}", e)
+    }
+    result
+}
+
+pub async fn token(req: HttpRequest, data: web::Form<TokenRequest>, service: web::Data<OAuthService>) -> Result<HttpResponse, OAuthError> {
+    let data = data.into_inner();
+    let credentials = client_credentials(&req, data.client_id.clone(), data.client_secret.clone())?;
+    info!("[Controller] Received token request from client: {}", credentials.client_id);
+    let result = service.token(credentials, data).await;
+    match &result {
+        Ok(_) => info!("[Controller] Token request completed successfully"),
+        Err(e) => info!("[Controller] Token request failed: {:?}", e)
+    }
+    result
+}
+
+pub async fn introspect(req: HttpRequest, data: web::Form<TokenIntrospectionRequest>, service: web::Data<OAuthService>) -> Result<HttpResponse, OAuthError> {
+    let data = data.into_inner();
+    let credentials = client_credentials(&req, data.client_id.clone(), data.client_secret.clone())?;
+    info!("[Controller] Received introspection request from client: {}", credentials.client_id);
+    let result = service.introspect(credentials, data).await;
+    match &result {
+        Ok(_) => info!("[Controller] Introspection request completed successfully"),
+        Err(e) => info!("[Controller] Introspection request failed: {:?}", e)
+    }
+    result
+}
+
+pub async fn revoke(req: HttpRequest, data: web::Form<TokenIntrospectionRequest>, service: web::Data<OAuthService>) -> Result<HttpResponse, OAuthError> {
+    let data = data.into_inner();
+    let credentials = client_credentials(&req, data.client_id.clone(), data.client_secret.clone())?;
+    info!("[Controller] Received revocation request from client: {}", credentials.client_id);
+    let result = service.revoke(credentials, data).await;
+    match &result {
+        Ok(_) => info!("[Controller] Revocation request completed successfully"),
+        Err(e) => info!("[Controller] Revocation request failed: {:?}", e)
+    }
+    result
+}
+
+// Aceita client_secret_basic (cabeçalho Authorization) ou client_secret_post (corpo do formulário)
+fn client_credentials(req: &HttpRequest, client_id: Option<String>, client_secret: Option<String>) -> Result<ClientCredentials, OAuthError> {
+    let basic = req.headers().get("Authorization")
+        .and_then(|h| h.to_str().ok())
+        .and_then(|h| h.strip_prefix("Basic "))
+        .and_then(|encoded| STANDARD.decode(encoded).ok())
+        .and_then(|decoded| String::from_utf8(decoded).ok());
+
+    if let Some(decoded) = basic {
+        let (id, secret) = decoded.split_once(':')
+            .ok_or_else(|| OAuthError::new("invalid_client", "Malformed basic credentials"))?;
+        return Ok(ClientCredentials { client_id: id.to_string(), client_secret: Some(secret.to_string()) });
+    }
+
+    match client_id {
+        Some(client_id) => Ok(ClientCredentials { client_id, client_secret }),
+        None => Err(OAuthError::new("invalid_client", "Client authentication is required")),
+    }
+}
@@ -0,0 +1,58 @@
+use actix_web::{web, HttpResponse};
+use log::info;
+use uuid::Uuid;
+use crate::middleware::auth::authenticated_user_id;
+use crate::core::entities::auth::ClaimsToUserToken;
+use crate::core::entities::identity::OidcCallback;
+use crate::services::oidc_service::OidcService;
+use crate::utils::errors::AppError;
+
+pub async fn start_login(service: web::Data<OidcService>) -> Result<HttpResponse, AppError> {
+    info!("[Controller] Received request to start OIDC login");
+    let result = service.start_login().await;
+    match &result {
+        Ok(_) => info!("[Controller] Start OIDC login request completed successfully"),
+        Err(e) => info!("[Controller] Start OIDC login request failed: {:?}", e)
+    }
+    result
+}
+
+pub async fn callback(data: web::Json<OidcCallback>, service: web::Data<OidcService>) -> Result<HttpResponse, AppError> {
+    info!("[Controller] Received OIDC callback");
+    let result = service.callback(data.into_inner()).await;
+    match &result {
+        Ok(_) => info!("[Controller] OIDC callback request completed successfully"),
+        Err(e) => info!("[Controller] OIDC callback request failed: {:?}", e)
+    }
+    result
+}
+
+pub async fn start_link(claims: ClaimsToUserToken, service: web::Data<OidcService>) -> Result<HttpResponse, AppError> {
+    info!("[Controller] Received request to link identity for user: {}", claims.id);
+    let result = service.start_link(authenticated_user_id(&claims)?).await;
+    match &result {
+        Ok(_) => info!("[Controller] Link identity request completed successfully"),
+        Err(e) => info!("[Controller] Link identity request failed: {:?}", e)
+    }
+    result
+}
+
+pub async fn find_identities(claims: ClaimsToUserToken, service: web::Data<OidcService>) -> Result<HttpResponse, AppError> {
+    info!("[Controller] Received request to list identities for user: {}", claims.id);
+    let result = service.find_identities(authenticated_user_id(&claims)?).await;
+    match &result {
+        Ok(_) => info!("[Controller] Find identities request completed successfully"),
+        Err(e) => info!("[Controller] Find identities request failed: {:?}", e)
+    }
+    result
+}
+
+pub async fn unlink_identity(id: web::Path<Uuid>, claims: ClaimsToUserToken, service: web::Data<OidcService>) -> Result<HttpResponse, AppError> {
+    info!("[Controller] Received request to unlink identity with id: {}", id);
+    let result = service.unlink_identity(authenticated_user_id(&claims)?, id.into_inner()).await;
+    match &result {
+        Ok(_) => info!("[Controller] Unlink identity request completed successfully"),
+        Err(e) => info!("[Controller] Unlink identity request failed: {:?}", e)
+    }
+    result
+}
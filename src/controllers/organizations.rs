@@ -0,0 +1,143 @@
+use actix_web::{web, HttpResponse};
+use log::info;
+use uuid::Uuid;
+use crate::core::entities::auth::ClaimsToUserToken;
+use crate::core::entities::organization::{
+    AcceptOrganizationInvitation, CreateOrganization, CreateOrganizationInvitation, UpdateMembership, UpdateOrganization,
+};
+use crate::middleware::auth::authenticated_user_id;
+use crate::services::organization_service::OrganizationService;
+use crate::utils::errors::AppError;
+
+pub async fn create_organization(data: web::Json<CreateOrganization>, claims: ClaimsToUserToken, service: web::Data<OrganizationService>) -> Result<HttpResponse, AppError> {
+    info!("[Controller] Received request to create organization with slug: {}", data.slug);
+    let result = service.create_organization(authenticated_user_id(&claims)?, data.into_inner()).await;
+    match &result {
+        Ok(_) => info!("[Controller] Organization creation request completed successfully"),
+        Err(e) => info!("[Controller] Organization creation request failed: {:?}", e)
+    }
+    result
+}
+
+pub async fn find_user_organizations(claims: ClaimsToUserToken, service: web::Data<OrganizationService>) -> Result<HttpResponse, AppError> {
+    info!("[Controller] Received request to find organizations of the authenticated user");
+    let result = service.find_user_organizations(authenticated_user_id(&claims)?).await;
+    match &result {
+        Ok(_) => info!("[Controller] Find organizations request completed successfully"),
+        Err(e) => info!("[Controller] Find organizations request failed: {:?}", e)
+    }
+    result
+}
+
+pub async fn find_organization(id: web::Path<Uuid>, claims: ClaimsToUserToken, service: web::Data<OrganizationService>) -> Result<HttpResponse, AppError> {
+    info!("[Controller] Received request to find organization with id: {}", id);
+    let result = service.find_organization(id.into_inner(), authenticated_user_id(&claims)?).await;
+    match &result {
+        Ok(_) => info!("[Controller] Find organization request completed successfully"),
+        Err(e) => info!("[Controller] Find organization request failed: {:?}", e)
+    }
+    result
+}
+
+pub async fn update_organization(id: web::Path<Uuid>, data: web::Json<UpdateOrganization>, claims: ClaimsToUserToken, service: web::Data<OrganizationService>) -> Result<HttpResponse, AppError> {
+    info!("[Controller] Received request to update organization with id: {}", id);
+    let result = service.update_organization(id.into_inner(), authenticated_user_id(&claims)?, data.into_inner()).await;
+    match &result {
+        Ok(_) => info!("[Controller] Update organization request completed successfully"),
+        Err(e) => info!("[Controller] Update organization request failed: {:?}", e)
+    }
+    result
+}
+
+pub async fn delete_organization(id: web::Path<Uuid>, claims: ClaimsToUserToken, service: web::Data<OrganizationService>) -> Result<HttpResponse, AppError> {
+    info!("[Controller] Received request to delete organization with id: {}", id);
+    let result = service.delete_organization(id.into_inner(), authenticated_user_id(&claims)?).await;
+    match &result {
+        Ok(_) => info!("[Controller] Delete organization request completed successfully"),
+        Err(e) => info!("[Controller] Delete organization request failed: {:?}", e)
+    }
+    result
+}
+
+pub async fn switch_organization(id: web::Path<Uuid>, claims: ClaimsToUserToken, service: web::Data<OrganizationService>) -> Result<HttpResponse, AppError> {
+    info!("[Controller] Received request to switch to organization with id: {}", id);
+    let result = service.switch_organization(id.into_inner(), authenticated_user_id(&claims)?).await;
+    match &result {
+        Ok(_) => info!("[Controller] Switch organization request completed successfully"),
+        Err(e) => info!("[Controller] Switch organization request failed: {:?}", e)
+    }
+    result
+}
+
+pub async fn find_members(id: web::Path<Uuid>, claims: ClaimsToUserToken, service: web::Data<OrganizationService>) -> Result<HttpResponse, AppError> {
+    info!("[Controller] Received request to find members of organization with id: {}", id);
+    let result = service.find_members(id.into_inner(), authenticated_user_id(&claims)?).await;
+    match &result {
+        Ok(_) => info!("[Controller] Find members request completed successfully"),
+        Err(e) => info!("[Controller] Find members request failed: {:?}", e)
+    }
+    result
+}
+
+pub async fn update_member_role(path: web::Path<(Uuid, Uuid)>, data: web::Json<UpdateMembership>, claims: ClaimsToUserToken, service: web::Data<OrganizationService>) -> Result<HttpResponse, AppError> {
+    let (id, member_id) = path.into_inner();
+    info!("[Controller] Received request to change role of member {} in organization {}", member_id, id);
+    let result = service.update_member_role(id, authenticated_user_id(&claims)?, member_id, data.into_inner()).await;
+    match &result {
+        Ok(_) => info!("[Controller] Change member role request completed successfully"),
+        Err(e) => info!("[Controller] Change member role request failed: {:?}", e)
+    }
+    result
+}
+
+pub async fn remove_member(path: web::Path<(Uuid, Uuid)>, claims: ClaimsToUserToken, service: web::Data<OrganizationService>) -> Result<HttpResponse, AppError> {
+    let (id, member_id) = path.into_inner();
+    info!("[Controller] Received request to remove member {} from organization {}", member_id, id);
+    let result = service.remove_member(id, authenticated_user_id(&claims)?, member_id).await;
+    match &result {
+        Ok(_) => info!("[Controller] Remove member request completed successfully"),
+        Err(e) => info!("[Controller] Remove member request failed: {:?}", e)
+    }
+    result
+}
+
+pub async fn create_invitation(id: web::Path<Uuid>, data: web::Json<CreateOrganizationInvitation>, claims: ClaimsToUserToken, service: web::Data<OrganizationService>) -> Result<HttpResponse, AppError> {
+    info!("[Controller] Received request to invite {} to organization {}", data.email, id);
+    let result = service.create_invitation(id.into_inner(), authenticated_user_id(&claims)?, data.into_inner()).await;
+    match &result {
+        Ok(_) => info!("[Controller] Create invitation request completed successfully"),
+        Err(e) => info!("[Controller] Create invitation request failed: {:?}", e)
+    }
+    result
+}
+
+pub async fn find_invitations(id: web::Path<Uuid>, claims: ClaimsToUserToken, service: web::Data<OrganizationService>) -> Result<HttpResponse, AppError> {
+    info!("[Controller] Received request to find invitations of organization with id: {}", id);
+    let result = service.find_invitations(id.into_inner(), authenticated_user_id(&claims)?).await;
+    match &result {
+        Ok(_) => info!("[Controller] Find invitations request completed successfully"),
+        Err(e) => info!("[Controller] Find invitations request failed: {:?}", e)
+    }
+    result
+}
+
+pub async fn revoke_invitation(path: web::Path<(Uuid, Uuid)>, claims: ClaimsToUserToken, service: web::Data<OrganizationService>) -> Result<HttpResponse, AppError> {
+    let (id, invitation_id) = path.into_inner();
+    info!("[Controller] Received request to revoke invitation {} of organization {}", invitation_id, id);
+    let result = service.revoke_invitation(id, authenticated_user_id(&claims)?, invitation_id).await;
+    match &result {
+        Ok(_) => info!("[Controller] Revoke invitation request completed successfully"),
+        Err(e) => info!("[Controller] Revoke invitation request failed: {:?}", e)
+    }
+    result
+}
+
+pub async fn accept_invitation(data: web::Json<AcceptOrganizationInvitation>, claims: ClaimsToUserToken, service: web::Data<OrganizationService>) -> Result<HttpResponse, AppError> {
+    info!("[Controller] Received request to accept organization invitation");
+    let result = service.accept_invitation(authenticated_user_id(&claims)?, data.into_inner()).await;
+    match &result {
+        Ok(_) => info!("[Controller] Accept invitation request completed successfully"),
+        Err(e) => info!("[Controller] Accept invitation request failed: {:?}", e)
+    }
+    result
+}
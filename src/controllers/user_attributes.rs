@@ -0,0 +1,52 @@
+use actix_web::{web, HttpResponse};
+use log::info;
+use crate::core::entities::auth::ClaimsToUserToken;
+use crate::core::entities::user::ROLE_ADMIN;
+use crate::core::entities::user_attribute::{CreateUserAttributeDefinition, UpdateUserAttributeDefinition};
+use crate::middleware::auth::require_role;
+use crate::services::user_attribute_service::UserAttributeService;
+use crate::utils::errors::AppError;
+
+pub async fn create_attribute(data: web::Json<CreateUserAttributeDefinition>, claims: ClaimsToUserToken, service: web::Data<UserAttributeService>) -> Result<HttpResponse, AppError> {
+    info!("[Controller] Received request to create user attribute with key: {}", data.key);
+    require_role(&claims, &[ROLE_ADMIN])?;
+    let result = service.create_definition(data.into_inner()).await;
+    match &result {
+        Ok(_) => info!("[Controller] User attribute creation request completed successfully"),
+        Err(e) => info!("[Controller] User attribute creation request failed: {:?}", e)
+    }
+    result
+}
+
+// Leitura liberada a qualquer usuário autenticado, para que os clientes montem os formulários
+pub async fn find_attributes(service: web::Data<UserAttributeService>) -> Result<HttpResponse, AppError> {
+    info!("[Controller] Received request to find user attributes");
+    let result = service.find_definitions().await;
+    match &result {
+        Ok(_) => info!("[Controller] Find user attributes request completed successfully"),
+        Err(e) => info!("[Controller] Find user attributes request failed: {:?}", e)
+    }
+    result
+}
+
+pub async fn update_attribute(key: web::Path<String>, data: web::Json<UpdateUserAttributeDefinition>, claims: ClaimsToUserToken, service: web::Data<UserAttributeService>) -> Result<HttpResponse, AppError> {
+    info!("[Controller] Received request to update user attribute with key: {}", key);
+    require_role(&claims, &[ROLE_ADMIN])?;
+    let result = service.update_definition(key.into_inner(), data.into_inner()).await;
+    match &result {
+        Ok(_) => info!("[Controller] Update user attribute request completed successfully"),
+        Err(e) => info!("[Controller] Update user attribute request failed: {:?}", e)
+    }
+    result
+}
+
+pub async fn delete_attribute(key: web::Path<String>, claims: ClaimsToUserToken, service: web::Data<UserAttributeService>) -> Result<HttpResponse, AppError> {
+    info!("[Controller] Received request to delete user attribute with key: {}", key);
+    require_role(&claims, &[ROLE_ADMIN])?;
+    let result = service.delete_definition(key.into_inner()).await;
+    match &result {
+        Ok(_) => info!("[Controller] Delete user attribute request completed successfully"),
+        Err(e) => info!("[Controller] Delete user attribute request failed: {:?}", e)
+    }
+    result
+}
@@ -1,9 +1,17 @@
-use actix_web::{web, HttpResponse};
+use actix_multipart::Multipart;
+use actix_web::{http::header, web, HttpRequest, HttpResponse};
 use log::info;
 use uuid::Uuid;
+use crate::services::audit_service::AuditService;
 use crate::services::user_service::UserService;
-use crate::core::entities::user::{CreateUser, UpdateUser};
+use crate::services::avatar_service::AvatarService;
+use crate::services::privacy_service::PrivacyService;
+use crate::middleware::auth::{authenticated_user_id, organization_scope, require_role};
+use crate::core::entities::audit::AuditQuery;
+use crate::core::entities::auth::ClaimsToUserToken;
+use crate::core::entities::user::{AvatarQuery, ChangeUserRole, ChangeUserStatus, CreateUser, PatchUser, UpdatePasswordUser, UpdateUser, UserExportQuery, UserImportFormat, UserImportQuery, UserListQuery, UserSearchQuery, ROLE_ADMIN, ROLE_AUDITOR, STATUS_ACTIVE, STATUS_DEACTIVATED, STATUS_SUSPENDED};
 use crate::utils::errors::AppError;
+use crate::utils::etag::IfMatch;
 
 pub async fn create_user(data: web::Json<CreateUser>, service: web::Data<UserService>) -> Result<HttpResponse, AppError> {
     info!("[Controller] Received request to create user with email: {}", data.email);
@@ -15,9 +23,9 @@ pub async fn create_user(data: web::Json<CreateUser>, service: web::Data<UserSer
     result
 }
 
-pub async fn update_user(data: web::Json<UpdateUser>, id: web::Path<Uuid>, service: web::Data<UserService>) -> Result<HttpResponse, AppError> {
+pub async fn update_user(data: web::Json<UpdateUser>, id: web::Path<Uuid>, if_match: IfMatch, service: web::Data<UserService>) -> Result<HttpResponse, AppError> {
     info!("[Controller] Received request to update user with email: {}", data.email);
-    let result = service.update_user(data.into_inner(), id.into_inner()).await;
+    let result = service.update_user(data.into_inner(), id.into_inner(), if_match).await;
     match &result {
         Ok(_) => info!("[Controller] User updated request completed successfully"),
         Err(e) => info!("[Controller] User updated request failed: {:?}", e)
@@ -25,6 +33,16 @@ pub async fn update_user(data: web::Json<UpdateUser>, id: web::Path<Uuid>, servi
     result
 }
 
+pub async fn patch_user(data: web::Json<PatchUser>, id: web::Path<Uuid>, if_match: IfMatch, service: web::Data<UserService>) -> Result<HttpResponse, AppError> {
+    info!("[Controller] Received request to patch user with id: {}", id);
+    let result = service.patch_user(data.into_inner(), id.into_inner(), if_match).await;
+    match &result {
+        Ok(_) => info!("[Controller] User patch request completed successfully"),
+        Err(e) => info!("[Controller] User patch request failed: {:?}", e)
+    }
+    result
+}
+
 pub async fn find_user_by_id(id: web::Path<Uuid>, service: web::Data<UserService>) -> Result<HttpResponse, AppError> {
     info!("[Controller] Received request to find user by id with id: {}", id);
     let result = service.find_user_by_id(id.into_inner()).await;
@@ -35,9 +53,9 @@ pub async fn find_user_by_id(id: web::Path<Uuid>, service: web::Data<UserService
     result
 }
 
-pub async fn delete_user_by_id(id: web::Path<Uuid>, service: web::Data<UserService>) -> Result<HttpResponse, AppError> {
+pub async fn delete_user_by_id(id: web::Path<Uuid>, if_match: IfMatch, service: web::Data<UserService>) -> Result<HttpResponse, AppError> {
     info!("[Controller] Received request to delete user by id with id: {}", id);
-    let result = service.delete_user_by_id(id.into_inner()).await;
+    let result = service.delete_user_by_id(id.into_inner(), if_match).await;
     match &result {
         Ok(_) => info!("[Controller] Delete user by id request completed successfully"),
         Err(e) => info!("[Controller] Delete user by id request failed: {:?}", e)
@@ -45,12 +63,203 @@ pub async fn delete_user_by_id(id: web::Path<Uuid>, service: web::Data<UserServi
     result
 }
 
-pub async fn find_all_users(service: web::Data<UserService>) -> Result<HttpResponse, AppError> {
+pub async fn find_all_users(query: web::Query<UserListQuery>, claims: ClaimsToUserToken, service: web::Data<UserService>) -> Result<HttpResponse, AppError> {
     info!("[Controller] Received request to finda all users");
-    let result = service.find_all_users().await;
+    let result = service.find_all_users(query.into_inner(), organization_scope(&claims)?).await;
     match &result {
         Ok(_) => info!("[Controller] Find all users request completed successfully"),
         Err(e) => info!("[Controller] Find all users request failed: {:?}", e)
     }
     result
 }
+
+pub async fn search_users(query: web::Query<UserSearchQuery>, claims: ClaimsToUserToken, service: web::Data<UserService>) -> Result<HttpResponse, AppError> {
+    info!("[Controller] Received request to search users with term: {}", query.q);
+    let result = service.search_users(query.into_inner(), organization_scope(&claims)?).await;
+    match &result {
+        Ok(_) => info!("[Controller] Search users request completed successfully"),
+        Err(e) => info!("[Controller] Search users request failed: {:?}", e)
+    }
+    result
+}
+
+pub async fn find_deleted_users(query: web::Query<UserListQuery>, claims: ClaimsToUserToken, service: web::Data<UserService>) -> Result<HttpResponse, AppError> {
+    info!("[Controller] Received request to find deleted users");
+    require_role(&claims, &[ROLE_ADMIN])?;
+    let result = service.find_deleted_users(query.into_inner(), organization_scope(&claims)?).await;
+    match &result {
+        Ok(_) => info!("[Controller] Find deleted users request completed successfully"),
+        Err(e) => info!("[Controller] Find deleted users request failed: {:?}", e)
+    }
+    result
+}
+
+pub async fn restore_user(id: web::Path<Uuid>, claims: ClaimsToUserToken, service: web::Data<UserService>) -> Result<HttpResponse, AppError> {
+    info!("[Controller] Received request to restore user with id: {}", id);
+    require_role(&claims, &[ROLE_ADMIN])?;
+    let result = service.restore_user(id.into_inner()).await;
+    match &result {
+        Ok(_) => info!("[Controller] Restore user request completed successfully"),
+        Err(e) => info!("[Controller] Restore user request failed: {:?}", e)
+    }
+    result
+}
+
+pub async fn suspend_user(id: web::Path<Uuid>, data: web::Json<ChangeUserStatus>, claims: ClaimsToUserToken, service: web::Data<UserService>) -> Result<HttpResponse, AppError> {
+    info!("[Controller] Received request to suspend user with id: {}", id);
+    change_user_status(id.into_inner(), STATUS_SUSPENDED, data.into_inner(), &claims, &service).await
+}
+
+pub async fn reactivate_user(id: web::Path<Uuid>, data: web::Json<ChangeUserStatus>, claims: ClaimsToUserToken, service: web::Data<UserService>) -> Result<HttpResponse, AppError> {
+    info!("[Controller] Received request to reactivate user with id: {}", id);
+    change_user_status(id.into_inner(), STATUS_ACTIVE, data.into_inner(), &claims, &service).await
+}
+
+pub async fn deactivate_user(id: web::Path<Uuid>, data: web::Json<ChangeUserStatus>, claims: ClaimsToUserToken, service: web::Data<UserService>) -> Result<HttpResponse, AppError> {
+    info!("[Controller] Received request to deactivate user with id: {}", id);
+    change_user_status(id.into_inner(), STATUS_DEACTIVATED, data.into_inner(), &claims, &service).await
+}
+
+async fn change_user_status(id: Uuid, to: &'static str, data: ChangeUserStatus, claims: &ClaimsToUserToken, service: &UserService) -> Result<HttpResponse, AppError> {
+    require_role(claims, &[ROLE_ADMIN])?;
+    let result = service.change_status(id, to, data, authenticated_user_id(claims)?).await;
+    match &result {
+        Ok(_) => info!("[Controller] Change user status request completed successfully"),
+        Err(e) => info!("[Controller] Change user status request failed: {:?}", e)
+    }
+    result
+}
+
+// Somente o próprio usuário, que precisa informar a senha atual
+pub async fn change_password(id: web::Path<Uuid>, data: web::Json<UpdatePasswordUser>, claims: ClaimsToUserToken, service: web::Data<UserService>) -> Result<HttpResponse, AppError> {
+    info!("[Controller] Received request to change password of user with id: {}", id);
+    let id = id.into_inner();
+    if authenticated_user_id(&claims)? != id {
+        return Err(AppError::Forbidden("You can only change your own password".into()));
+    }
+    let result = service.change_password(id, data.into_inner()).await;
+    match &result {
+        Ok(_) => info!("[Controller] Change password request completed successfully"),
+        Err(e) => info!("[Controller] Change password request failed: {:?}", e)
+    }
+    result
+}
+
+pub async fn change_role(id: web::Path<Uuid>, data: web::Json<ChangeUserRole>, claims: ClaimsToUserToken, service: web::Data<UserService>) -> Result<HttpResponse, AppError> {
+    info!("[Controller] Received request to change role of user with id: {}", id);
+    require_role(&claims, &[ROLE_ADMIN])?;
+    let result = service.change_role(id.into_inner(), data.into_inner(), authenticated_user_id(&claims)?).await;
+    match &result {
+        Ok(_) => info!("[Controller] Change role request completed successfully"),
+        Err(e) => info!("[Controller] Change role request failed: {:?}", e)
+    }
+    result
+}
+
+pub async fn find_user_history(id: web::Path<Uuid>, query: web::Query<AuditQuery>, claims: ClaimsToUserToken, service: web::Data<AuditService>) -> Result<HttpResponse, AppError> {
+    info!("[Controller] Received request to find audit history for user with id: {}", id);
+    require_role(&claims, &[ROLE_ADMIN, ROLE_AUDITOR])?;
+    let result = service.find_user_history(id.into_inner(), query.into_inner(), claims.organization_id).await;
+    match &result {
+        Ok(_) => info!("[Controller] Find user history request completed successfully"),
+        Err(e) => info!("[Controller] Find user history request failed: {:?}", e)
+    }
+    result
+}
+
+pub async fn find_status_history(id: web::Path<Uuid>, claims: ClaimsToUserToken, service: web::Data<UserService>) -> Result<HttpResponse, AppError> {
+    info!("[Controller] Received request to find status history for user with id: {}", id);
+    require_role(&claims, &[ROLE_ADMIN])?;
+    let result = service.find_status_history(id.into_inner()).await;
+    match &result {
+        Ok(_) => info!("[Controller] Find status history request completed successfully"),
+        Err(e) => info!("[Controller] Find status history request failed: {:?}", e)
+    }
+    result
+}
+
+pub async fn import_users(req: HttpRequest, body: web::Bytes, query: web::Query<UserImportQuery>, claims: ClaimsToUserToken, service: web::Data<UserService>) -> Result<HttpResponse, AppError> {
+    info!("[Controller] Received request to import users");
+    require_role(&claims, &[ROLE_ADMIN])?;
+    let result = service.import_users(import_format(&req)?, body, query.into_inner()).await;
+    match &result {
+        Ok(_) => info!("[Controller] Import users request completed successfully"),
+        Err(e) => info!("[Controller] Import users request failed: {:?}", e)
+    }
+    result
+}
+
+fn import_format(req: &HttpRequest) -> Result<UserImportFormat, AppError> {
+    let content_type = req.headers()
+        .get(header::CONTENT_TYPE)
+        .and_then(|h| h.to_str().ok())
+        .unwrap_or_default();
+
+    match content_type.split(';').next().unwrap_or_default().trim().to_ascii_lowercase().as_str() {
+        "text/csv" => Ok(UserImportFormat::Csv),
+        "application/json" => Ok(UserImportFormat::Json),
+        _ => Err(AppError::BadRequest("Content-Type must be text/csv or application/json".into())),
+    }
+}
+
+pub async fn export_users(query: web::Query<UserExportQuery>, claims: ClaimsToUserToken, service: web::Data<UserService>) -> Result<HttpResponse, AppError> {
+    info!("[Controller] Received request to export users as {:?}", query.format);
+    let result = service.export_users(query.into_inner(), organization_scope(&claims)?).await;
+    match &result {
+        Ok(_) => info!("[Controller] Export users request started streaming"),
+        Err(e) => info!("[Controller] Export users request failed: {:?}", e)
+    }
+    result
+}
+
+pub async fn upload_avatar(id: web::Path<Uuid>, payload: Multipart, claims: ClaimsToUserToken, service: web::Data<AvatarService>) -> Result<HttpResponse, AppError> {
+    info!("[Controller] Received request to upload avatar for user with id: {}", id);
+    let id = id.into_inner();
+    // Apenas o próprio usuário ou um administrador pode trocar o avatar
+    if authenticated_user_id(&claims)? != id {
+        require_role(&claims, &[ROLE_ADMIN])?;
+    }
+    let result = service.upload_avatar(id, payload).await;
+    match &result {
+        Ok(_) => info!("[Controller] Upload avatar request completed successfully"),
+        Err(e) => info!("[Controller] Upload avatar request failed: {:?}", e)
+    }
+    result
+}
+
+pub async fn find_avatar(req: HttpRequest, id: web::Path<Uuid>, query: web::Query<AvatarQuery>, service: web::Data<AvatarService>) -> Result<HttpResponse, AppError> {
+    info!("[Controller] Received request to find avatar for user with id: {}", id);
+    let if_none_match = req.headers().get(header::IF_NONE_MATCH).and_then(|h| h.to_str().ok());
+    let result = service.find_avatar(id.into_inner(), query.size, if_none_match).await;
+    match &result {
+        Ok(_) => info!("[Controller] Find avatar request completed successfully"),
+        Err(e) => info!("[Controller] Find avatar request failed: {:?}", e)
+    }
+    result
+}
+
+// O próprio titular pode pedir uma cópia dos seus dados
+pub async fn export_user_data(id: web::Path<Uuid>, claims: ClaimsToUserToken, service: web::Data<PrivacyService>) -> Result<HttpResponse, AppError> {
+    info!("[Controller] Received request to export data of user with id: {}", id);
+    let id = id.into_inner();
+    if authenticated_user_id(&claims)? != id {
+        require_role(&claims, &[ROLE_ADMIN])?;
+    }
+    let result = service.export_user_data(id).await;
+    match &result {
+        Ok(_) => info!("[Controller] User data export request completed successfully"),
+        Err(e) => info!("[Controller] User data export request failed: {:?}", e)
+    }
+    result
+}
+
+pub async fn erase_user(id: web::Path<Uuid>, claims: ClaimsToUserToken, service: web::Data<PrivacyService>) -> Result<HttpResponse, AppError> {
+    info!("[Controller] Received request to erase personal data of user with id: {}", id);
+    require_role(&claims, &[ROLE_ADMIN])?;
+    let result = service.erase_user(id.into_inner(), authenticated_user_id(&claims)?).await;
+    match &result {
+        Ok(_) => info!("[Controller] User erasure request completed successfully"),
+        Err(e) => info!("[Controller] User erasure request failed: {:?}", e)
+    }
+    result
+}
@@ -0,0 +1,10 @@
+use async_trait::async_trait;
+use futures::stream::BoxStream;
+use crate::core::entities::audit::{AuditChainLink, AuditEntry, AuditFilters};
+
+#[async_trait]
+pub trait AuditRepository: Send + Sync + 'static {
+    async fn find_chain_links(&self, after_seq: i64, limit: i64) -> Result<Vec<AuditChainLink>, sqlx::Error>;
+    async fn find_entries(&self, filters: &AuditFilters) -> Result<Vec<AuditEntry>, sqlx::Error>;
+    fn stream_entries(&self, filters: AuditFilters) -> BoxStream<'static, Result<AuditEntry, sqlx::Error>>;
+}
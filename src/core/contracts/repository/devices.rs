@@ -0,0 +1,13 @@
+use async_trait::async_trait;
+use uuid::Uuid;
+use crate::core::entities::audit::{AuditTrail, NewAuditEntry};
+use crate::core::entities::device::{CreateDeviceAlert, DeviceAlert, DeviceAlertReport, DeviceFingerprint, DeviceSighting};
+
+#[async_trait]
+pub trait DeviceRepository: Send + Sync + 'static {
+    async fn record_device(&self, user_id: Uuid, fingerprint: &DeviceFingerprint) -> Result<DeviceSighting, sqlx::Error>;
+    async fn create_alert(&self, alert: CreateDeviceAlert) -> Result<DeviceAlert, sqlx::Error>;
+    async fn find_alert_by_token(&self, token_hash: &str) -> Result<Option<DeviceAlert>, sqlx::Error>;
+    async fn report_alert(&self, alert: &DeviceAlert, audit: AuditTrail<DeviceAlertReport>) -> Result<Option<DeviceAlertReport>, sqlx::Error>;
+    async fn reset_password(&self, alert: &DeviceAlert, password_hash: &str, history_size: i64, audit: Vec<NewAuditEntry>) -> Result<bool, sqlx::Error>;
+}
@@ -0,0 +1,12 @@
+use async_trait::async_trait;
+use crate::core::entities::audit::NewAuditEntry;
+use crate::core::entities::email_change::{CreateEmailChange, EmailChange};
+
+#[async_trait]
+pub trait EmailChangeRepository: Send + Sync + 'static {
+    async fn create_email_change(&self, data: CreateEmailChange) -> Result<EmailChange, sqlx::Error>;
+    async fn find_by_confirm_token(&self, token_hash: &str) -> Result<Option<EmailChange>, sqlx::Error>;
+    async fn find_by_revert_token(&self, token_hash: &str) -> Result<Option<EmailChange>, sqlx::Error>;
+    async fn confirm_email_change(&self, change: &EmailChange, audit: Vec<NewAuditEntry>) -> Result<bool, sqlx::Error>;
+    async fn revert_email_change(&self, change: &EmailChange, audit: Vec<NewAuditEntry>) -> Result<bool, sqlx::Error>;
+}
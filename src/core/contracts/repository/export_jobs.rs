@@ -0,0 +1,17 @@
+use async_trait::async_trait;
+use chrono::NaiveDateTime;
+use uuid::Uuid;
+use crate::core::entities::export_job::{ExportFilters, ExportJob};
+
+#[async_trait]
+pub trait ExportJobRepository: Send + Sync + 'static {
+    async fn create_job(&self, requested_by: Uuid, organization_id: Option<Uuid>, format: &str, filters: ExportFilters) -> Result<ExportJob, sqlx::Error>;
+    async fn find_job_by_id(&self, id: Uuid) -> Result<ExportJob, sqlx::Error>;
+    async fn start_job(&self, id: Uuid, total_rows: i64) -> Result<(), sqlx::Error>;
+    async fn update_progress(&self, id: Uuid, processed_rows: i64) -> Result<(), sqlx::Error>;
+    async fn complete_job(&self, id: Uuid, processed_rows: i64, file_path: &str, expires_at: NaiveDateTime) -> Result<(), sqlx::Error>;
+    async fn fail_job(&self, id: Uuid, error: &str) -> Result<(), sqlx::Error>;
+    async fn fail_interrupted_jobs(&self) -> Result<Vec<ExportJob>, sqlx::Error>;
+    async fn find_expired_jobs(&self, now: NaiveDateTime) -> Result<Vec<ExportJob>, sqlx::Error>;
+    async fn expire_job(&self, id: Uuid) -> Result<(), sqlx::Error>;
+}
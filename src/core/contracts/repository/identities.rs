@@ -0,0 +1,14 @@
+use async_trait::async_trait;
+use uuid::Uuid;
+use crate::core::entities::audit::NewAuditEntry;
+use crate::core::entities::identity::{CreateUserIdentity, OidcLoginState, UserIdentity};
+
+#[async_trait]
+pub trait IdentityRepository: Send + Sync + 'static {
+    async fn create_identity(&self, data: CreateUserIdentity) -> Result<UserIdentity, sqlx::Error>;
+    async fn find_identity_by_subject(&self, issuer: &str, subject: &str) -> Result<UserIdentity, sqlx::Error>;
+    async fn find_identities_by_user(&self, user_id: Uuid) -> Result<Vec<UserIdentity>, sqlx::Error>;
+    async fn delete_identity(&self, id: Uuid, user_id: Uuid, audit: Vec<NewAuditEntry>) -> Result<bool, sqlx::Error>;
+    async fn create_login_state(&self, data: OidcLoginState) -> Result<(), sqlx::Error>;
+    async fn take_login_state(&self, state_hash: &str) -> Result<OidcLoginState, sqlx::Error>;
+}
@@ -0,0 +1,12 @@
+use async_trait::async_trait;
+use chrono::NaiveDateTime;
+use uuid::Uuid;
+use crate::config::request_context::RequestContext;
+use crate::core::entities::login_attempt::{LoginActivityCursor, LoginAttempt, NewLoginAttempt};
+
+#[async_trait]
+pub trait LoginAttemptRepository: Send + Sync + 'static {
+    async fn create_attempt(&self, attempt: NewLoginAttempt, context: &RequestContext) -> Result<(), sqlx::Error>;
+    async fn find_attempts_by_user(&self, user_id: Uuid, after: Option<LoginActivityCursor>, limit: i64) -> Result<Vec<LoginAttempt>, sqlx::Error>;
+    async fn delete_attempts_older_than(&self, cutoff: NaiveDateTime) -> Result<u64, sqlx::Error>;
+}
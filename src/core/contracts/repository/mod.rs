@@ -1 +1,13 @@
 pub mod users;
+pub mod passkeys;
+pub mod oauth;
+pub mod identities;
+pub mod export_jobs;
+pub mod organizations;
+pub mod user_attributes;
+pub mod user_invitations;
+pub mod privacy;
+pub mod email_changes;
+pub mod audits;
+pub mod login_attempts;
+pub mod devices;
@@ -0,0 +1,16 @@
+use async_trait::async_trait;
+use uuid::Uuid;
+use crate::core::entities::oauth::{NewOAuthClient, OAuthAuthorizationCode, OAuthClient, OAuthRefreshToken};
+
+#[async_trait]
+pub trait OAuthRepository: Send + Sync + 'static {
+    async fn create_client(&self, data: NewOAuthClient) -> Result<OAuthClient, sqlx::Error>;
+    async fn find_client_by_client_id(&self, client_id: &str) -> Result<OAuthClient, sqlx::Error>;
+    async fn find_clients_by_owner(&self, owner_id: Uuid) -> Result<Vec<OAuthClient>, sqlx::Error>;
+    async fn delete_client(&self, id: Uuid, owner_id: Uuid) -> Result<bool, sqlx::Error>;
+    async fn create_authorization_code(&self, data: OAuthAuthorizationCode) -> Result<(), sqlx::Error>;
+    async fn take_authorization_code(&self, code_hash: &str) -> Result<OAuthAuthorizationCode, sqlx::Error>;
+    async fn create_refresh_token(&self, token_hash: &str, client_id: &str, user_id: Uuid, scope: &str, ttl_seconds: i64) -> Result<(), sqlx::Error>;
+    async fn find_refresh_token(&self, token_hash: &str) -> Result<OAuthRefreshToken, sqlx::Error>;
+    async fn revoke_refresh_token(&self, token_hash: &str, client_id: &str) -> Result<bool, sqlx::Error>;
+}
@@ -0,0 +1,34 @@
+use async_trait::async_trait;
+use chrono::NaiveDateTime;
+use uuid::Uuid;
+use crate::core::entities::organization::{
+    CreateOrganization, MembershipChange, Organization, OrganizationInvitation, OrganizationMember, OrganizationMembership,
+    UserOrganization,
+};
+
+#[async_trait]
+pub trait OrganizationRepository: Send + Sync + 'static {
+    async fn create_organization(&self, data: CreateOrganization, owner_id: Uuid) -> Result<Organization, sqlx::Error>;
+    async fn find_organization_by_id(&self, id: Uuid) -> Result<Organization, sqlx::Error>;
+    async fn find_organizations_by_user(&self, user_id: Uuid) -> Result<Vec<UserOrganization>, sqlx::Error>;
+    async fn update_organization(&self, id: Uuid, name: String) -> Result<Organization, sqlx::Error>;
+    async fn delete_organization(&self, id: Uuid) -> Result<bool, sqlx::Error>;
+    async fn find_membership(&self, organization_id: Uuid, user_id: Uuid) -> Result<Option<OrganizationMembership>, sqlx::Error>;
+    async fn find_default_organization(&self, user_id: Uuid) -> Result<Option<Uuid>, sqlx::Error>;
+    async fn find_members(&self, organization_id: Uuid) -> Result<Vec<OrganizationMember>, sqlx::Error>;
+    async fn update_membership_role(&self, organization_id: Uuid, user_id: Uuid, role: &str) -> Result<MembershipChange<OrganizationMembership>, sqlx::Error>;
+    async fn delete_membership(&self, organization_id: Uuid, user_id: Uuid) -> Result<MembershipChange<()>, sqlx::Error>;
+    async fn create_invitation(
+        &self,
+        organization_id: Uuid,
+        email: &str,
+        role: &str,
+        token_hash: &str,
+        invited_by: Uuid,
+        expires_at: NaiveDateTime,
+    ) -> Result<OrganizationInvitation, sqlx::Error>;
+    async fn find_pending_invitations(&self, organization_id: Uuid) -> Result<Vec<OrganizationInvitation>, sqlx::Error>;
+    async fn find_invitation_by_token(&self, token_hash: &str) -> Result<Option<OrganizationInvitation>, sqlx::Error>;
+    async fn accept_invitation(&self, invitation: &OrganizationInvitation, user_id: Uuid) -> Result<OrganizationMembership, sqlx::Error>;
+    async fn delete_invitation(&self, id: Uuid, organization_id: Uuid) -> Result<bool, sqlx::Error>;
+}
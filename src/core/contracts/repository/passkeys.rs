@@ -0,0 +1,15 @@
+use async_trait::async_trait;
+use uuid::Uuid;
+use crate::core::entities::audit::{AuditTrail, NewAuditEntry};
+use crate::core::entities::passkey::{CreatePasskeyCredential, PasskeyChallenge, PasskeyCredential};
+
+#[async_trait]
+pub trait PasskeyRepository: Send + Sync + 'static {
+    async fn create_challenge(&self, user_id: Option<Uuid>, challenge: String, ceremony: &str, ttl_seconds: i64) -> Result<PasskeyChallenge, sqlx::Error>;
+    async fn take_challenge(&self, id: Uuid, ceremony: &str) -> Result<PasskeyChallenge, sqlx::Error>;
+    async fn create_credential(&self, data: CreatePasskeyCredential, audit: AuditTrail<PasskeyCredential>) -> Result<PasskeyCredential, sqlx::Error>;
+    async fn find_credentials_by_user(&self, user_id: Uuid) -> Result<Vec<PasskeyCredential>, sqlx::Error>;
+    async fn find_credential_by_credential_id(&self, credential_id: &str) -> Result<PasskeyCredential, sqlx::Error>;
+    async fn update_sign_count(&self, id: Uuid, sign_count: i64) -> Result<(), sqlx::Error>;
+    async fn delete_credential(&self, id: Uuid, user_id: Uuid, audit: Vec<NewAuditEntry>) -> Result<bool, sqlx::Error>;
+}
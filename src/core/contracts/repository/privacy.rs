@@ -0,0 +1,11 @@
+use async_trait::async_trait;
+use uuid::Uuid;
+use crate::core::entities::audit::AuditTrail;
+use crate::core::entities::privacy::{ExportedProfile, UserDataExport, UserErasureReport};
+
+#[async_trait]
+pub trait PrivacyRepository: Send + Sync + 'static {
+    async fn find_profile(&self, id: Uuid) -> Result<Option<ExportedProfile>, sqlx::Error>;
+    async fn find_user_data(&self, id: Uuid) -> Result<Option<UserDataExport>, sqlx::Error>;
+    async fn erase_user(&self, id: Uuid, password_hash: &str, erased_by: Uuid, audit: AuditTrail<UserErasureReport>) -> Result<Option<UserErasureReport>, sqlx::Error>;
+}
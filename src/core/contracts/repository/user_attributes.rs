@@ -0,0 +1,14 @@
+use async_trait::async_trait;
+use crate::core::entities::audit::AuditTrail;
+use crate::core::entities::user_attribute::{
+    CreateUserAttributeDefinition, RemovedUserAttribute, UpdateUserAttributeDefinition, UserAttributeDefinition,
+};
+
+#[async_trait]
+pub trait UserAttributeRepository: Send + Sync + 'static {
+    async fn create_definition(&self, data: CreateUserAttributeDefinition) -> Result<UserAttributeDefinition, sqlx::Error>;
+    async fn find_definitions(&self) -> Result<Vec<UserAttributeDefinition>, sqlx::Error>;
+    async fn find_definition(&self, key: &str) -> Result<Option<UserAttributeDefinition>, sqlx::Error>;
+    async fn update_definition(&self, key: &str, data: UpdateUserAttributeDefinition) -> Result<UserAttributeDefinition, sqlx::Error>;
+    async fn delete_definition(&self, key: &str, audit: AuditTrail<Vec<RemovedUserAttribute>>) -> Result<bool, sqlx::Error>;
+}
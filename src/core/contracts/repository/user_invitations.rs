@@ -0,0 +1,26 @@
+use async_trait::async_trait;
+use chrono::NaiveDateTime;
+use uuid::Uuid;
+use crate::core::entities::audit::AuditTrail;
+use crate::core::entities::user::UserDataCreated;
+use crate::core::entities::user_invitation::UserInvitation;
+
+#[async_trait]
+pub trait UserInvitationRepository: Send + Sync + 'static {
+    async fn create_invitation(
+        &self,
+        email: &str,
+        role: &str,
+        organization_id: Option<Uuid>,
+        organization_role: Option<String>,
+        invited_by: Uuid,
+        expires_at: NaiveDateTime,
+    ) -> Result<UserInvitation, sqlx::Error>;
+    async fn find_invitations(&self) -> Result<Vec<UserInvitation>, sqlx::Error>;
+    async fn find_invitation_by_id(&self, id: Uuid) -> Result<Option<UserInvitation>, sqlx::Error>;
+    async fn resend_invitation(&self, id: Uuid, expires_at: NaiveDateTime) -> Result<Option<UserInvitation>, sqlx::Error>;
+    async fn revoke_invitation(&self, id: Uuid) -> Result<Option<UserInvitation>, sqlx::Error>;
+    async fn claim_invitation(&self, invitation: &UserInvitation) -> Result<bool, sqlx::Error>;
+    async fn release_invitation(&self, id: Uuid) -> Result<(), sqlx::Error>;
+    async fn complete_invitation(&self, invitation: &UserInvitation, user_id: Uuid, audit: AuditTrail<UserDataCreated>) -> Result<(), sqlx::Error>;
+}
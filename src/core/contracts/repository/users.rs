@@ -1,15 +1,35 @@
 use async_trait::async_trait;
+use chrono::NaiveDateTime;
+use futures::stream::BoxStream;
 use uuid::Uuid;
-use crate::core::entities::user::{CreateUser, UpdatePasswordUser, UpdateUser, UserDataCreated, UserCompleteData};
+use crate::core::entities::audit::{AuditTrail, NewAuditEntry};
+use crate::core::entities::user::{CreateUser, PatchUser, UpdateUser, UserDataCreated, UserCompleteData, UserListParams, UserSearchRow, UserSessionState, UserStatusChange};
 
 #[async_trait]
 pub trait UserRepository: Send + Sync + 'static {
-    async fn create_user(&self, data: CreateUser) -> Result<UserDataCreated, sqlx::Error>;
-    async fn update_user(&self, id: Uuid, data: UpdateUser) -> Result<UserDataCreated, sqlx::Error>;
-    async fn update_password_user(&self, id: Uuid, data: UpdatePasswordUser) -> Result<UserDataCreated, sqlx::Error>;
-    async fn find_all_users(&self) -> Result<Vec<UserDataCreated>, sqlx::Error>;
+    async fn create_user(&self, data: CreateUser, audit: AuditTrail<UserDataCreated>) -> Result<UserDataCreated, sqlx::Error>;
+    async fn import_users(&self, users: Vec<CreateUser>, skip_conflicts: bool, audit: AuditTrail<Vec<UserDataCreated>>) -> Result<Vec<UserDataCreated>, sqlx::Error>;
+    async fn find_existing_emails(&self, emails: &[String]) -> Result<Vec<String>, sqlx::Error>;
+    async fn update_user(&self, id: Uuid, data: UpdateUser, expected_versions: Option<Vec<i64>>, audit: AuditTrail<UserDataCreated>) -> Result<UserDataCreated, sqlx::Error>;
+    async fn patch_user(&self, id: Uuid, data: PatchUser, expected_versions: Option<Vec<i64>>, audit: AuditTrail<UserDataCreated>) -> Result<UserDataCreated, sqlx::Error>;
+    async fn update_password_user(&self, id: Uuid, password_hash: &str, history_size: i64, audit: Vec<NewAuditEntry>) -> Result<UserDataCreated, sqlx::Error>;
+    async fn find_password_hash(&self, id: Uuid) -> Result<Option<String>, sqlx::Error>;
+    async fn find_password_history(&self, id: Uuid, limit: i64) -> Result<Vec<String>, sqlx::Error>;
+    async fn change_role(&self, id: Uuid, role: &str, audit: AuditTrail<UserDataCreated>) -> Result<UserDataCreated, sqlx::Error>;
+    async fn find_all_users(&self, params: &UserListParams) -> Result<Vec<UserDataCreated>, sqlx::Error>;
+    async fn count_users(&self, params: &UserListParams) -> Result<i64, sqlx::Error>;
+    fn stream_users(&self, params: UserListParams) -> BoxStream<'static, Result<UserDataCreated, sqlx::Error>>;
+    async fn search_users(&self, term: &str, limit: i64, organization_id: Option<Uuid>) -> Result<Vec<UserSearchRow>, sqlx::Error>;
     async fn find_user_by_id(&self, id: Uuid) -> Result<UserDataCreated, sqlx::Error>;
     async fn find_user_by_email(&self, email: String) -> Result<UserCompleteData, sqlx::Error>;
-    async fn delete_user(&self, id: Uuid) -> Result<bool, sqlx::Error>;
+    async fn find_complete_user_by_id(&self, id: Uuid) -> Result<UserCompleteData, sqlx::Error>;
+    async fn set_avatar(&self, id: Uuid, updated_at: NaiveDateTime) -> Result<UserDataCreated, sqlx::Error>;
+    async fn find_user_status(&self, id: Uuid) -> Result<Option<String>, sqlx::Error>;
+    async fn find_session_state(&self, id: Uuid) -> Result<Option<UserSessionState>, sqlx::Error>;
+    async fn change_status(&self, id: Uuid, from: &str, to: &str, reason: &str, changed_by: Uuid, audit: Vec<NewAuditEntry>) -> Result<UserDataCreated, sqlx::Error>;
+    async fn find_status_history(&self, id: Uuid) -> Result<Vec<UserStatusChange>, sqlx::Error>;
+    async fn delete_user(&self, id: Uuid, expected_versions: Option<Vec<i64>>, audit: Vec<NewAuditEntry>) -> Result<bool, sqlx::Error>;
+    async fn restore_user(&self, id: Uuid, audit: AuditTrail<UserDataCreated>) -> Result<UserDataCreated, sqlx::Error>;
+    async fn purge_deleted_users(&self, deleted_before: NaiveDateTime) -> Result<u64, sqlx::Error>;
     async fn email_exists_for_other_user(&self, email: &str, id: Uuid) -> Result<bool, sqlx::Error>;
 }
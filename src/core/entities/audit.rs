@@ -0,0 +1,194 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+use sqlx::types::Json;
+use sqlx::FromRow;
+use uuid::Uuid;
+use crate::utils::crypto::hmac_sha256_hex;
+
+pub const AUDIT_RESOURCE_USER: &str = "user";
+pub const AUDIT_RESOURCE_PASSKEY: &str = "passkey";
+pub const AUDIT_RESOURCE_IDENTITY: &str = "identity";
+
+pub const AUDIT_USER_CREATED: &str = "user.created";
+pub const AUDIT_USER_UPDATED: &str = "user.updated";
+pub const AUDIT_USER_DELETED: &str = "user.deleted";
+pub const AUDIT_USER_RESTORED: &str = "user.restored";
+pub const AUDIT_USER_STATUS_CHANGED: &str = "user.status_changed";
+pub const AUDIT_USER_PASSWORD_CHANGED: &str = "user.password_changed";
+pub const AUDIT_USER_ROLE_CHANGED: &str = "user.role_changed";
+pub const AUDIT_USER_EMAIL_CHANGED: &str = "user.email_changed";
+pub const AUDIT_USER_ERASED: &str = "user.erased";
+pub const AUDIT_USER_SESSIONS_REVOKED: &str = "user.sessions_revoked";
+pub const AUDIT_PASSKEY_REGISTERED: &str = "passkey.registered";
+pub const AUDIT_PASSKEY_DELETED: &str = "passkey.deleted";
+pub const AUDIT_IDENTITY_UNLINKED: &str = "identity.unlinked";
+
+// Hash anterior da primeira entrada da cadeia
+pub const AUDIT_GENESIS_HASH: &str = "0000000000000000000000000000000000000000000000000000000000000000";
+
+// Metadados que mudam em toda alteração e não dizem nada sobre ela
+const IGNORED_DIFF_FIELDS: [&str; 2] = ["updated_at", "version"];
+
+// Dados pessoais: a trilha é imutável e sobrevive à anonimização, então guarda apenas o HMAC do valor com a
+// chave do titular. A anonimização e o expurgo destroem a chave, e os valores deixam de ser verificáveis
+const PSEUDONYMIZED_FIELDS: [&str; 3] = ["full_name", "email", "attributes"];
+
+const AUDIT_REDACTED: &str = "[REDACTED]";
+
+#[derive(Serialize, FromRow, Debug, Clone)]
+pub struct AuditEntry {
+    pub seq: i64,
+    pub id: Uuid,
+    pub occurred_at: NaiveDateTime,
+    pub actor_id: Option<Uuid>,
+    pub organization_id: Option<Uuid>,
+    pub request_id: Option<String>,
+    pub ip: Option<String>,
+    pub action: String,
+    pub resource_type: String,
+    pub resource_id: Option<Uuid>,
+    pub changes: Json<Value>,
+    pub prev_hash: String,
+    pub hash: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct NewAuditEntry {
+    pub action: &'static str,
+    pub resource_type: &'static str,
+    pub resource_id: Option<Uuid>,
+    pub changes: Value,
+}
+
+// Monta as entradas a partir do resultado da alteração; o repositório as grava na mesma transação
+pub type AuditTrail<T> = Box<dyn FnOnce(&T) -> Vec<NewAuditEntry> + Send>;
+
+#[derive(FromRow, Debug)]
+pub struct AuditChainLink {
+    pub seq: i64,
+    pub prev_hash: String,
+    pub hash: String,
+    // Hash recalculado pelo banco a partir do conteúdo atual da linha
+    pub digest: String,
+}
+
+#[derive(Serialize, Debug)]
+pub struct AuditChainVerification {
+    pub valid: bool,
+    pub checked: i64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_hash: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub broken_at_seq: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+// Diferença campo a campo entre dois objetos JSON; `Value::Null` representa o recurso inexistente
+pub fn audit_diff(before: &Value, after: &Value) -> Value {
+    let empty = Map::new();
+    let before = before.as_object().unwrap_or(&empty);
+    let after = after.as_object().unwrap_or(&empty);
+
+    let mut changes = Map::new();
+    for key in before.keys().chain(after.keys().filter(|key| !before.contains_key(*key))) {
+        if IGNORED_DIFF_FIELDS.contains(&key.as_str()) {
+            continue;
+        }
+        let old = before.get(key).unwrap_or(&Value::Null);
+        let new = after.get(key).unwrap_or(&Value::Null);
+        if old != new {
+            changes.insert(key.clone(), json!({ "before": old, "after": new }));
+        }
+    }
+    Value::Object(changes)
+}
+
+impl NewAuditEntry {
+    // Titular dos dados pessoais da entrada: só entradas sobre um usuário têm a chave para pseudonimizá-los
+    pub fn data_subject(&self) -> Option<Uuid> {
+        self.resource_id.filter(|_| self.resource_type == AUDIT_RESOURCE_USER)
+    }
+
+    pub fn has_personal_data(&self) -> bool {
+        PSEUDONYMIZED_FIELDS.iter().any(|field| {
+            self.changes.get(field).is_some_and(|change| [&change["before"], &change["after"]].into_iter().any(is_personal_value))
+        })
+    }
+
+    // Aplicada pelo repositório antes de gravar; sem chave do titular os valores são omitidos
+    pub fn pseudonymize(&mut self, key: Option<&[u8]>) {
+        for field in PSEUDONYMIZED_FIELDS {
+            if let Some(change) = self.changes.get_mut(field).and_then(Value::as_object_mut) {
+                for value in change.values_mut().filter(|value| is_personal_value(value)) {
+                    *value = match key {
+                        Some(key) => Value::String(format!("hmac-sha256:{}", hmac_sha256_hex(key, value.to_string().as_bytes()))),
+                        None => Value::String(AUDIT_REDACTED.to_string()),
+                    };
+                }
+            }
+        }
+    }
+}
+
+fn is_personal_value(value: &Value) -> bool {
+    !value.is_null() && value.as_str() != Some(AUDIT_REDACTED)
+}
+
+// Recurso ausente em um dos lados (criação, exclusão) entra como null no diff
+pub fn audit_entry<T: Serialize>(
+    action: &'static str,
+    resource_type: &'static str,
+    resource_id: Uuid,
+    before: Option<&T>,
+    after: Option<&T>,
+) -> NewAuditEntry {
+    let before = before.and_then(|value| serde_json::to_value(value).ok()).unwrap_or_default();
+    let after = after.and_then(|value| serde_json::to_value(value).ok()).unwrap_or_default();
+    NewAuditEntry {
+        action,
+        resource_type,
+        resource_id: Some(resource_id),
+        changes: audit_diff(&before, &after),
+    }
+}
+
+// Segredos e dados apagados entram na trilha só como "alterado", nunca com o valor
+pub fn audit_redacted_changes(fields: &[&str]) -> Value {
+    let redacted = json!({ "before": AUDIT_REDACTED, "after": AUDIT_REDACTED });
+    Value::Object(fields.iter().map(|field| (field.to_string(), redacted.clone())).collect())
+}
+
+#[derive(Deserialize, Debug, Default)]
+pub struct AuditQuery {
+    pub limit: Option<i64>,
+    pub cursor: Option<String>,
+    pub actor_id: Option<Uuid>,
+    pub resource_type: Option<String>,
+    pub resource_id: Option<Uuid>,
+    // Uma ou mais ações separadas por vírgula, ex.: user.created,user.deleted
+    pub action: Option<String>,
+    pub from: Option<NaiveDateTime>,
+    pub to: Option<NaiveDateTime>,
+    // `csv` exporta todas as entradas filtradas, sem paginação
+    pub format: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct AuditCursor {
+    pub seq: i64,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct AuditFilters {
+    pub organization_id: Option<Uuid>,
+    pub actor_id: Option<Uuid>,
+    pub resource_type: Option<String>,
+    pub resource_id: Option<Uuid>,
+    pub actions: Option<Vec<String>>,
+    pub from: Option<NaiveDateTime>,
+    pub to: Option<NaiveDateTime>,
+    pub before_seq: Option<i64>,
+    pub limit: Option<i64>,
+}
@@ -1,5 +1,6 @@
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
+use crate::core::entities::user::ROLE_USER;
 
 
 #[derive(Serialize, Deserialize)]
@@ -14,13 +15,42 @@ pub struct LoginResponse {
     pub id: Uuid,
     pub full_name: String,
     pub email: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub organization_id: Option<Uuid>,
+    // Senha vencida: o token só permite trocar a senha
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub password_expired: bool,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ClaimsToUserToken {
     pub id: String,
     pub exp: usize,
+    // Emissão do token; tokens anteriores à revogação das sessões do usuário são recusados
+    #[serde(default)]
+    pub iat: usize,
     pub full_name: String,
     pub email: String,
+    #[serde(default = "default_role")]
+    pub role: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub client_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scope: Option<String>,
+    // Organização ativa: define o escopo das listagens
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub organization_id: Option<Uuid>,
+    // Emitido para uma senha vencida; o AuthMiddleware libera apenas a troca de senha
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub password_expired: bool,
 }
 
+impl ClaimsToUserToken {
+    pub fn user_id(&self) -> Option<Uuid> {
+        Uuid::parse_str(&self.id).ok()
+    }
+}
+
+fn default_role() -> String {
+    ROLE_USER.to_string()
+}
@@ -0,0 +1,99 @@
+use std::net::{IpAddr, SocketAddr};
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+use crate::utils::crypto::sha256_hex;
+
+#[derive(Serialize, FromRow, Debug, Clone)]
+pub struct KnownDevice {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub fingerprint: String,
+    pub user_agent: Option<String>,
+    pub ip_network: String,
+    pub first_seen_at: NaiveDateTime,
+    pub last_seen_at: NaiveDateTime,
+}
+
+#[derive(Debug, Clone)]
+pub struct DeviceFingerprint {
+    pub fingerprint: String,
+    pub user_agent: Option<String>,
+    pub ip_network: String,
+}
+
+// Resultado do registro de um login: `first_device` evita o aviso no primeiro acesso da conta
+#[derive(Debug, Clone)]
+pub struct DeviceSighting {
+    pub device: KnownDevice,
+    pub new_device: bool,
+    pub first_device: bool,
+}
+
+#[derive(Serialize, FromRow, Debug, Clone)]
+pub struct DeviceAlert {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub device_id: Option<Uuid>,
+    pub ip: Option<String>,
+    pub user_agent: Option<String>,
+    pub expires_at: NaiveDateTime,
+    pub reported_at: Option<NaiveDateTime>,
+    pub password_reset_at: Option<NaiveDateTime>,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Debug, Clone)]
+pub struct CreateDeviceAlert {
+    pub user_id: Uuid,
+    pub device_id: Uuid,
+    pub token_hash: String,
+    pub ip: Option<String>,
+    pub user_agent: Option<String>,
+    pub expires_at: NaiveDateTime,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct DeviceAlertToken {
+    pub token: String,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct DeviceAlertPasswordReset {
+    pub token: String,
+    pub new_password: String,
+}
+
+#[derive(Serialize, Debug)]
+pub struct DeviceAlertReport {
+    pub user_id: Uuid,
+    pub sessions_revoked_at: NaiveDateTime,
+    pub refresh_tokens_revoked: u64,
+    pub passkeys_removed: u64,
+    pub identities_removed: u64,
+    pub password_reset_required: bool,
+}
+
+// Trocar de rede dentro do mesmo provedor (/24 no IPv4, /64 no IPv6) não conta como dispositivo novo
+pub fn device_fingerprint(user_agent: Option<&str>, ip: Option<&str>) -> DeviceFingerprint {
+    let ip_network = ip.map(ip_network).unwrap_or_else(|| "unknown".to_string());
+    let user_agent = user_agent.map(str::to_string);
+    let fingerprint = sha256_hex(format!("{}\n{}", user_agent.as_deref().unwrap_or_default(), ip_network).as_bytes());
+    DeviceFingerprint { fingerprint, user_agent, ip_network }
+}
+
+fn ip_network(ip: &str) -> String {
+    let address = ip.parse::<IpAddr>().ok().or_else(|| ip.parse::<SocketAddr>().ok().map(|socket| socket.ip()));
+    match address {
+        Some(IpAddr::V4(v4)) => {
+            let [a, b, c, _] = v4.octets();
+            format!("{}.{}.{}.0/24", a, b, c)
+        }
+        Some(IpAddr::V6(v6)) => {
+            let s = v6.segments();
+            format!("{:x}:{:x}:{:x}:{:x}::/64", s[0], s[1], s[2], s[3])
+        }
+        None => ip.to_string(),
+    }
+}
@@ -0,0 +1,40 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+#[derive(Serialize, FromRow, Debug, Clone)]
+pub struct EmailChange {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub old_email: String,
+    pub new_email: String,
+    pub confirm_expires_at: NaiveDateTime,
+    pub revert_expires_at: NaiveDateTime,
+    pub confirmed_at: Option<NaiveDateTime>,
+    pub reverted_at: Option<NaiveDateTime>,
+    pub cancelled_at: Option<NaiveDateTime>,
+    pub created_at: NaiveDateTime,
+}
+
+impl EmailChange {
+    pub fn is_pending(&self) -> bool {
+        self.confirmed_at.is_none() && self.reverted_at.is_none() && self.cancelled_at.is_none()
+    }
+}
+
+#[derive(Debug)]
+pub struct CreateEmailChange {
+    pub user_id: Uuid,
+    pub old_email: String,
+    pub new_email: String,
+    pub confirm_token_hash: String,
+    pub revert_token_hash: String,
+    pub confirm_expires_at: NaiveDateTime,
+    pub revert_expires_at: NaiveDateTime,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct EmailChangeToken {
+    pub token: String,
+}
@@ -0,0 +1,65 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use sqlx::prelude::FromRow;
+use sqlx::types::Json;
+use uuid::Uuid;
+use crate::core::entities::user::UserExportFormat;
+use crate::core::entities::user_attribute::UserAttributes;
+
+pub const EXPORT_STATUS_PENDING: &str = "pending";
+pub const EXPORT_STATUS_RUNNING: &str = "running";
+pub const EXPORT_STATUS_COMPLETED: &str = "completed";
+pub const EXPORT_STATUS_FAILED: &str = "failed";
+pub const EXPORT_STATUS_EXPIRED: &str = "expired";
+
+// Mesmos filtros aceitos pela listagem de usuários
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct ExportFilters {
+    pub sort: Option<String>,
+    pub email_contains: Option<String>,
+    pub created_from: Option<NaiveDateTime>,
+    pub created_to: Option<NaiveDateTime>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub attributes: Option<UserAttributes>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct CreateExportJob {
+    pub format: UserExportFormat,
+    #[serde(flatten)]
+    pub filters: ExportFilters,
+}
+
+#[derive(Serialize, Debug, Clone, FromRow)]
+pub struct ExportJob {
+    pub id: Uuid,
+    pub requested_by: Option<Uuid>,
+    pub format: String,
+    pub filters: Json<ExportFilters>,
+    pub status: String,
+    pub processed_rows: i64,
+    pub total_rows: Option<i64>,
+    #[serde(skip)]
+    pub file_path: Option<String>,
+    pub error: Option<String>,
+    pub created_at: NaiveDateTime,
+    pub started_at: Option<NaiveDateTime>,
+    pub completed_at: Option<NaiveDateTime>,
+    pub expires_at: Option<NaiveDateTime>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct ExportJobResponse {
+    #[serde(flatten)]
+    pub job: ExportJob,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub download_url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub download_url_expires_at: Option<NaiveDateTime>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct ExportDownloadQuery {
+    pub expires: i64,
+    pub signature: String,
+}
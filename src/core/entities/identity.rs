@@ -0,0 +1,42 @@
+use serde::{Deserialize, Serialize};
+use sqlx::prelude::FromRow;
+use uuid::Uuid;
+use chrono::NaiveDateTime;
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct UserIdentity {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub issuer: String,
+    pub subject: String,
+    pub email: Option<String>,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Debug)]
+pub struct CreateUserIdentity {
+    pub user_id: Uuid,
+    pub issuer: String,
+    pub subject: String,
+    pub email: Option<String>,
+}
+
+#[derive(Debug, Clone, FromRow)]
+pub struct OidcLoginState {
+    pub state_hash: String,
+    pub nonce: String,
+    pub code_verifier: String,
+    pub link_user_id: Option<Uuid>,
+    pub expires_at: NaiveDateTime,
+}
+
+#[derive(Serialize)]
+pub struct OidcAuthorization {
+    pub authorization_url: String,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct OidcCallback {
+    pub code: String,
+    pub state: String,
+}
@@ -0,0 +1,45 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+pub const LOGIN_FAILURE_UNKNOWN_EMAIL: &str = "unknown_email";
+pub const LOGIN_FAILURE_INVALID_PASSWORD: &str = "invalid_password";
+pub const LOGIN_FAILURE_ACCOUNT_INACTIVE: &str = "account_inactive";
+pub const LOGIN_FAILURE_PASSWORD_RESET_REQUIRED: &str = "password_reset_required";
+pub const LOGIN_FAILURE_PASSWORD_EXPIRED: &str = "password_expired";
+
+#[derive(Serialize, FromRow, Debug, Clone)]
+pub struct LoginAttempt {
+    pub id: Uuid,
+    pub user_id: Option<Uuid>,
+    pub email: String,
+    pub succeeded: bool,
+    pub failure_reason: Option<String>,
+    pub ip: Option<String>,
+    pub user_agent: Option<String>,
+    pub request_id: Option<String>,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Debug, Clone)]
+pub struct NewLoginAttempt {
+    pub user_id: Option<Uuid>,
+    pub email: String,
+    pub succeeded: bool,
+    pub failure_reason: Option<&'static str>,
+}
+
+#[derive(Deserialize, Debug, Default)]
+pub struct LoginActivityQuery {
+    // Apenas administradores consultam outro usuário; sem ele, o próprio usuário do token
+    pub user_id: Option<Uuid>,
+    pub limit: Option<i64>,
+    pub cursor: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct LoginActivityCursor {
+    pub created_at: NaiveDateTime,
+    pub id: Uuid,
+}
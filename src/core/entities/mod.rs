@@ -1,2 +1,15 @@
 pub mod user;
 pub mod auth;
+pub mod passkey;
+pub mod oauth;
+pub mod identity;
+pub mod patch;
+pub mod export_job;
+pub mod organization;
+pub mod user_attribute;
+pub mod user_invitation;
+pub mod privacy;
+pub mod email_change;
+pub mod audit;
+pub mod login_attempt;
+pub mod device;
@@ -0,0 +1,106 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use sqlx::prelude::FromRow;
+use uuid::Uuid;
+
+pub const ORG_ROLE_OWNER: &str = "owner";
+pub const ORG_ROLE_ADMIN: &str = "admin";
+pub const ORG_ROLE_MEMBER: &str = "member";
+
+pub fn is_valid_org_role(role: &str) -> bool {
+    matches!(role, ORG_ROLE_OWNER | ORG_ROLE_ADMIN | ORG_ROLE_MEMBER)
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct Organization {
+    pub id: Uuid,
+    pub name: String,
+    pub slug: String,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+// Organização vista por um membro, com o papel dele nela
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct UserOrganization {
+    pub id: Uuid,
+    pub name: String,
+    pub slug: String,
+    pub role: String,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct CreateOrganization {
+    pub name: String,
+    pub slug: String,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct UpdateOrganization {
+    pub name: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct OrganizationMembership {
+    pub organization_id: Uuid,
+    pub user_id: Uuid,
+    pub role: String,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct OrganizationMember {
+    pub user_id: Uuid,
+    pub full_name: String,
+    pub email: String,
+    pub role: String,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct UpdateMembership {
+    pub role: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct OrganizationInvitation {
+    pub id: Uuid,
+    pub organization_id: Uuid,
+    pub email: String,
+    pub role: String,
+    #[serde(skip_serializing)]
+    pub token_hash: String,
+    pub invited_by: Option<Uuid>,
+    pub expires_at: NaiveDateTime,
+    pub accepted_at: Option<NaiveDateTime>,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct CreateOrganizationInvitation {
+    pub email: String,
+    pub role: Option<String>,
+}
+
+// O token só é devolvido na criação; o banco guarda apenas o hash
+#[derive(Serialize, Debug)]
+pub struct OrganizationInvitationCreated {
+    #[serde(flatten)]
+    pub invitation: OrganizationInvitation,
+    pub token: String,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct AcceptOrganizationInvitation {
+    pub token: String,
+}
+
+// Resultado de mudanças de papel ou remoção, que nunca podem deixar a organização sem proprietário
+#[derive(Debug)]
+pub enum MembershipChange<T> {
+    Done(T),
+    NotFound,
+    LastOwner,
+}
@@ -0,0 +1,113 @@
+use serde::{Deserialize, Serialize};
+use sqlx::prelude::FromRow;
+use uuid::Uuid;
+use chrono::NaiveDateTime;
+
+pub const REGISTRATION_CEREMONY: &str = "registration";
+pub const AUTHENTICATION_CEREMONY: &str = "authentication";
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct PasskeyCredential {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub credential_id: String,
+    #[serde(skip_serializing)]
+    pub public_key: Vec<u8>,
+    pub sign_count: i64,
+    pub transports: Vec<String>,
+    pub created_at: NaiveDateTime,
+    pub last_used_at: Option<NaiveDateTime>,
+}
+
+#[derive(Debug)]
+pub struct CreatePasskeyCredential {
+    pub user_id: Uuid,
+    pub credential_id: String,
+    pub public_key: Vec<u8>,
+    pub sign_count: i64,
+    pub transports: Vec<String>,
+}
+
+#[derive(Debug, Clone, FromRow)]
+pub struct PasskeyChallenge {
+    pub id: Uuid,
+    pub user_id: Option<Uuid>,
+    pub challenge: String,
+    pub ceremony: String,
+    pub expires_at: NaiveDateTime,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct RelyingParty {
+    pub id: String,
+    pub name: String,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct PasskeyUser {
+    pub id: String,
+    pub name: String,
+    pub display_name: String,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct PubKeyCredParam {
+    #[serde(rename = "type")]
+    pub kind: String,
+    pub alg: i64,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct CredentialDescriptor {
+    #[serde(rename = "type")]
+    pub kind: String,
+    pub id: String,
+    pub transports: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct PasskeyRegistrationOptions {
+    pub challenge_id: Uuid,
+    pub challenge: String,
+    pub rp: RelyingParty,
+    pub user: PasskeyUser,
+    pub pub_key_cred_params: Vec<PubKeyCredParam>,
+    pub timeout: u64,
+    pub attestation: String,
+    pub user_verification: String,
+    pub exclude_credentials: Vec<CredentialDescriptor>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct PasskeyLoginOptions {
+    pub challenge_id: Uuid,
+    pub challenge: String,
+    pub rp_id: String,
+    pub timeout: u64,
+    pub user_verification: String,
+    pub allow_credentials: Vec<CredentialDescriptor>,
+}
+
+// Binary fields are base64url encoded without padding, as produced by the browser API
+#[derive(Serialize, Deserialize)]
+pub struct PasskeyRegistration {
+    pub challenge_id: Uuid,
+    pub client_data_json: String,
+    pub attestation_object: String,
+    #[serde(default)]
+    pub transports: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct PasskeyLoginOptionsRequest {
+    pub email: Option<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct PasskeyLogin {
+    pub challenge_id: Uuid,
+    pub credential_id: String,
+    pub client_data_json: String,
+    pub authenticator_data: String,
+    pub signature: String,
+}
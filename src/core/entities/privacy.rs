@@ -0,0 +1,149 @@
+use chrono::NaiveDateTime;
+use serde::Serialize;
+use sqlx::types::Json;
+use sqlx::FromRow;
+use uuid::Uuid;
+use crate::core::entities::audit::AuditEntry;
+use crate::core::entities::device::KnownDevice;
+use crate::core::entities::email_change::EmailChange;
+use crate::core::entities::login_attempt::LoginAttempt;
+use crate::core::entities::organization::UserOrganization;
+use crate::core::entities::user::UserStatusChange;
+use crate::core::entities::user_attribute::UserAttributes;
+
+// Versão do formato do arquivo de dados do titular; incrementada quando campos mudam de sentido
+pub const USER_DATA_EXPORT_VERSION: u32 = 2;
+
+pub const ERASED_FULL_NAME: &str = "Erased user";
+
+pub fn erased_email(id: Uuid) -> String {
+    format!("erased-{}@erased.invalid", id)
+}
+
+#[derive(Serialize, FromRow, Debug)]
+pub struct ExportedProfile {
+    pub id: Uuid,
+    pub full_name: String,
+    pub email: String,
+    pub role: String,
+    pub status: String,
+    pub attributes: Json<UserAttributes>,
+    pub avatar_updated_at: Option<NaiveDateTime>,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+    pub deleted_at: Option<NaiveDateTime>,
+    pub erased_at: Option<NaiveDateTime>,
+}
+
+// Cada refresh token emitido para um cliente OAuth é uma sessão
+#[derive(Serialize, FromRow, Debug)]
+pub struct ExportedSession {
+    pub client_id: String,
+    pub client_name: String,
+    pub scope: String,
+    pub created_at: NaiveDateTime,
+    pub expires_at: NaiveDateTime,
+    pub revoked_at: Option<NaiveDateTime>,
+}
+
+// Autorizações concedidas na tela de consentimento OAuth, agrupadas por cliente
+#[derive(Serialize, FromRow, Debug)]
+pub struct ExportedConsent {
+    pub client_id: String,
+    pub client_name: String,
+    pub scopes: Vec<String>,
+    pub first_granted_at: NaiveDateTime,
+    pub last_granted_at: NaiveDateTime,
+    pub active: bool,
+}
+
+#[derive(Serialize, FromRow, Debug)]
+pub struct ExportedPasskey {
+    pub id: Uuid,
+    pub transports: Vec<String>,
+    pub created_at: NaiveDateTime,
+    pub last_used_at: Option<NaiveDateTime>,
+}
+
+#[derive(Serialize, FromRow, Debug)]
+pub struct ExportedIdentity {
+    pub issuer: String,
+    pub subject: String,
+    pub email: Option<String>,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Serialize, FromRow, Debug)]
+pub struct ExportedOAuthClient {
+    pub client_id: String,
+    pub name: String,
+    pub redirect_uris: Vec<String>,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Serialize, FromRow, Debug)]
+pub struct ExportedInvitation {
+    pub id: Uuid,
+    // `user` (convite para criar a conta) ou `organization`
+    pub kind: String,
+    pub organization_id: Option<Uuid>,
+    pub role: String,
+    pub invited_by: Option<Uuid>,
+    pub created_at: NaiveDateTime,
+    pub accepted_at: Option<NaiveDateTime>,
+}
+
+#[derive(Serialize, FromRow, Debug)]
+pub struct ExportedExportJob {
+    pub id: Uuid,
+    pub format: String,
+    pub status: String,
+    pub created_at: NaiveDateTime,
+    pub completed_at: Option<NaiveDateTime>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct UserDataExport {
+    pub version: u32,
+    pub generated_at: NaiveDateTime,
+    pub profile: ExportedProfile,
+    pub organizations: Vec<UserOrganization>,
+    pub sessions: Vec<ExportedSession>,
+    pub consents: Vec<ExportedConsent>,
+    pub passkeys: Vec<ExportedPasskey>,
+    pub identities: Vec<ExportedIdentity>,
+    pub oauth_clients: Vec<ExportedOAuthClient>,
+    pub invitations: Vec<ExportedInvitation>,
+    pub email_changes: Vec<EmailChange>,
+    pub export_jobs: Vec<ExportedExportJob>,
+    // Mudanças de status sofridas ou feitas pelo usuário
+    pub status_history: Vec<UserStatusChange>,
+    // Entradas da trilha de auditoria feitas pelo usuário ou sobre ele
+    pub audit: Vec<AuditEntry>,
+    pub login_activity: Vec<LoginAttempt>,
+    pub devices: Vec<KnownDevice>,
+}
+
+#[derive(FromRow, Debug)]
+pub struct ErasureCandidate {
+    pub email: String,
+    pub status: String,
+    pub has_attributes: bool,
+    pub has_avatar: bool,
+}
+
+#[derive(Serialize, Debug)]
+pub struct ErasureChange {
+    pub resource: &'static str,
+    pub action: &'static str,
+    pub count: u64,
+}
+
+#[derive(Serialize, Debug)]
+pub struct UserErasureReport {
+    pub user_id: Uuid,
+    pub erased_at: NaiveDateTime,
+    pub changed_fields: Vec<&'static str>,
+    pub changes: Vec<ErasureChange>,
+    pub retained: Vec<&'static str>,
+}
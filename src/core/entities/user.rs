@@ -2,14 +2,57 @@ use serde::{Deserialize, Serialize};
 use sqlx::prelude::FromRow;
 use uuid::Uuid;
 use chrono::NaiveDateTime;
+use crate::core::entities::patch::Patch;
+use crate::core::entities::user_attribute::UserAttributes;
+use sqlx::types::Json;
+
+pub const ROLE_USER: &str = "user";
+pub const ROLE_ADMIN: &str = "admin";
+// Somente leitura da trilha de auditoria
+pub const ROLE_AUDITOR: &str = "auditor";
+
+pub fn is_valid_role(role: &str) -> bool {
+    matches!(role, ROLE_USER | ROLE_ADMIN | ROLE_AUDITOR)
+}
+
+// Contas privilegiadas precisam trocar a senha periodicamente
+pub fn is_privileged_role(role: &str) -> bool {
+    matches!(role, ROLE_ADMIN | ROLE_AUDITOR)
+}
+
+pub const STATUS_PENDING: &str = "pending";
+pub const STATUS_ACTIVE: &str = "active";
+pub const STATUS_SUSPENDED: &str = "suspended";
+pub const STATUS_DEACTIVATED: &str = "deactivated";
+
+// Transições permitidas a partir de cada status
+pub fn can_change_status(from: &str, to: &str) -> bool {
+    matches!(
+        (from, to),
+        (STATUS_PENDING, STATUS_ACTIVE)
+            | (STATUS_PENDING, STATUS_DEACTIVATED)
+            | (STATUS_ACTIVE, STATUS_SUSPENDED)
+            | (STATUS_ACTIVE, STATUS_DEACTIVATED)
+            | (STATUS_SUSPENDED, STATUS_ACTIVE)
+            | (STATUS_SUSPENDED, STATUS_DEACTIVATED)
+            | (STATUS_DEACTIVATED, STATUS_ACTIVE)
+    )
+}
 
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
 pub struct UserDataCreated {
     pub id: Uuid,
     pub full_name: String,
     pub email: String,
+    pub role: String,
+    pub status: String,
+    pub version: i64,
+    pub avatar_updated_at: Option<NaiveDateTime>,
+    pub attributes: Json<UserAttributes>,
     pub created_at: NaiveDateTime,
     pub updated_at: NaiveDateTime,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deleted_at: Option<NaiveDateTime>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
@@ -18,6 +61,10 @@ pub struct UserCompleteData {
     pub full_name: String,
     pub email: String,
     pub password: String,
+    pub role: String,
+    pub status: String,
+    pub password_reset_required: bool,
+    pub password_changed_at: NaiveDateTime,
     pub created_at: NaiveDateTime,
     pub updated_at: NaiveDateTime,
 }
@@ -27,16 +74,268 @@ pub struct CreateUser {
     pub full_name: String,
     pub email: String,
     pub password: String,
+    #[serde(default)]
+    pub attributes: UserAttributes,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
 pub struct UpdateUser {
     pub full_name: String,
     pub email: String,
+    // Substitui todos os atributos; ausente equivale a nenhum atributo
+    #[serde(default)]
+    pub attributes: UserAttributes,
+}
+
+#[derive(Deserialize, Debug, Default)]
+#[serde(deny_unknown_fields)]
+pub struct PatchUser {
+    #[serde(default)]
+    pub full_name: Patch<String>,
+    #[serde(default)]
+    pub email: Patch<String>,
+    // Mesclado aos atributos atuais; chaves com null são removidas
+    #[serde(default)]
+    pub attributes: Patch<UserAttributes>,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Deserialize)]
 pub struct UpdatePasswordUser {
     pub current_password: String,
     pub new_password: String,
 }
+
+#[derive(Deserialize, Debug, Default)]
+pub struct UserListQuery {
+    pub limit: Option<i64>,
+    pub cursor: Option<String>,
+    pub sort: Option<String>,
+    pub email_contains: Option<String>,
+    pub created_from: Option<NaiveDateTime>,
+    pub created_to: Option<NaiveDateTime>,
+    // Objeto JSON com os valores exigidos, ex.: {"department":"sales"}
+    pub attributes: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum UserSortField {
+    CreatedAt,
+    FullName,
+    Email,
+}
+
+impl UserSortField {
+    pub fn column(&self) -> &'static str {
+        match self {
+            UserSortField::CreatedAt => "created_at",
+            UserSortField::FullName => "full_name",
+            UserSortField::Email => "email",
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum SortDirection {
+    Asc,
+    Desc,
+}
+
+impl SortDirection {
+    pub fn keyword(&self) -> &'static str {
+        match self {
+            SortDirection::Asc => "ASC",
+            SortDirection::Desc => "DESC",
+        }
+    }
+}
+
+// Posição da última linha entregue; o valor depende do campo de ordenação
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct UserCursor {
+    pub sort: UserSortField,
+    pub direction: SortDirection,
+    pub value: String,
+    pub id: Uuid,
+}
+
+#[derive(Debug, Clone)]
+pub struct UserListParams {
+    pub limit: i64,
+    pub sort: UserSortField,
+    pub direction: SortDirection,
+    pub after: Option<UserCursor>,
+    pub email_contains: Option<String>,
+    pub created_from: Option<NaiveDateTime>,
+    pub created_to: Option<NaiveDateTime>,
+    pub attributes: Option<UserAttributes>,
+    pub only_deleted: bool,
+    // Organização que limita a listagem; `None` apenas para administradores globais
+    pub organization_id: Option<Uuid>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct UserExportQuery {
+    pub format: UserExportFormat,
+    pub sort: Option<String>,
+    pub email_contains: Option<String>,
+    pub created_from: Option<NaiveDateTime>,
+    pub created_to: Option<NaiveDateTime>,
+    pub attributes: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum UserExportFormat {
+    Csv,
+    Ndjson,
+}
+
+impl UserExportFormat {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            UserExportFormat::Csv => "csv",
+            UserExportFormat::Ndjson => "ndjson",
+        }
+    }
+
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            UserExportFormat::Csv => "text/csv; charset=utf-8",
+            UserExportFormat::Ndjson => "application/x-ndjson",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "csv" => Some(UserExportFormat::Csv),
+            "ndjson" => Some(UserExportFormat::Ndjson),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Deserialize, Debug)]
+pub struct ChangeUserStatus {
+    pub reason: String,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct ChangeUserRole {
+    pub role: String,
+}
+
+// Conferido a cada requisição autenticada pelo AuthMiddleware
+#[derive(Debug, Clone, FromRow)]
+pub struct UserSessionState {
+    pub status: String,
+    pub sessions_revoked_at: Option<NaiveDateTime>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, FromRow)]
+pub struct UserStatusChange {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub from_status: String,
+    pub to_status: String,
+    pub reason: String,
+    pub changed_by: Option<Uuid>,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct AvatarQuery {
+    pub size: Option<u32>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct UserSearchQuery {
+    pub q: String,
+    pub limit: Option<i64>,
+}
+
+#[derive(Debug, Clone, FromRow)]
+pub struct UserSearchRow {
+    #[sqlx(flatten)]
+    pub user: UserDataCreated,
+    pub rank: f32,
+}
+
+#[derive(Serialize, Debug)]
+pub struct UserSearchHighlights {
+    pub full_name: String,
+    pub email: String,
+}
+
+#[derive(Serialize, Debug)]
+pub struct UserSearchHit {
+    #[serde(flatten)]
+    pub user: UserDataCreated,
+    pub rank: f32,
+    pub highlights: UserSearchHighlights,
+}
+
+#[derive(Deserialize, Debug, Default)]
+pub struct UserImportQuery {
+    #[serde(default)]
+    pub dry_run: bool,
+    #[serde(default)]
+    pub mode: UserImportMode,
+}
+
+#[derive(Serialize, Deserialize, Debug, Default, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum UserImportMode {
+    // Importa as linhas válidas e reporta as demais
+    #[default]
+    Partial,
+    // Qualquer linha inválida cancela a importação inteira
+    AllOrNothing,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum UserImportFormat {
+    Csv,
+    Json,
+}
+
+#[derive(Deserialize, Debug, Default)]
+#[serde(default)]
+pub struct UserImportRow {
+    pub full_name: String,
+    pub email: String,
+    pub password: String,
+    // No CSV, vem das colunas `attributes.<chave>`
+    pub attributes: UserAttributes,
+}
+
+#[derive(Serialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum UserImportRowStatus {
+    Valid,
+    Created,
+    Failed,
+}
+
+#[derive(Serialize, Debug)]
+pub struct UserImportRowResult {
+    pub row: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub email: Option<String>,
+    pub status: UserImportRowStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<Uuid>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub errors: Vec<String>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct UserImportReport {
+    pub dry_run: bool,
+    pub mode: UserImportMode,
+    pub total: usize,
+    pub created: usize,
+    pub failed: usize,
+    pub rows: Vec<UserImportRowResult>,
+}
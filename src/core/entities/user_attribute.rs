@@ -0,0 +1,121 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sqlx::prelude::FromRow;
+use sqlx::types::Json;
+use uuid::Uuid;
+
+pub const ATTRIBUTE_TYPE_STRING: &str = "string";
+pub const ATTRIBUTE_TYPE_NUMBER: &str = "number";
+pub const ATTRIBUTE_TYPE_BOOLEAN: &str = "boolean";
+pub const ATTRIBUTE_TYPE_ENUM: &str = "enum";
+
+// Valores dos atributos customizados de um usuário, indexados pela chave da definição
+pub type UserAttributes = serde_json::Map<String, Value>;
+
+pub fn is_valid_attribute_type(attribute_type: &str) -> bool {
+    matches!(
+        attribute_type,
+        ATTRIBUTE_TYPE_STRING | ATTRIBUTE_TYPE_NUMBER | ATTRIBUTE_TYPE_BOOLEAN | ATTRIBUTE_TYPE_ENUM
+    )
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct UserAttributeDefinition {
+    pub key: String,
+    pub label: String,
+    #[serde(rename = "type")]
+    pub attribute_type: String,
+    pub required: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub allowed_values: Option<Vec<String>>,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+impl UserAttributeDefinition {
+    pub fn accepts(&self, value: &Value) -> bool {
+        match (self.attribute_type.as_str(), value) {
+            (ATTRIBUTE_TYPE_STRING, Value::String(_)) => true,
+            (ATTRIBUTE_TYPE_NUMBER, Value::Number(_)) => true,
+            (ATTRIBUTE_TYPE_BOOLEAN, Value::Bool(_)) => true,
+            (ATTRIBUTE_TYPE_ENUM, Value::String(value)) => {
+                self.allowed_values.as_ref().is_some_and(|allowed| allowed.contains(value))
+            }
+            _ => false,
+        }
+    }
+
+    // Mensagem de erro para um valor recusado por `accepts`
+    pub fn expected(&self) -> String {
+        match (self.attribute_type.as_str(), &self.allowed_values) {
+            (ATTRIBUTE_TYPE_ENUM, Some(allowed)) => format!("one of: {}", allowed.join(", ")),
+            (attribute_type, _) => format!("a {}", attribute_type),
+        }
+    }
+}
+
+#[derive(Deserialize, Debug)]
+pub struct CreateUserAttributeDefinition {
+    pub key: String,
+    pub label: Option<String>,
+    #[serde(rename = "type")]
+    pub attribute_type: String,
+    #[serde(default)]
+    pub required: bool,
+    pub allowed_values: Option<Vec<String>>,
+}
+
+// Chave e tipo não mudam: os valores já gravados continuariam no formato antigo
+#[derive(Deserialize, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct UpdateUserAttributeDefinition {
+    pub label: String,
+    #[serde(default)]
+    pub required: bool,
+    pub allowed_values: Option<Vec<String>>,
+}
+
+// Erros dos atributos enviados numa escrita. Em `partial` (PATCH) os obrigatórios ausentes continuam
+// com o valor atual; null remove a chave e só é aceito em atributos opcionais
+pub fn attribute_errors(definitions: &[UserAttributeDefinition], attributes: &UserAttributes, partial: bool) -> Vec<String> {
+    let mut errors: Vec<String> = attributes
+        .keys()
+        .filter(|key| !definitions.iter().any(|definition| &definition.key == *key))
+        .map(|key| format!("attribute '{}' is not defined", key))
+        .collect();
+
+    for definition in definitions {
+        let missing = match attributes.get(&definition.key) {
+            None => !partial,
+            Some(value) if is_blank_attribute(value) => true,
+            Some(value) => {
+                if !definition.accepts(value) {
+                    errors.push(format!("attribute '{}' must be {}", definition.key, definition.expected()));
+                }
+                false
+            }
+        };
+        if missing && definition.required {
+            errors.push(format!("attribute '{}' is required", definition.key));
+        }
+    }
+    errors
+}
+
+// Null e texto vazio equivalem a atributo ausente
+pub fn is_blank_attribute(value: &Value) -> bool {
+    match value {
+        Value::Null => true,
+        Value::String(value) => value.trim().is_empty(),
+        _ => false,
+    }
+}
+
+// Atributos de um usuário antes e depois da exclusão da definição
+#[derive(Debug, Clone, FromRow)]
+pub struct RemovedUserAttribute {
+    pub user_id: Uuid,
+    pub before: Json<UserAttributes>,
+    pub after: Json<UserAttributes>,
+}
@@ -0,0 +1,70 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use sqlx::prelude::FromRow;
+use uuid::Uuid;
+use crate::core::entities::user_attribute::UserAttributes;
+
+pub const INVITATION_STATUS_PENDING: &str = "pending";
+pub const INVITATION_STATUS_ACCEPTED: &str = "accepted";
+pub const INVITATION_STATUS_REVOKED: &str = "revoked";
+pub const INVITATION_STATUS_EXPIRED: &str = "expired";
+
+#[derive(Serialize, Debug, Clone, FromRow)]
+pub struct UserInvitation {
+    pub id: Uuid,
+    pub email: String,
+    pub role: String,
+    pub organization_id: Option<Uuid>,
+    pub organization_role: Option<String>,
+    pub invited_by: Option<Uuid>,
+    #[serde(skip_serializing)]
+    pub send_count: i32,
+    pub last_sent_at: NaiveDateTime,
+    pub expires_at: NaiveDateTime,
+    pub accepted_at: Option<NaiveDateTime>,
+    pub accepted_user_id: Option<Uuid>,
+    pub revoked_at: Option<NaiveDateTime>,
+    pub created_at: NaiveDateTime,
+}
+
+impl UserInvitation {
+    pub fn status(&self, now: NaiveDateTime) -> &'static str {
+        if self.accepted_at.is_some() {
+            INVITATION_STATUS_ACCEPTED
+        } else if self.revoked_at.is_some() {
+            INVITATION_STATUS_REVOKED
+        } else if self.expires_at <= now {
+            INVITATION_STATUS_EXPIRED
+        } else {
+            INVITATION_STATUS_PENDING
+        }
+    }
+}
+
+#[derive(Serialize, Debug)]
+pub struct UserInvitationResponse {
+    #[serde(flatten)]
+    pub invitation: UserInvitation,
+    pub status: &'static str,
+    // Presente apenas quando a requisição enviou o email
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub email_sent: Option<bool>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct CreateUserInvitation {
+    pub email: String,
+    // Papel global da conta criada; padrão `user`
+    pub role: Option<String>,
+    // Papel na organização ativa de quem convida; sem ele o convidado entra como `member`
+    pub organization_role: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct AcceptUserInvitation {
+    pub token: String,
+    pub full_name: String,
+    pub password: String,
+    #[serde(default)]
+    pub attributes: UserAttributes,
+}
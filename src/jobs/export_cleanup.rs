@@ -0,0 +1,24 @@
+use std::time::Duration;
+use actix_web::{rt, web};
+use log::{error, info};
+use crate::config::tenant::{TenantScope, CURRENT_TENANT};
+use crate::services::export_service::ExportService;
+
+// Encerra jobs interrompidos na inicialização e remove periodicamente os arquivos expirados,
+// com acesso declarado a todas as organizações
+pub fn spawn_export_cleanup(service: web::Data<ExportService>, interval_seconds: u64) {
+    rt::spawn(CURRENT_TENANT.scope(TenantScope::All, async move {
+        if let Err(e) = service.fail_interrupted_jobs().await {
+            error!("[Job] Failed to close interrupted export jobs: {:?}", e);
+        }
+
+        let mut interval = rt::time::interval(Duration::from_secs(interval_seconds.max(1)));
+        loop {
+            interval.tick().await;
+            info!("[Job] Running expired exports cleanup");
+            if let Err(e) = service.cleanup_expired_exports().await {
+                error!("[Job] Expired exports cleanup failed: {:?}", e);
+            }
+        }
+    }));
+}
@@ -0,0 +1,18 @@
+use std::time::Duration;
+use actix_web::{rt, web};
+use log::{error, info};
+use crate::services::login_activity_service::LoginActivityService;
+
+// Tarefa periódica que remove o histórico de login mais antigo que o período de retenção
+pub fn spawn_login_attempt_purge(service: web::Data<LoginActivityService>, retention_days: i64, interval_seconds: u64) {
+    rt::spawn(async move {
+        let mut interval = rt::time::interval(Duration::from_secs(interval_seconds.max(1)));
+        loop {
+            interval.tick().await;
+            info!("[Job] Running login attempts purge");
+            if let Err(e) = service.purge_old_attempts(retention_days).await {
+                error!("[Job] Login attempts purge failed: {:?}", e);
+            }
+        }
+    });
+}
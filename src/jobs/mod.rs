@@ -0,0 +1,3 @@
+pub mod user_purge;
+pub mod export_cleanup;
+pub mod login_attempt_purge;
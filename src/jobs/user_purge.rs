@@ -0,0 +1,18 @@
+use std::time::Duration;
+use actix_web::{rt, web};
+use log::{error, info};
+use crate::services::user_service::UserService;
+
+// Tarefa periódica que expurga os usuários excluídos após o período de retenção
+pub fn spawn_user_purge(service: web::Data<UserService>, retention_days: i64, interval_seconds: u64) {
+    rt::spawn(async move {
+        let mut interval = rt::time::interval(Duration::from_secs(interval_seconds.max(1)));
+        loop {
+            interval.tick().await;
+            info!("[Job] Running deleted users purge");
+            if let Err(e) = service.purge_deleted_users(retention_days).await {
+                error!("[Job] Deleted users purge failed: {:?}", e);
+            }
+        }
+    });
+}
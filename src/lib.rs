@@ -2,6 +2,7 @@ pub mod adapters;
 pub mod config;
 pub mod controllers;
 pub mod core;
+pub mod jobs;
 pub mod middleware;
 pub mod repositories;
 pub mod routes;
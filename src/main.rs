@@ -1,15 +1,47 @@
+use std::sync::Arc;
 use actix_web::{web, App, HttpServer, middleware::Logger};
 use actix_cors::Cors;
 use env_logger::{Builder, Env};
 use log::info;
+use rust_api_architecture_model::adapters::mailer::{LogMailer, MailerPort, SmtpMailer};
+use rust_api_architecture_model::adapters::file_storage::{FileStoragePort, InMemoryFileStorage, LocalFileStorage};
+use rust_api_architecture_model::adapters::oidc_provider::{HttpOidcProvider, OidcProviderPort};
+use rust_api_architecture_model::adapters::passkey_verifier::WebAuthnVerifier;
 use rust_api_architecture_model::adapters::password_hasher::Argon2PasswordEncryptor;
 use rust_api_architecture_model::adapters::token_hasher::JwtTokenGenerator;
 use rust_api_architecture_model::config::{config_env::Config, database::init_database};
+use rust_api_architecture_model::repositories::audit_repository::PgAuditRepository;
+use rust_api_architecture_model::repositories::device_repository::PgDeviceRepository;
+use rust_api_architecture_model::repositories::email_change_repository::PgEmailChangeRepository;
+use rust_api_architecture_model::repositories::export_job_repository::PgExportJobRepository;
+use rust_api_architecture_model::repositories::identity_repository::PgIdentityRepository;
+use rust_api_architecture_model::repositories::login_attempt_repository::PgLoginAttemptRepository;
+use rust_api_architecture_model::repositories::oauth_repository::PgOAuthRepository;
+use rust_api_architecture_model::repositories::organization_repository::PgOrganizationRepository;
+use rust_api_architecture_model::repositories::passkey_repository::PgPasskeyRepository;
+use rust_api_architecture_model::repositories::privacy_repository::PgPrivacyRepository;
+use rust_api_architecture_model::repositories::user_attribute_repository::PgUserAttributeRepository;
+use rust_api_architecture_model::repositories::user_invitation_repository::PgUserInvitationRepository;
 use rust_api_architecture_model::repositories::user_repository::PgUserRepository;
 use rust_api_architecture_model::routes::config::base_routes::configure_routes;
 use rust_api_architecture_model::services::user_service::UserService;
 use rust_api_architecture_model::services::auth_service::AuthService;
+use rust_api_architecture_model::services::oauth_service::OAuthService;
+use rust_api_architecture_model::services::oidc_service::OidcService;
+use rust_api_architecture_model::services::export_service::ExportService;
+use rust_api_architecture_model::services::avatar_service::AvatarService;
+use rust_api_architecture_model::services::organization_service::OrganizationService;
+use rust_api_architecture_model::services::user_attribute_service::UserAttributeService;
+use rust_api_architecture_model::services::user_invitation_service::UserInvitationService;
+use rust_api_architecture_model::services::privacy_service::PrivacyService;
+use rust_api_architecture_model::services::email_change_service::EmailChangeService;
+use rust_api_architecture_model::services::audit_service::AuditService;
+use rust_api_architecture_model::services::login_activity_service::LoginActivityService;
+use rust_api_architecture_model::services::device_alert_service::DeviceAlertService;
 use rust_api_architecture_model::middleware::auth::AuthMiddleware;
+use rust_api_architecture_model::jobs::user_purge::spawn_user_purge;
+use rust_api_architecture_model::jobs::export_cleanup::spawn_export_cleanup;
+use rust_api_architecture_model::jobs::login_attempt_purge::spawn_login_attempt_purge;
 
 
 #[actix_web::main]
@@ -30,25 +62,149 @@ async fn main() -> std::io::Result<()> {
     info!("Database connection established");
 
     //Create adapters
-    let password_encryptor = Box::new(Argon2PasswordEncryptor::new());
+    let password_encryptor = Arc::new(Argon2PasswordEncryptor::new());
+    let mailer: Arc<dyn MailerPort> = match config.mailer.as_str() {
+        "smtp" => Arc::new(SmtpMailer::new(&config.smtp_url, &config.mail_from).expect("Invalid SMTP configuration")),
+        _ => Arc::new(LogMailer::new()),
+    };
 
     //Create repositories
     let user_repository = web::Data::new(PgUserRepository::new(pool.clone()));
+    let passkey_repository = web::Data::new(PgPasskeyRepository::new(pool.clone()));
+    let oauth_repository = web::Data::new(PgOAuthRepository::new(pool.clone()));
+    let identity_repository = web::Data::new(PgIdentityRepository::new(pool.clone()));
+    let export_job_repository = web::Data::new(PgExportJobRepository::new(pool.clone()));
+    let organization_repository = web::Data::new(PgOrganizationRepository::new(pool.clone()));
+    let user_attribute_repository = web::Data::new(PgUserAttributeRepository::new(pool.clone()));
+    let user_invitation_repository = web::Data::new(PgUserInvitationRepository::new(pool.clone()));
+    let privacy_repository = web::Data::new(PgPrivacyRepository::new(pool.clone()));
+    let email_change_repository = web::Data::new(PgEmailChangeRepository::new(pool.clone()));
+    let audit_repository = web::Data::new(PgAuditRepository::new(pool.clone()));
+    let login_attempt_repository = web::Data::new(PgLoginAttemptRepository::new(pool.clone()));
+    let device_repository = web::Data::new(PgDeviceRepository::new(pool.clone()));
     info!("Repositories Created");
 
     //Create services
+    let audit_service = web::Data::new(AuditService::new(audit_repository.clone()));
+
+    let login_activity_service = web::Data::new(LoginActivityService::new(
+        login_attempt_repository.clone(),
+        user_repository.clone(),
+    ));
+
+    let email_change_service = web::Data::new(EmailChangeService::new(
+        email_change_repository.clone(),
+        user_repository.clone(),
+        mailer.clone(),
+        web::Data::new(config.clone()),
+    ));
+
     let user_service = web::Data::new(UserService::new(
           user_repository.clone(),
+          user_attribute_repository.clone(),
           password_encryptor.clone(),
+          email_change_service.clone(),
+          config.require_if_match,
+          config.password_history_size,
+    ));
+
+    let device_alert_service = web::Data::new(DeviceAlertService::new(
+        device_repository.clone(),
+        user_service.clone(),
+        mailer.clone(),
+        web::Data::new(config.clone()),
     ));
 
     let auth_service = web::Data::new(AuthService::new(
+        user_repository.clone(),
+        passkey_repository.clone(),
+        organization_repository.clone(),
+        login_activity_service.clone(),
+        device_alert_service.clone(),
+        web::Data::new(config.clone()),
+        Box::new(Argon2PasswordEncryptor::new()),
+        Box::new(JwtTokenGenerator::new()),
+        Box::new(WebAuthnVerifier::new(config.webauthn_rp_id.clone(), config.webauthn_origin.clone())),
+    ));
+
+    let oauth_service = web::Data::new(OAuthService::new(
+        oauth_repository.clone(),
         user_repository.clone(),
         web::Data::new(config.clone()),
         Box::new(Argon2PasswordEncryptor::new()),
         Box::new(JwtTokenGenerator::new()),
     ));
 
+    let oidc_provider = config.oidc_issuer.clone().map(|issuer| {
+        Box::new(HttpOidcProvider::new(
+            issuer,
+            config.oidc_client_id.clone(),
+            config.oidc_client_secret.clone(),
+            config.oidc_redirect_uri.clone(),
+        )) as Box<dyn OidcProviderPort>
+    });
+
+    let oidc_service = web::Data::new(OidcService::new(
+        identity_repository.clone(),
+        user_repository.clone(),
+        auth_service.clone(),
+        password_encryptor.clone(),
+        oidc_provider,
+    ));
+
+    let export_service = web::Data::new(ExportService::new(
+        export_job_repository.clone(),
+        user_repository.clone(),
+        user_service.clone(),
+        web::Data::new(config.clone()),
+    ));
+
+    let avatar_storage: Box<dyn FileStoragePort> = match config.avatar_storage.as_str() {
+        "memory" => Box::new(InMemoryFileStorage::new()),
+        _ => Box::new(LocalFileStorage::new(config.avatar_storage_dir.clone())),
+    };
+
+    let avatar_service = web::Data::new(AvatarService::new(
+        user_repository.clone(),
+        avatar_storage,
+        config.avatar_max_bytes,
+    ));
+
+    let privacy_service = web::Data::new(PrivacyService::new(
+        privacy_repository.clone(),
+        avatar_service.clone(),
+        password_encryptor.clone(),
+    ));
+
+    let organization_service = web::Data::new(OrganizationService::new(
+        organization_repository.clone(),
+        user_repository.clone(),
+        auth_service.clone(),
+    ));
+
+    let user_attribute_service = web::Data::new(UserAttributeService::new(user_attribute_repository.clone()));
+
+    let user_invitation_service = web::Data::new(UserInvitationService::new(
+        user_invitation_repository.clone(),
+        user_repository.clone(),
+        user_service.clone(),
+        mailer.clone(),
+        web::Data::new(config.clone()),
+    ));
+
+    //Start background jobs
+    spawn_user_purge(
+        user_service.clone(),
+        config.user_purge_retention_days,
+        config.user_purge_interval_seconds,
+    );
+    spawn_export_cleanup(export_service.clone(), config.export_cleanup_interval_seconds);
+    spawn_login_attempt_purge(
+        login_activity_service.clone(),
+        config.login_attempt_retention_days,
+        config.login_attempt_purge_interval_seconds,
+    );
+
     //Start the server
     let server_addr = config.server_addr.clone();
     info!("Server will be started at: http://{}", server_addr);
@@ -59,6 +215,7 @@ async fn main() -> std::io::Result<()> {
             .allow_any_origin()
             .allow_any_method()
             .allow_any_header()
+            .expose_headers(["ETag", "X-Request-Id"])
             .max_age(3600);
 
         App::new()
@@ -66,8 +223,21 @@ async fn main() -> std::io::Result<()> {
             .wrap(Logger::default())
             .wrap(AuthMiddleware)
             .app_data(user_repository.clone())
+            .app_data(organization_repository.clone())
             .app_data(user_service.clone())
             .app_data(auth_service.clone())
+            .app_data(oauth_service.clone())
+            .app_data(oidc_service.clone())
+            .app_data(export_service.clone())
+            .app_data(avatar_service.clone())
+            .app_data(organization_service.clone())
+            .app_data(user_attribute_service.clone())
+            .app_data(user_invitation_service.clone())
+            .app_data(privacy_service.clone())
+            .app_data(email_change_service.clone())
+            .app_data(audit_service.clone())
+            .app_data(login_activity_service.clone())
+            .app_data(device_alert_service.clone())
             .app_data(web::Data::new(config.clone()))
             .configure(configure_routes)
     })
@@ -1,18 +1,30 @@
 use actix_web::{
-    dev::{forward_ready, Service, ServiceRequest, ServiceResponse, Transform},
-    error::ErrorUnauthorized, Error, HttpMessage, web,
+    dev::{forward_ready, Payload, Service, ServiceRequest, ServiceResponse, Transform},
+    error::ErrorUnauthorized, http::header::{HeaderName, HeaderValue}, Error, FromRequest, HttpMessage, HttpRequest, web,
 };
-use futures::future::{err, ok, Ready, LocalBoxFuture};
+use futures::future::{err, ok, ready, Ready, LocalBoxFuture};
 use jsonwebtoken::{decode, DecodingKey, Validation};
+use log::error;
+use std::future::Future;
+use std::rc::Rc;
+use uuid::Uuid;
+use crate::core::contracts::repository::organizations::OrganizationRepository;
+use crate::core::contracts::repository::users::UserRepository;
 use crate::core::entities::auth::ClaimsToUserToken;
+use crate::core::entities::user::{ROLE_ADMIN, STATUS_ACTIVE};
 use crate::config::config_env::Config;
-use crate::utils::validations::is_public_route;
+use crate::config::request_context::{request_context, RequestContext, CURRENT_REQUEST, REQUEST_ID_HEADER};
+use crate::config::tenant::{TenantScope, CURRENT_TENANT};
+use crate::repositories::organization_repository::PgOrganizationRepository;
+use crate::repositories::user_repository::PgUserRepository;
+use crate::utils::errors::AppError;
+use crate::utils::validations::{is_client_authenticated_route, is_password_change_route, is_public_route, is_signed_link_route};
 
 pub struct AuthMiddleware;
 
 impl<S, B> Transform<S, ServiceRequest> for AuthMiddleware
 where
-    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error>,
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
     S::Future: 'static,
     B: 'static,
 {
@@ -23,17 +35,17 @@ where
     type Future = Ready<Result<Self::Transform, Self::InitError>>;
 
     fn new_transform(&self, service: S) -> Self::Future {
-        ok(AuthMiddlewareService { service })
+        ok(AuthMiddlewareService { service: Rc::new(service) })
     }
 }
 
 pub struct AuthMiddlewareService<S> {
-    service: S,
+    service: Rc<S>,
 }
 
 impl<S, B> Service<ServiceRequest> for AuthMiddlewareService<S>
 where
-    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error>,
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
     S::Future: 'static,
     B: 'static,
 {
@@ -50,23 +62,87 @@ where
             return Box::pin(err(e));
         }
 
+        // Autenticação e links com token: ainda não existe organização ativa, então o acesso a todas é declarado
         if is_public_route(req.path()) {
-            return Box::pin(self.service.call(req));
+            let context = request_context(&req, None);
+            return Box::pin(with_request_context(context, CURRENT_TENANT.scope(TenantScope::All, self.service.call(req))));
         }
 
-        match self.verify_jwt_token(&req, config) {
-            Ok(claims) => {
-                req.extensions_mut().insert(claims);
-                Box::pin(self.service.call(req))
-            }
-            Err(e) => Box::pin(err(e)),
+        let claims = match self.verify_jwt_token(&req, config) {
+            Ok(claims) => claims,
+            Err(e) => return Box::pin(err(e)),
+        };
+
+        // Tokens delegados a clientes OAuth servem às outras aplicações, que os validam pela introspecção
+        if claims.client_id.is_some() {
+            return Box::pin(err(ErrorUnauthorized("OAuth access tokens are not accepted by this API")));
         }
+
+        if claims.password_expired && !is_password_change_route(req.method(), req.path(), &claims.id) {
+            return Box::pin(err(AppError::Forbidden("Password expired; change your password to continue".into()).into()));
+        }
+
+        // O status é consultado a cada requisição: suspender uma conta ou revogar as sessões invalida os tokens já emitidos
+        let service = Rc::clone(&self.service);
+        let user_repo = req.app_data::<web::Data<PgUserRepository>>().cloned();
+        let organization_repo = req.app_data::<web::Data<PgOrganizationRepository>>().cloned();
+        Box::pin(async move {
+            if let (Some(user_repo), Some(user_id)) = (user_repo, claims.user_id()) {
+                match user_repo.find_session_state(user_id).await {
+                    Ok(Some(state)) => {
+                        ensure_active_status(&state.status)?;
+                        // `iat` tem precisão de segundos: um token do mesmo segundo da revogação também cai
+                        if state.sessions_revoked_at.is_some_and(|revoked_at| claims.iat as i64 <= revoked_at.and_utc().timestamp()) {
+                            return Err(ErrorUnauthorized("Session has been revoked"));
+                        }
+                    }
+                    Ok(None) => return Err(ErrorUnauthorized("Account not found")),
+                    Err(e) => {
+                        error!("Database error while checking account status: {:?}", e);
+                        return Err(AppError::InternalServerError.into());
+                    }
+                }
+
+                // Quem saiu ou foi removido da organização perde o escopo dela mesmo com o token ainda válido
+                if let (Some(organization_repo), Some(organization_id)) = (organization_repo, claims.organization_id) {
+                    match organization_repo.find_membership(organization_id, user_id).await {
+                        Ok(Some(_)) => {}
+                        Ok(None) => return Err(ErrorUnauthorized("Organization membership is no longer valid")),
+                        Err(e) => {
+                            error!("Database error while checking organization membership: {:?}", e);
+                            return Err(AppError::InternalServerError.into());
+                        }
+                    }
+                }
+            }
+
+            // O acesso ao banco feito durante a requisição fica restrito ao escopo do token (RLS)
+            let scope = tenant_scope(&claims);
+            let context = request_context(&req, claims.user_id());
+            req.extensions_mut().insert(claims);
+            with_request_context(context, CURRENT_TENANT.scope(scope, service.call(req))).await
+        })
+    }
+}
+
+// O id da requisição volta no cabeçalho da resposta para o cliente citar ao reportar um problema
+async fn with_request_context<B>(
+    context: RequestContext,
+    call: impl Future<Output = Result<ServiceResponse<B>, Error>>,
+) -> Result<ServiceResponse<B>, Error> {
+    let request_id = context.request_id.clone();
+    let mut response = CURRENT_REQUEST.scope(context, call).await?;
+    if let Some(value) = request_id.and_then(|id| HeaderValue::from_str(&id).ok()) {
+        response.headers_mut().insert(HeaderName::from_static(REQUEST_ID_HEADER), value);
     }
+    Ok(response)
 }
 
 impl<S> AuthMiddlewareService<S> {
     fn verify_api_key(&self, req: &ServiceRequest, config: &Config) -> Result<(), Error> {
-        if req.path().starts_with("/api/swagger") {
+        if req.path().starts_with("/api/swagger")
+            || is_client_authenticated_route(req.path())
+            || is_signed_link_route(req.path()) {
             return Ok(());
         }
 
@@ -101,3 +177,59 @@ impl<S> AuthMiddlewareService<S> {
         .map_err(|_| ErrorUnauthorized("Invalid token"))
     }
 }
+
+// Permite receber as claims do token autenticado diretamente nos controllers
+impl FromRequest for ClaimsToUserToken {
+    type Error = Error;
+    type Future = Ready<Result<Self, Self::Error>>;
+
+    fn from_request(req: &HttpRequest, _: &mut Payload) -> Self::Future {
+        ready(
+            req.extensions()
+                .get::<ClaimsToUserToken>()
+                .cloned()
+                .ok_or_else(|| ErrorUnauthorized("Missing token claims")),
+        )
+    }
+}
+
+pub fn authenticated_user_id(claims: &ClaimsToUserToken) -> Result<Uuid, AppError> {
+    claims.user_id().ok_or_else(|| AppError::Unauthorized("Invalid token".into()))
+}
+
+pub fn require_role(claims: &ClaimsToUserToken, roles: &[&str]) -> Result<(), AppError> {
+    if roles.contains(&claims.role.as_str()) {
+        Ok(())
+    } else {
+        Err(AppError::Forbidden("You do not have permission to perform this action".into()))
+    }
+}
+
+// Escopo das listagens: a organização ativa do token; administradores globais sem organização ativa veem todos
+pub fn organization_scope(claims: &ClaimsToUserToken) -> Result<Option<Uuid>, AppError> {
+    match claims.organization_id {
+        Some(organization_id) => Ok(Some(organization_id)),
+        None if claims.role == ROLE_ADMIN => Ok(None),
+        None => Err(AppError::Forbidden("An active organization is required; switch to one of your organizations".into())),
+    }
+}
+
+// Escopo do banco (RLS): apenas administradores globais sem organização ativa alcançam todas as organizações;
+// os demais usuários sem organização veem somente a própria conta
+pub fn tenant_scope(claims: &ClaimsToUserToken) -> TenantScope {
+    match (claims.organization_id, claims.user_id()) {
+        (Some(organization_id), _) => TenantScope::Organization(organization_id),
+        (None, _) if claims.role == ROLE_ADMIN => TenantScope::All,
+        (None, Some(user_id)) => TenantScope::User(user_id),
+        (None, None) => TenantScope::Undeclared,
+    }
+}
+
+// Apenas contas ativas podem autenticar; o status é informado para o cliente exibir a mensagem certa
+pub fn ensure_active_status(status: &str) -> Result<(), AppError> {
+    if status == STATUS_ACTIVE {
+        Ok(())
+    } else {
+        Err(AppError::AccountInactive(format!("Account is {}", status)))
+    }
+}
@@ -0,0 +1,163 @@
+use async_stream::try_stream;
+use async_trait::async_trait;
+use chrono::NaiveDateTime;
+use futures::stream::BoxStream;
+use futures::TryStreamExt;
+use log::info;
+use sqlx::{PgConnection, PgPool, Postgres, QueryBuilder};
+use sqlx::types::Json;
+use uuid::Uuid;
+use crate::config::querys::audit::AuditQueries;
+use crate::config::querys::tenant::TenantQueries;
+use crate::config::request_context::current_request;
+use crate::config::tenant::{begin_tenant_transaction, current_scope, current_tenant, TenantScope};
+use crate::core::contracts::repository::audits::AuditRepository;
+use crate::core::entities::audit::{AuditChainLink, AuditEntry, AuditFilters, NewAuditEntry};
+use crate::utils::crypto::random_key;
+
+#[derive(Clone)]
+pub struct PgAuditRepository {
+    pool: PgPool,
+}
+
+impl PgAuditRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+// Grava as entradas na transação da alteração: uma falha na auditoria desfaz a alteração junto.
+// Chamada logo antes do commit, já que o lock do encadeamento vale até o fim da transação
+pub async fn write_audit_entries(conn: &mut PgConnection, entries: Vec<NewAuditEntry>) -> Result<(), sqlx::Error> {
+    if entries.is_empty() {
+        return Ok(());
+    }
+    info!("[Repository] Executing SQL query to create {} audit entries", entries.len());
+    let context = current_request();
+    let organization_id = current_tenant();
+    let occurred_at = chrono::Utc::now().naive_utc();
+
+    // A política do audit_log só aceita a entrada da organização ativa; sem organização a entrada
+    // fica de fora de todas, então a transação declara o bypass. Vale só até o commit que vem em seguida
+    match organization_id {
+        Some(tenant) => sqlx::query(TenantQueries::SET_CURRENT_TENANT).bind(tenant.to_string()).execute(&mut *conn).await?,
+        None => sqlx::query(TenantQueries::BYPASS_RLS).execute(&mut *conn).await?,
+    };
+
+    for mut entry in entries {
+        if entry.has_personal_data() {
+            let key = match entry.data_subject() {
+                Some(user_id) => Some(subject_key(conn, user_id, occurred_at).await?),
+                None => None,
+            };
+            entry.pseudonymize(key.as_deref());
+        }
+
+        sqlx::query(AuditQueries::CREATE_ENTRY)
+            .bind(Uuid::new_v4())
+            .bind(occurred_at)
+            .bind(context.actor_id)
+            .bind(organization_id)
+            .bind(&context.request_id)
+            .bind(&context.ip)
+            .bind(entry.action)
+            .bind(entry.resource_type)
+            .bind(entry.resource_id)
+            .bind(Json(entry.changes))
+            .execute(&mut *conn)
+            .await?;
+    }
+    Ok(())
+}
+
+async fn subject_key(conn: &mut PgConnection, user_id: Uuid, created_at: NaiveDateTime) -> Result<Vec<u8>, sqlx::Error> {
+    sqlx::query_scalar(AuditQueries::FIND_OR_CREATE_SUBJECT_KEY)
+        .bind(user_id)
+        .bind(random_key())
+        .bind(created_at)
+        .fetch_one(&mut *conn)
+        .await
+}
+
+#[async_trait]
+impl AuditRepository for PgAuditRepository {
+    // A cadeia é uma só para todas as organizações
+    async fn find_chain_links(&self, after_seq: i64, limit: i64) -> Result<Vec<AuditChainLink>, sqlx::Error> {
+        let mut tx = begin_tenant_transaction(&self.pool, TenantScope::All).await?;
+        let links = sqlx::query_as(AuditQueries::FIND_CHAIN_LINKS)
+            .bind(after_seq)
+            .bind(limit)
+            .fetch_all(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+        Ok(links)
+    }
+
+    async fn find_entries(&self, filters: &AuditFilters) -> Result<Vec<AuditEntry>, sqlx::Error> {
+        info!("[Repository] Executing SQL query to find audit entries");
+        let mut tx = begin_tenant_transaction(&self.pool, current_scope()).await?;
+        let entries = audit_entries_query(filters)
+            .build_query_as()
+            .fetch_all(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+        Ok(entries)
+    }
+
+    // Exportação: as linhas são entregues conforme chegam do banco
+    fn stream_entries(&self, filters: AuditFilters) -> BoxStream<'static, Result<AuditEntry, sqlx::Error>> {
+        let pool = self.pool.clone();
+        // O corpo é consumido depois do handler retornar, fora do escopo da requisição
+        let scope = current_scope();
+
+        Box::pin(try_stream! {
+            info!("[Repository] Executing SQL query to stream audit entries");
+            let mut tx = begin_tenant_transaction(&pool, scope).await?;
+            let mut query = audit_entries_query(&filters);
+            {
+                let mut rows = query.build_query_as::<AuditEntry>().fetch(&mut *tx);
+                while let Some(entry) = rows.try_next().await? {
+                    yield entry;
+                }
+            }
+            tx.commit().await?;
+        })
+    }
+}
+
+// Mais recentes primeiro; a paginação segue por `seq` estritamente menor que o da última entrada entregue
+fn audit_entries_query(filters: &AuditFilters) -> QueryBuilder<'static, Postgres> {
+    let mut query = QueryBuilder::<Postgres>::new(AuditQueries::FIND_ENTRIES);
+    if let Some(organization_id) = filters.organization_id {
+        query.push(" AND organization_id = ").push_bind(organization_id);
+    }
+    if let Some(actor_id) = filters.actor_id {
+        query.push(" AND actor_id = ").push_bind(actor_id);
+    }
+    if let Some(resource_type) = &filters.resource_type {
+        query.push(" AND resource_type = ").push_bind(resource_type.clone());
+    }
+    if let Some(resource_id) = filters.resource_id {
+        query.push(" AND resource_id = ").push_bind(resource_id);
+    }
+    if let Some(actions) = &filters.actions {
+        query.push(" AND action = ANY(").push_bind(actions.clone()).push(")");
+    }
+    if let Some(from) = filters.from {
+        query.push(" AND occurred_at >= ").push_bind(from);
+    }
+    if let Some(to) = filters.to {
+        query.push(" AND occurred_at <= ").push_bind(to);
+    }
+    if let Some(before_seq) = filters.before_seq {
+        query.push(" AND seq < ").push_bind(before_seq);
+    }
+
+    query.push(" ORDER BY seq DESC");
+    if let Some(limit) = filters.limit {
+        query.push(" LIMIT ").push_bind(limit);
+    }
+    query
+}
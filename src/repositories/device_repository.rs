@@ -0,0 +1,184 @@
+use async_trait::async_trait;
+use log::info;
+use sqlx::{FromRow, PgPool, Row};
+use uuid::Uuid;
+use crate::config::querys::device::DeviceQueries;
+use crate::config::querys::user::UserQueries;
+use crate::config::tenant::{begin_tenant_transaction, TenantScope};
+use crate::core::contracts::repository::devices::DeviceRepository;
+use crate::core::entities::audit::{AuditTrail, NewAuditEntry};
+use crate::core::entities::device::{CreateDeviceAlert, DeviceAlert, DeviceAlertReport, DeviceFingerprint, DeviceSighting, KnownDevice};
+use crate::repositories::audit_repository::write_audit_entries;
+
+#[derive(Clone)]
+pub struct PgDeviceRepository {
+    pool: PgPool,
+}
+
+impl PgDeviceRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+// Usado no login e pelos links públicos, antes de existir uma organização ativa
+#[async_trait]
+impl DeviceRepository for PgDeviceRepository {
+    async fn record_device(&self, user_id: Uuid, fingerprint: &DeviceFingerprint) -> Result<DeviceSighting, sqlx::Error> {
+        info!("[Repository] Executing SQL query to record device of user with ID: {}", user_id);
+        let row = sqlx::query(DeviceQueries::UPSERT_DEVICE)
+            .bind(Uuid::new_v4())
+            .bind(user_id)
+            .bind(&fingerprint.fingerprint)
+            .bind(&fingerprint.user_agent)
+            .bind(&fingerprint.ip_network)
+            .bind(chrono::Utc::now().naive_utc())
+            .fetch_one(&self.pool)
+            .await?;
+        let device = KnownDevice::from_row(&row)?;
+        let new_device: bool = row.try_get("inserted")?;
+
+        let first_device = new_device
+            && !sqlx::query_scalar::<_, bool>(DeviceQueries::HAS_OTHER_DEVICES)
+                .bind(user_id)
+                .bind(device.id)
+                .fetch_one(&self.pool)
+                .await?;
+
+        Ok(DeviceSighting { device, new_device, first_device })
+    }
+
+    async fn create_alert(&self, alert: CreateDeviceAlert) -> Result<DeviceAlert, sqlx::Error> {
+        let id = Uuid::new_v4();
+        info!("[Repository] Executing SQL query to create device alert with ID: {}", id);
+        sqlx::query_as(DeviceQueries::CREATE_ALERT)
+            .bind(id)
+            .bind(alert.user_id)
+            .bind(alert.device_id)
+            .bind(alert.token_hash)
+            .bind(alert.ip)
+            .bind(alert.user_agent)
+            .bind(alert.expires_at)
+            .bind(chrono::Utc::now().naive_utc())
+            .fetch_one(&self.pool)
+            .await
+    }
+
+    async fn find_alert_by_token(&self, token_hash: &str) -> Result<Option<DeviceAlert>, sqlx::Error> {
+        sqlx::query_as(DeviceQueries::FIND_ALERT_BY_TOKEN)
+            .bind(token_hash)
+            .fetch_optional(&self.pool)
+            .await
+    }
+
+    // Numa transação: o aviso só conta como denunciado se as sessões forem de fato revogadas
+    async fn report_alert(&self, alert: &DeviceAlert, audit: AuditTrail<DeviceAlertReport>) -> Result<Option<DeviceAlertReport>, sqlx::Error> {
+        info!("[Repository] Executing SQL queries to report device alert with ID: {}", alert.id);
+        let now = chrono::Utc::now().naive_utc();
+        // O link do aviso vale fora de qualquer organização
+        let mut tx = begin_tenant_transaction(&self.pool, TenantScope::All).await?;
+
+        let reported = sqlx::query(DeviceQueries::MARK_ALERT_REPORTED)
+            .bind(alert.id)
+            .bind(now)
+            .execute(&mut *tx)
+            .await?;
+        if reported.rows_affected() == 0 {
+            return Ok(None);
+        }
+
+        let revoked = sqlx::query(DeviceQueries::REVOKE_USER_SESSIONS)
+            .bind(alert.user_id)
+            .bind(now)
+            .execute(&mut *tx)
+            .await?;
+        if revoked.rows_affected() == 0 {
+            return Err(sqlx::Error::RowNotFound);
+        }
+
+        let refresh_tokens_revoked = sqlx::query(DeviceQueries::REVOKE_REFRESH_TOKENS)
+            .bind(alert.user_id)
+            .bind(now)
+            .execute(&mut *tx)
+            .await?
+            .rows_affected();
+
+        let passkeys_removed = sqlx::query(DeviceQueries::DELETE_PASSKEYS_SINCE)
+            .bind(alert.user_id)
+            .bind(alert.created_at)
+            .execute(&mut *tx)
+            .await?
+            .rows_affected();
+
+        let identities_removed = sqlx::query(DeviceQueries::DELETE_IDENTITIES_SINCE)
+            .bind(alert.user_id)
+            .bind(alert.created_at)
+            .execute(&mut *tx)
+            .await?
+            .rows_affected();
+
+        // Esquecido, o dispositivo volta a gerar aviso se entrar de novo
+        if let Some(device_id) = alert.device_id {
+            sqlx::query(DeviceQueries::FORGET_DEVICE)
+                .bind(device_id)
+                .execute(&mut *tx)
+                .await?;
+        }
+
+        let report = DeviceAlertReport {
+            user_id: alert.user_id,
+            sessions_revoked_at: now,
+            refresh_tokens_revoked,
+            passkeys_removed,
+            identities_removed,
+            password_reset_required: true,
+        };
+        write_audit_entries(&mut tx, audit(&report)).await?;
+        tx.commit().await?;
+        Ok(Some(report))
+    }
+
+    async fn reset_password(&self, alert: &DeviceAlert, password_hash: &str, history_size: i64, audit: Vec<NewAuditEntry>) -> Result<bool, sqlx::Error> {
+        info!("[Repository] Executing SQL queries to reset password through device alert with ID: {}", alert.id);
+        let now = chrono::Utc::now().naive_utc();
+        let mut tx = begin_tenant_transaction(&self.pool, TenantScope::All).await?;
+
+        let marked = sqlx::query(DeviceQueries::MARK_ALERT_PASSWORD_RESET)
+            .bind(alert.id)
+            .bind(now)
+            .execute(&mut *tx)
+            .await?;
+        if marked.rows_affected() == 0 {
+            return Ok(false);
+        }
+
+        if history_size > 0 {
+            sqlx::query(UserQueries::ARCHIVE_PASSWORD)
+                .bind(alert.user_id)
+                .bind(Uuid::new_v4())
+                .bind(now)
+                .execute(&mut *tx)
+                .await?;
+        }
+
+        let updated = sqlx::query(DeviceQueries::RESET_PASSWORD)
+            .bind(alert.user_id)
+            .bind(password_hash)
+            .bind(now)
+            .execute(&mut *tx)
+            .await?;
+        if updated.rows_affected() == 0 {
+            return Err(sqlx::Error::RowNotFound);
+        }
+
+        sqlx::query(UserQueries::PRUNE_PASSWORD_HISTORY)
+            .bind(alert.user_id)
+            .bind(history_size.max(0))
+            .execute(&mut *tx)
+            .await?;
+
+        write_audit_entries(&mut tx, audit).await?;
+        tx.commit().await?;
+        Ok(true)
+    }
+}
@@ -0,0 +1,135 @@
+use async_trait::async_trait;
+use chrono::NaiveDateTime;
+use log::info;
+use sqlx::PgPool;
+use uuid::Uuid;
+use crate::config::querys::email_change::EmailChangeQueries;
+use crate::config::tenant::{begin_tenant_transaction, TenantScope};
+use crate::core::contracts::repository::email_changes::EmailChangeRepository;
+use crate::core::entities::audit::NewAuditEntry;
+use crate::core::entities::email_change::{CreateEmailChange, EmailChange};
+use crate::repositories::audit_repository::write_audit_entries;
+
+#[derive(Clone)]
+pub struct PgEmailChangeRepository {
+    pool: PgPool,
+}
+
+impl PgEmailChangeRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl EmailChangeRepository for PgEmailChangeRepository {
+    async fn create_email_change(&self, data: CreateEmailChange) -> Result<EmailChange, sqlx::Error> {
+        let id = Uuid::new_v4();
+        let created_at = chrono::Utc::now().naive_utc();
+
+        info!("[Repository] Executing SQL query to create email change with ID: {}", id);
+        let mut tx = self.pool.begin().await?;
+
+        sqlx::query(EmailChangeQueries::CANCEL_PENDING_FOR_USER)
+            .bind(data.user_id)
+            .bind(created_at)
+            .execute(&mut *tx)
+            .await?;
+
+        let change: EmailChange = sqlx::query_as(EmailChangeQueries::CREATE_EMAIL_CHANGE)
+            .bind(id)
+            .bind(data.user_id)
+            .bind(data.old_email)
+            .bind(data.new_email)
+            .bind(data.confirm_token_hash)
+            .bind(data.revert_token_hash)
+            .bind(data.confirm_expires_at)
+            .bind(data.revert_expires_at)
+            .bind(created_at)
+            .fetch_one(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+        Ok(change)
+    }
+
+    async fn find_by_confirm_token(&self, token_hash: &str) -> Result<Option<EmailChange>, sqlx::Error> {
+        sqlx::query_as(EmailChangeQueries::FIND_BY_CONFIRM_TOKEN)
+            .bind(token_hash)
+            .fetch_optional(&self.pool)
+            .await
+    }
+
+    async fn find_by_revert_token(&self, token_hash: &str) -> Result<Option<EmailChange>, sqlx::Error> {
+        sqlx::query_as(EmailChangeQueries::FIND_BY_REVERT_TOKEN)
+            .bind(token_hash)
+            .fetch_optional(&self.pool)
+            .await
+    }
+
+    async fn confirm_email_change(&self, change: &EmailChange, audit: Vec<NewAuditEntry>) -> Result<bool, sqlx::Error> {
+        info!("[Repository] Executing SQL query to confirm email change with ID: {}", change.id);
+        let now = chrono::Utc::now().naive_utc();
+        // Os links de confirmação e de reversão valem fora de qualquer organização
+        let mut tx = begin_tenant_transaction(&self.pool, TenantScope::All).await?;
+
+        let confirmed = sqlx::query(EmailChangeQueries::CONFIRM_EMAIL_CHANGE)
+            .bind(change.id)
+            .bind(now)
+            .execute(&mut *tx)
+            .await?;
+        if confirmed.rows_affected() == 0 {
+            return Ok(false);
+        }
+
+        let swapped = sqlx::query(EmailChangeQueries::SWAP_USER_EMAIL)
+            .bind(change.user_id)
+            .bind(&change.old_email)
+            .bind(&change.new_email)
+            .bind(now)
+            .execute(&mut *tx)
+            .await?;
+        if swapped.rows_affected() == 0 {
+            return Ok(false);
+        }
+
+        write_audit_entries(&mut tx, audit).await?;
+        tx.commit().await?;
+        Ok(true)
+    }
+
+    // Antes da confirmação apenas cancela a troca; depois dela devolve o email antigo
+    async fn revert_email_change(&self, change: &EmailChange, audit: Vec<NewAuditEntry>) -> Result<bool, sqlx::Error> {
+        info!("[Repository] Executing SQL query to revert email change with ID: {}", change.id);
+        let now = chrono::Utc::now().naive_utc();
+        let mut tx = begin_tenant_transaction(&self.pool, TenantScope::All).await?;
+
+        // O estado de confirmação vem da linha atualizada, não da leitura anterior
+        let reverted: Option<(Option<NaiveDateTime>,)> = sqlx::query_as(EmailChangeQueries::REVERT_EMAIL_CHANGE)
+            .bind(change.id)
+            .bind(now)
+            .fetch_optional(&mut *tx)
+            .await?;
+        let Some((confirmed_at,)) = reverted else {
+            return Ok(false);
+        };
+
+        if confirmed_at.is_some() {
+            let swapped = sqlx::query(EmailChangeQueries::SWAP_USER_EMAIL)
+                .bind(change.user_id)
+                .bind(&change.new_email)
+                .bind(&change.old_email)
+                .bind(now)
+                .execute(&mut *tx)
+                .await?;
+            if swapped.rows_affected() == 0 {
+                return Ok(false);
+            }
+            // Só há o que auditar quando o email de fato volta; cancelar a troca pendente não altera o usuário
+            write_audit_entries(&mut tx, audit).await?;
+        }
+
+        tx.commit().await?;
+        Ok(true)
+    }
+}
@@ -0,0 +1,148 @@
+use async_trait::async_trait;
+use chrono::NaiveDateTime;
+use log::info;
+use sqlx::types::Json;
+use sqlx::{PgPool, Postgres, Transaction};
+use uuid::Uuid;
+use crate::core::contracts::repository::export_jobs::ExportJobRepository;
+use crate::core::entities::export_job::{ExportFilters, ExportJob};
+use crate::config::querys::export_job::ExportJobQueries;
+use crate::config::tenant::{begin_tenant_transaction, current_scope};
+
+#[derive(Clone)]
+pub struct PgExportJobRepository {
+    pool: PgPool,
+}
+
+impl PgExportJobRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    // Jobs pedidos numa organização ficam visíveis apenas nela; o processamento e a limpeza rodam
+    // com o escopo declarado por quem os dispara
+    async fn begin(&self) -> Result<Transaction<'static, Postgres>, sqlx::Error> {
+        begin_tenant_transaction(&self.pool, current_scope()).await
+    }
+}
+
+#[async_trait]
+impl ExportJobRepository for PgExportJobRepository {
+    async fn create_job(&self, requested_by: Uuid, organization_id: Option<Uuid>, format: &str, filters: ExportFilters) -> Result<ExportJob, sqlx::Error> {
+        let id = Uuid::new_v4();
+
+        info!("[Repository] Executing SQL query to create export job with ID: {}", id);
+        let mut tx = self.begin().await?;
+        let job: ExportJob = sqlx::query_as(ExportJobQueries::CREATE_JOB)
+            .bind(id)
+            .bind(requested_by)
+            .bind(organization_id)
+            .bind(format)
+            .bind(Json(filters))
+            .bind(chrono::Utc::now().naive_utc())
+            .fetch_one(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+        Ok(job)
+    }
+
+    async fn find_job_by_id(&self, id: Uuid) -> Result<ExportJob, sqlx::Error> {
+        let mut tx = self.begin().await?;
+        let job: ExportJob = sqlx::query_as(ExportJobQueries::FIND_BY_ID)
+            .bind(id)
+            .fetch_one(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+        Ok(job)
+    }
+
+    async fn start_job(&self, id: Uuid, total_rows: i64) -> Result<(), sqlx::Error> {
+        let mut tx = self.begin().await?;
+        sqlx::query(ExportJobQueries::START_JOB)
+            .bind(id)
+            .bind(total_rows)
+            .bind(chrono::Utc::now().naive_utc())
+            .execute(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    async fn update_progress(&self, id: Uuid, processed_rows: i64) -> Result<(), sqlx::Error> {
+        let mut tx = self.begin().await?;
+        sqlx::query(ExportJobQueries::UPDATE_PROGRESS)
+            .bind(id)
+            .bind(processed_rows)
+            .execute(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    async fn complete_job(&self, id: Uuid, processed_rows: i64, file_path: &str, expires_at: NaiveDateTime) -> Result<(), sqlx::Error> {
+        info!("[Repository] Executing SQL query to complete export job with ID: {}", id);
+        let mut tx = self.begin().await?;
+        sqlx::query(ExportJobQueries::COMPLETE_JOB)
+            .bind(id)
+            .bind(processed_rows)
+            .bind(file_path)
+            .bind(chrono::Utc::now().naive_utc())
+            .bind(expires_at)
+            .execute(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    async fn fail_job(&self, id: Uuid, error: &str) -> Result<(), sqlx::Error> {
+        info!("[Repository] Executing SQL query to fail export job with ID: {}", id);
+        let mut tx = self.begin().await?;
+        sqlx::query(ExportJobQueries::FAIL_JOB)
+            .bind(id)
+            .bind(error)
+            .bind(chrono::Utc::now().naive_utc())
+            .execute(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    async fn fail_interrupted_jobs(&self) -> Result<Vec<ExportJob>, sqlx::Error> {
+        let mut tx = self.begin().await?;
+        let jobs: Vec<ExportJob> = sqlx::query_as(ExportJobQueries::FAIL_INTERRUPTED_JOBS)
+            .bind(chrono::Utc::now().naive_utc())
+            .fetch_all(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+        Ok(jobs)
+    }
+
+    async fn find_expired_jobs(&self, now: NaiveDateTime) -> Result<Vec<ExportJob>, sqlx::Error> {
+        let mut tx = self.begin().await?;
+        let jobs: Vec<ExportJob> = sqlx::query_as(ExportJobQueries::FIND_EXPIRED_JOBS)
+            .bind(now)
+            .fetch_all(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+        Ok(jobs)
+    }
+
+    async fn expire_job(&self, id: Uuid) -> Result<(), sqlx::Error> {
+        let mut tx = self.begin().await?;
+        sqlx::query(ExportJobQueries::EXPIRE_JOB)
+            .bind(id)
+            .execute(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+        Ok(())
+    }
+}
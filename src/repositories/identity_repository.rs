@@ -0,0 +1,104 @@
+use async_trait::async_trait;
+use log::info;
+use sqlx::PgPool;
+use uuid::Uuid;
+use crate::core::contracts::repository::identities::IdentityRepository;
+use crate::core::entities::audit::NewAuditEntry;
+use crate::core::entities::identity::{CreateUserIdentity, OidcLoginState, UserIdentity};
+use crate::config::querys::identity::IdentityQueries;
+use crate::repositories::audit_repository::write_audit_entries;
+
+#[derive(Clone)]
+pub struct PgIdentityRepository {
+    pool: PgPool,
+}
+
+impl PgIdentityRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl IdentityRepository for PgIdentityRepository {
+    async fn create_identity(&self, data: CreateUserIdentity) -> Result<UserIdentity, sqlx::Error> {
+        let id = Uuid::new_v4();
+        let date = chrono::Utc::now().naive_utc();
+
+        info!("[Repository] Executing SQL query to link identity {} to user ID: {}", data.subject, data.user_id);
+        let identity: UserIdentity = sqlx::query_as(IdentityQueries::CREATE_IDENTITY)
+            .bind(id)
+            .bind(data.user_id)
+            .bind(data.issuer)
+            .bind(data.subject)
+            .bind(data.email)
+            .bind(date)
+            .fetch_one(&self.pool)
+            .await?;
+
+        Ok(identity)
+    }
+
+    async fn find_identity_by_subject(&self, issuer: &str, subject: &str) -> Result<UserIdentity, sqlx::Error> {
+        let identity: UserIdentity = sqlx::query_as(IdentityQueries::FIND_BY_SUBJECT)
+            .bind(issuer)
+            .bind(subject)
+            .fetch_one(&self.pool)
+            .await?;
+
+        Ok(identity)
+    }
+
+    async fn find_identities_by_user(&self, user_id: Uuid) -> Result<Vec<UserIdentity>, sqlx::Error> {
+        let identities: Vec<UserIdentity> = sqlx::query_as(IdentityQueries::FIND_BY_USER)
+            .bind(user_id)
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(identities)
+    }
+
+    async fn delete_identity(&self, id: Uuid, user_id: Uuid, audit: Vec<NewAuditEntry>) -> Result<bool, sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+        let result = sqlx::query(IdentityQueries::DELETE_IDENTITY)
+            .bind(id)
+            .bind(user_id)
+            .execute(&mut *tx)
+            .await?;
+
+        let deleted = result.rows_affected() > 0;
+        if deleted {
+            write_audit_entries(&mut tx, audit).await?;
+        }
+        tx.commit().await?;
+        Ok(deleted)
+    }
+
+    async fn create_login_state(&self, data: OidcLoginState) -> Result<(), sqlx::Error> {
+        sqlx::query(IdentityQueries::DELETE_EXPIRED_LOGIN_STATES)
+            .bind(chrono::Utc::now().naive_utc())
+            .execute(&self.pool)
+            .await?;
+
+        sqlx::query(IdentityQueries::CREATE_LOGIN_STATE)
+            .bind(data.state_hash)
+            .bind(data.nonce)
+            .bind(data.code_verifier)
+            .bind(data.link_user_id)
+            .bind(data.expires_at)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn take_login_state(&self, state_hash: &str) -> Result<OidcLoginState, sqlx::Error> {
+        let state: OidcLoginState = sqlx::query_as(IdentityQueries::TAKE_LOGIN_STATE)
+            .bind(state_hash)
+            .bind(chrono::Utc::now().naive_utc())
+            .fetch_one(&self.pool)
+            .await?;
+
+        Ok(state)
+    }
+}
@@ -0,0 +1,62 @@
+use async_trait::async_trait;
+use chrono::NaiveDateTime;
+use log::info;
+use sqlx::PgPool;
+use uuid::Uuid;
+use crate::config::querys::login_attempt::LoginAttemptQueries;
+use crate::config::request_context::RequestContext;
+use crate::core::contracts::repository::login_attempts::LoginAttemptRepository;
+use crate::core::entities::login_attempt::{LoginActivityCursor, LoginAttempt, NewLoginAttempt};
+
+#[derive(Clone)]
+pub struct PgLoginAttemptRepository {
+    pool: PgPool,
+}
+
+impl PgLoginAttemptRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+// Fora das políticas de RLS: a tentativa acontece antes de existir uma organização ativa
+#[async_trait]
+impl LoginAttemptRepository for PgLoginAttemptRepository {
+    async fn create_attempt(&self, attempt: NewLoginAttempt, context: &RequestContext) -> Result<(), sqlx::Error> {
+        info!("[Repository] Executing SQL query to record login attempt for email: {}", attempt.email);
+        sqlx::query(LoginAttemptQueries::CREATE)
+            .bind(Uuid::new_v4())
+            .bind(attempt.user_id)
+            .bind(&attempt.email)
+            .bind(attempt.succeeded)
+            .bind(attempt.failure_reason)
+            .bind(&context.ip)
+            .bind(&context.user_agent)
+            .bind(&context.request_id)
+            .bind(chrono::Utc::now().naive_utc())
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    async fn find_attempts_by_user(&self, user_id: Uuid, after: Option<LoginActivityCursor>, limit: i64) -> Result<Vec<LoginAttempt>, sqlx::Error> {
+        info!("[Repository] Executing SQL query to find login attempts of user with ID: {}", user_id);
+        let (created_at, id) = after.map(|cursor| (cursor.created_at, cursor.id)).unzip();
+        sqlx::query_as(LoginAttemptQueries::FIND_BY_USER)
+            .bind(user_id)
+            .bind(created_at)
+            .bind(id)
+            .bind(limit)
+            .fetch_all(&self.pool)
+            .await
+    }
+
+    async fn delete_attempts_older_than(&self, cutoff: NaiveDateTime) -> Result<u64, sqlx::Error> {
+        info!("[Repository] Executing SQL query to delete login attempts older than {}", cutoff);
+        let result = sqlx::query(LoginAttemptQueries::DELETE_OLDER_THAN)
+            .bind(cutoff)
+            .execute(&self.pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+}
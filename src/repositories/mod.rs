@@ -1 +1,13 @@
 pub mod user_repository;
+pub mod passkey_repository;
+pub mod oauth_repository;
+pub mod identity_repository;
+pub mod export_job_repository;
+pub mod organization_repository;
+pub mod user_attribute_repository;
+pub mod user_invitation_repository;
+pub mod privacy_repository;
+pub mod email_change_repository;
+pub mod audit_repository;
+pub mod login_attempt_repository;
+pub mod device_repository;
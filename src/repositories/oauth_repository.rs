@@ -0,0 +1,131 @@
+use async_trait::async_trait;
+use log::info;
+use sqlx::PgPool;
+use uuid::Uuid;
+use crate::core::contracts::repository::oauth::OAuthRepository;
+use crate::core::entities::oauth::{NewOAuthClient, OAuthAuthorizationCode, OAuthClient, OAuthRefreshToken};
+use crate::config::querys::oauth::OAuthQueries;
+
+#[derive(Clone)]
+pub struct PgOAuthRepository {
+    pool: PgPool,
+}
+
+impl PgOAuthRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl OAuthRepository for PgOAuthRepository {
+    async fn create_client(&self, data: NewOAuthClient) -> Result<OAuthClient, sqlx::Error> {
+        let id = Uuid::new_v4();
+        let date = chrono::Utc::now().naive_utc();
+
+        info!("[Repository] Executing SQL query to create OAuth client: {}", data.client_id);
+        let client: OAuthClient = sqlx::query_as(OAuthQueries::CREATE_CLIENT)
+            .bind(id)
+            .bind(data.client_id)
+            .bind(data.client_secret_hash)
+            .bind(data.name)
+            .bind(data.redirect_uris)
+            .bind(data.owner_id)
+            .bind(date)
+            .fetch_one(&self.pool)
+            .await?;
+
+        info!("[Repository] OAuth client successfully inserted into database with ID: {}", client.id);
+        Ok(client)
+    }
+
+    async fn find_client_by_client_id(&self, client_id: &str) -> Result<OAuthClient, sqlx::Error> {
+        let client: OAuthClient = sqlx::query_as(OAuthQueries::FIND_CLIENT_BY_CLIENT_ID)
+            .bind(client_id)
+            .fetch_one(&self.pool)
+            .await?;
+
+        Ok(client)
+    }
+
+    async fn find_clients_by_owner(&self, owner_id: Uuid) -> Result<Vec<OAuthClient>, sqlx::Error> {
+        let clients: Vec<OAuthClient> = sqlx::query_as(OAuthQueries::FIND_CLIENTS_BY_OWNER)
+            .bind(owner_id)
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(clients)
+    }
+
+    async fn delete_client(&self, id: Uuid, owner_id: Uuid) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query(OAuthQueries::DELETE_CLIENT)
+            .bind(id)
+            .bind(owner_id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn create_authorization_code(&self, data: OAuthAuthorizationCode) -> Result<(), sqlx::Error> {
+        info!("[Repository] Executing SQL query to create authorization code for client: {}", data.client_id);
+        sqlx::query(OAuthQueries::CREATE_AUTHORIZATION_CODE)
+            .bind(data.code_hash)
+            .bind(data.client_id)
+            .bind(data.user_id)
+            .bind(data.redirect_uri)
+            .bind(data.scope)
+            .bind(data.code_challenge)
+            .bind(data.expires_at)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn take_authorization_code(&self, code_hash: &str) -> Result<OAuthAuthorizationCode, sqlx::Error> {
+        let code: OAuthAuthorizationCode = sqlx::query_as(OAuthQueries::TAKE_AUTHORIZATION_CODE)
+            .bind(code_hash)
+            .bind(chrono::Utc::now().naive_utc())
+            .fetch_one(&self.pool)
+            .await?;
+
+        Ok(code)
+    }
+
+    async fn create_refresh_token(&self, token_hash: &str, client_id: &str, user_id: Uuid, scope: &str, ttl_seconds: i64) -> Result<(), sqlx::Error> {
+        let date = chrono::Utc::now().naive_utc();
+
+        sqlx::query(OAuthQueries::CREATE_REFRESH_TOKEN)
+            .bind(token_hash)
+            .bind(client_id)
+            .bind(user_id)
+            .bind(scope)
+            .bind(date + chrono::Duration::seconds(ttl_seconds))
+            .bind(date)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn find_refresh_token(&self, token_hash: &str) -> Result<OAuthRefreshToken, sqlx::Error> {
+        let token: OAuthRefreshToken = sqlx::query_as(OAuthQueries::FIND_REFRESH_TOKEN)
+            .bind(token_hash)
+            .fetch_one(&self.pool)
+            .await?;
+
+        Ok(token)
+    }
+
+    async fn revoke_refresh_token(&self, token_hash: &str, client_id: &str) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query(OAuthQueries::REVOKE_REFRESH_TOKEN)
+            .bind(token_hash)
+            .bind(client_id)
+            .bind(chrono::Utc::now().naive_utc())
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
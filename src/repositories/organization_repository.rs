@@ -0,0 +1,307 @@
+use async_trait::async_trait;
+use chrono::NaiveDateTime;
+use log::info;
+use sqlx::{PgPool, Postgres, Row, Transaction};
+use uuid::Uuid;
+use crate::core::contracts::repository::organizations::OrganizationRepository;
+use crate::core::entities::organization::{
+    CreateOrganization, MembershipChange, Organization, OrganizationInvitation, OrganizationMember, OrganizationMembership,
+    UserOrganization, ORG_ROLE_OWNER,
+};
+use crate::config::querys::organization::OrganizationQueries;
+use crate::config::tenant::{begin_tenant_transaction, TenantScope};
+
+#[derive(Clone)]
+pub struct PgOrganizationRepository {
+    pool: PgPool,
+}
+
+impl PgOrganizationRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    // Vínculos e convites são conferidos pelos serviços e alcançam todas as organizações do usuário,
+    // não só a ativa (listagem, troca de organização e aceite de convites)
+    async fn begin(&self) -> Result<Transaction<'static, Postgres>, sqlx::Error> {
+        begin_tenant_transaction(&self.pool, TenantScope::All).await
+    }
+
+    // Retorna o vínculo e se ele é o único proprietário, com os proprietários travados até o fim da transação
+    async fn lock_membership(
+        tx: &mut Transaction<'static, Postgres>,
+        organization_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<Option<(OrganizationMembership, bool)>, sqlx::Error> {
+        let owners: Vec<Uuid> = sqlx::query(OrganizationQueries::LOCK_OWNERS)
+            .bind(organization_id)
+            .fetch_all(&mut **tx)
+            .await?
+            .iter()
+            .map(|row| row.get("user_id"))
+            .collect();
+
+        let membership: Option<OrganizationMembership> = sqlx::query_as(OrganizationQueries::FIND_MEMBERSHIP)
+            .bind(organization_id)
+            .bind(user_id)
+            .fetch_optional(&mut **tx)
+            .await?;
+
+        Ok(membership.map(|membership| {
+            let last_owner = membership.role == ORG_ROLE_OWNER && owners.len() <= 1;
+            (membership, last_owner)
+        }))
+    }
+}
+
+#[async_trait]
+impl OrganizationRepository for PgOrganizationRepository {
+    async fn create_organization(&self, data: CreateOrganization, owner_id: Uuid) -> Result<Organization, sqlx::Error> {
+        let id = Uuid::new_v4();
+        let created_at = chrono::Utc::now().naive_utc();
+
+        info!("[Repository] Executing SQL query to create organization with ID: {}", id);
+        let mut tx = self.begin().await?;
+
+        let organization: Organization = sqlx::query_as(OrganizationQueries::CREATE_ORGANIZATION)
+            .bind(id)
+            .bind(data.name)
+            .bind(data.slug)
+            .bind(created_at)
+            .fetch_one(&mut *tx)
+            .await?;
+
+        sqlx::query(OrganizationQueries::CREATE_MEMBERSHIP)
+            .bind(id)
+            .bind(owner_id)
+            .bind(ORG_ROLE_OWNER)
+            .bind(created_at)
+            .execute(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+        Ok(organization)
+    }
+
+    async fn find_organization_by_id(&self, id: Uuid) -> Result<Organization, sqlx::Error> {
+        let mut tx = self.begin().await?;
+        let organization: Organization = sqlx::query_as(OrganizationQueries::FIND_BY_ID)
+            .bind(id)
+            .fetch_one(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+        Ok(organization)
+    }
+
+    async fn find_organizations_by_user(&self, user_id: Uuid) -> Result<Vec<UserOrganization>, sqlx::Error> {
+        let mut tx = self.begin().await?;
+        let organizations: Vec<UserOrganization> = sqlx::query_as(OrganizationQueries::FIND_BY_USER)
+            .bind(user_id)
+            .fetch_all(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+        Ok(organizations)
+    }
+
+    async fn update_organization(&self, id: Uuid, name: String) -> Result<Organization, sqlx::Error> {
+        let mut tx = self.begin().await?;
+        let organization: Organization = sqlx::query_as(OrganizationQueries::UPDATE_ORGANIZATION)
+            .bind(id)
+            .bind(name)
+            .bind(chrono::Utc::now().naive_utc())
+            .fetch_one(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+        Ok(organization)
+    }
+
+    async fn delete_organization(&self, id: Uuid) -> Result<bool, sqlx::Error> {
+        info!("[Repository] Executing SQL query to delete organization with ID: {}", id);
+        let mut tx = self.begin().await?;
+        let result = sqlx::query(OrganizationQueries::DELETE_ORGANIZATION)
+            .bind(id)
+            .execute(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn find_membership(&self, organization_id: Uuid, user_id: Uuid) -> Result<Option<OrganizationMembership>, sqlx::Error> {
+        let mut tx = self.begin().await?;
+        let membership: Option<OrganizationMembership> = sqlx::query_as(OrganizationQueries::FIND_MEMBERSHIP)
+            .bind(organization_id)
+            .bind(user_id)
+            .fetch_optional(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+        Ok(membership)
+    }
+
+    async fn find_default_organization(&self, user_id: Uuid) -> Result<Option<Uuid>, sqlx::Error> {
+        let mut tx = self.begin().await?;
+        let row = sqlx::query(OrganizationQueries::FIND_DEFAULT_ORGANIZATION)
+            .bind(user_id)
+            .fetch_optional(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+        Ok(row.map(|row| row.get("organization_id")))
+    }
+
+    async fn find_members(&self, organization_id: Uuid) -> Result<Vec<OrganizationMember>, sqlx::Error> {
+        let mut tx = self.begin().await?;
+        let members: Vec<OrganizationMember> = sqlx::query_as(OrganizationQueries::FIND_MEMBERS)
+            .bind(organization_id)
+            .fetch_all(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+        Ok(members)
+    }
+
+    async fn update_membership_role(&self, organization_id: Uuid, user_id: Uuid, role: &str) -> Result<MembershipChange<OrganizationMembership>, sqlx::Error> {
+        info!("[Repository] Executing SQL query to change role of user {} in organization {}", user_id, organization_id);
+        let mut tx = self.begin().await?;
+
+        match Self::lock_membership(&mut tx, organization_id, user_id).await? {
+            None => return Ok(MembershipChange::NotFound),
+            Some((_, true)) if role != ORG_ROLE_OWNER => return Ok(MembershipChange::LastOwner),
+            Some(_) => {}
+        }
+
+        let membership: OrganizationMembership = sqlx::query_as(OrganizationQueries::UPDATE_MEMBERSHIP_ROLE)
+            .bind(organization_id)
+            .bind(user_id)
+            .bind(role)
+            .fetch_one(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+        Ok(MembershipChange::Done(membership))
+    }
+
+    async fn delete_membership(&self, organization_id: Uuid, user_id: Uuid) -> Result<MembershipChange<()>, sqlx::Error> {
+        info!("[Repository] Executing SQL query to remove user {} from organization {}", user_id, organization_id);
+        let mut tx = self.begin().await?;
+
+        match Self::lock_membership(&mut tx, organization_id, user_id).await? {
+            None => return Ok(MembershipChange::NotFound),
+            Some((_, true)) => return Ok(MembershipChange::LastOwner),
+            Some(_) => {}
+        }
+
+        sqlx::query(OrganizationQueries::DELETE_MEMBERSHIP)
+            .bind(organization_id)
+            .bind(user_id)
+            .execute(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+        Ok(MembershipChange::Done(()))
+    }
+
+    async fn create_invitation(
+        &self,
+        organization_id: Uuid,
+        email: &str,
+        role: &str,
+        token_hash: &str,
+        invited_by: Uuid,
+        expires_at: NaiveDateTime,
+    ) -> Result<OrganizationInvitation, sqlx::Error> {
+        let id = Uuid::new_v4();
+        let created_at = chrono::Utc::now().naive_utc();
+
+        info!("[Repository] Executing SQL query to create invitation with ID: {}", id);
+        let mut tx = self.begin().await?;
+
+        sqlx::query(OrganizationQueries::DELETE_EXPIRED_INVITATION)
+            .bind(organization_id)
+            .bind(email)
+            .bind(created_at)
+            .execute(&mut *tx)
+            .await?;
+
+        let invitation: OrganizationInvitation = sqlx::query_as(OrganizationQueries::CREATE_INVITATION)
+            .bind(id)
+            .bind(organization_id)
+            .bind(email)
+            .bind(role)
+            .bind(token_hash)
+            .bind(invited_by)
+            .bind(expires_at)
+            .bind(created_at)
+            .fetch_one(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+        Ok(invitation)
+    }
+
+    async fn find_pending_invitations(&self, organization_id: Uuid) -> Result<Vec<OrganizationInvitation>, sqlx::Error> {
+        let mut tx = self.begin().await?;
+        let invitations: Vec<OrganizationInvitation> = sqlx::query_as(OrganizationQueries::FIND_PENDING_INVITATIONS)
+            .bind(organization_id)
+            .fetch_all(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+        Ok(invitations)
+    }
+
+    async fn find_invitation_by_token(&self, token_hash: &str) -> Result<Option<OrganizationInvitation>, sqlx::Error> {
+        let mut tx = self.begin().await?;
+        let invitation: Option<OrganizationInvitation> = sqlx::query_as(OrganizationQueries::FIND_INVITATION_BY_TOKEN)
+            .bind(token_hash)
+            .fetch_optional(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+        Ok(invitation)
+    }
+
+    async fn accept_invitation(&self, invitation: &OrganizationInvitation, user_id: Uuid) -> Result<OrganizationMembership, sqlx::Error> {
+        let accepted_at = chrono::Utc::now().naive_utc();
+
+        info!("[Repository] Executing SQL query to accept invitation with ID: {}", invitation.id);
+        let mut tx = self.begin().await?;
+
+        // Aceite concorrente do mesmo convite: apenas um marca o convite
+        let result = sqlx::query(OrganizationQueries::ACCEPT_INVITATION)
+            .bind(invitation.id)
+            .bind(accepted_at)
+            .execute(&mut *tx)
+            .await?;
+        if result.rows_affected() == 0 {
+            return Err(sqlx::Error::RowNotFound);
+        }
+
+        let membership: OrganizationMembership = sqlx::query_as(OrganizationQueries::CREATE_MEMBERSHIP)
+            .bind(invitation.organization_id)
+            .bind(user_id)
+            .bind(&invitation.role)
+            .bind(accepted_at)
+            .fetch_one(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+        Ok(membership)
+    }
+
+    async fn delete_invitation(&self, id: Uuid, organization_id: Uuid) -> Result<bool, sqlx::Error> {
+        let mut tx = self.begin().await?;
+        let result = sqlx::query(OrganizationQueries::DELETE_INVITATION)
+            .bind(id)
+            .bind(organization_id)
+            .execute(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+        Ok(result.rows_affected() > 0)
+    }
+}
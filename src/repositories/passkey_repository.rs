@@ -0,0 +1,123 @@
+use async_trait::async_trait;
+use log::info;
+use sqlx::PgPool;
+use uuid::Uuid;
+use crate::core::contracts::repository::passkeys::PasskeyRepository;
+use crate::core::entities::audit::{AuditTrail, NewAuditEntry};
+use crate::core::entities::passkey::{CreatePasskeyCredential, PasskeyChallenge, PasskeyCredential};
+use crate::config::querys::passkey::PasskeyQueries;
+use crate::repositories::audit_repository::write_audit_entries;
+
+#[derive(Clone)]
+pub struct PgPasskeyRepository {
+    pool: PgPool,
+}
+
+impl PgPasskeyRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl PasskeyRepository for PgPasskeyRepository {
+    async fn create_challenge(&self, user_id: Option<Uuid>, challenge: String, ceremony: &str, ttl_seconds: i64) -> Result<PasskeyChallenge, sqlx::Error> {
+        let now = chrono::Utc::now().naive_utc();
+
+        sqlx::query(PasskeyQueries::DELETE_EXPIRED_CHALLENGES)
+            .bind(now)
+            .execute(&self.pool)
+            .await?;
+
+        info!("[Repository] Executing SQL query to create {} challenge", ceremony);
+        let challenge: PasskeyChallenge = sqlx::query_as(PasskeyQueries::CREATE_CHALLENGE)
+            .bind(Uuid::new_v4())
+            .bind(user_id)
+            .bind(challenge)
+            .bind(ceremony)
+            .bind(now + chrono::Duration::seconds(ttl_seconds))
+            .fetch_one(&self.pool)
+            .await?;
+
+        Ok(challenge)
+    }
+
+    async fn take_challenge(&self, id: Uuid, ceremony: &str) -> Result<PasskeyChallenge, sqlx::Error> {
+        let challenge: PasskeyChallenge = sqlx::query_as(PasskeyQueries::TAKE_CHALLENGE)
+            .bind(id)
+            .bind(ceremony)
+            .bind(chrono::Utc::now().naive_utc())
+            .fetch_one(&self.pool)
+            .await?;
+
+        Ok(challenge)
+    }
+
+    async fn create_credential(&self, data: CreatePasskeyCredential, audit: AuditTrail<PasskeyCredential>) -> Result<PasskeyCredential, sqlx::Error> {
+        let id = Uuid::new_v4();
+        let date = chrono::Utc::now().naive_utc();
+
+        info!("[Repository] Executing SQL query to create passkey for user ID: {}", data.user_id);
+        let mut tx = self.pool.begin().await?;
+        let credential: PasskeyCredential = sqlx::query_as(PasskeyQueries::CREATE_CREDENTIAL)
+            .bind(id)
+            .bind(data.user_id)
+            .bind(data.credential_id)
+            .bind(data.public_key)
+            .bind(data.sign_count)
+            .bind(data.transports)
+            .bind(date)
+            .fetch_one(&mut *tx)
+            .await?;
+
+        write_audit_entries(&mut tx, audit(&credential)).await?;
+        tx.commit().await?;
+        info!("[Repository] Passkey successfully inserted into database with ID: {}", credential.id);
+        Ok(credential)
+    }
+
+    async fn find_credentials_by_user(&self, user_id: Uuid) -> Result<Vec<PasskeyCredential>, sqlx::Error> {
+        let credentials: Vec<PasskeyCredential> = sqlx::query_as(PasskeyQueries::FIND_BY_USER)
+            .bind(user_id)
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(credentials)
+    }
+
+    async fn find_credential_by_credential_id(&self, credential_id: &str) -> Result<PasskeyCredential, sqlx::Error> {
+        let credential: PasskeyCredential = sqlx::query_as(PasskeyQueries::FIND_BY_CREDENTIAL_ID)
+            .bind(credential_id)
+            .fetch_one(&self.pool)
+            .await?;
+
+        Ok(credential)
+    }
+
+    async fn update_sign_count(&self, id: Uuid, sign_count: i64) -> Result<(), sqlx::Error> {
+        sqlx::query(PasskeyQueries::UPDATE_SIGN_COUNT)
+            .bind(id)
+            .bind(sign_count)
+            .bind(chrono::Utc::now().naive_utc())
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn delete_credential(&self, id: Uuid, user_id: Uuid, audit: Vec<NewAuditEntry>) -> Result<bool, sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+        let result = sqlx::query(PasskeyQueries::DELETE_CREDENTIAL)
+            .bind(id)
+            .bind(user_id)
+            .execute(&mut *tx)
+            .await?;
+
+        let deleted = result.rows_affected() > 0;
+        if deleted {
+            write_audit_entries(&mut tx, audit).await?;
+        }
+        tx.commit().await?;
+        Ok(deleted)
+    }
+}
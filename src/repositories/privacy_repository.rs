@@ -0,0 +1,217 @@
+use async_trait::async_trait;
+use log::info;
+use sqlx::{PgPool, Postgres, Transaction};
+use uuid::Uuid;
+use crate::config::querys::organization::OrganizationQueries;
+use crate::config::querys::privacy::PrivacyQueries;
+use crate::config::querys::user::UserQueries;
+use crate::config::tenant::{begin_tenant_transaction, current_scope, TenantScope};
+use crate::core::contracts::repository::privacy::PrivacyRepository;
+use crate::core::entities::audit::AuditTrail;
+use crate::core::entities::privacy::{
+    erased_email, ErasureCandidate, ErasureChange, ExportedProfile, UserDataExport, UserErasureReport, ERASED_FULL_NAME,
+    USER_DATA_EXPORT_VERSION,
+};
+use crate::core::entities::user::STATUS_DEACTIVATED;
+use crate::repositories::audit_repository::write_audit_entries;
+
+// Registros mantidos na anonimização: preservam a integridade referencial e a trilha de auditoria
+const RETAINED_RECORDS: [&str; 5] = ["organization_memberships", "oauth_clients", "user_status_history", "export_jobs", "audit_log"];
+
+#[derive(Clone)]
+pub struct PgPrivacyRepository {
+    pool: PgPool,
+}
+
+impl PgPrivacyRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    async fn begin(&self) -> Result<Transaction<'static, Postgres>, sqlx::Error> {
+        begin_tenant_transaction(&self.pool, current_scope()).await
+    }
+}
+
+#[async_trait]
+impl PrivacyRepository for PgPrivacyRepository {
+    async fn find_profile(&self, id: Uuid) -> Result<Option<ExportedProfile>, sqlx::Error> {
+        let mut tx = self.begin().await?;
+        let profile: Option<ExportedProfile> = sqlx::query_as(PrivacyQueries::FIND_PROFILE)
+            .bind(id)
+            .fetch_optional(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+        Ok(profile)
+    }
+
+    // Lido numa única transação para que o arquivo seja um retrato consistente
+    async fn find_user_data(&self, id: Uuid) -> Result<Option<UserDataExport>, sqlx::Error> {
+        info!("[Repository] Executing SQL queries to collect data of user with ID: {}", id);
+        let mut tx = self.begin().await?;
+        let now = chrono::Utc::now().naive_utc();
+
+        let Some(profile) = sqlx::query_as::<_, ExportedProfile>(PrivacyQueries::FIND_PROFILE)
+            .bind(id)
+            .fetch_optional(&mut *tx)
+            .await?
+        else {
+            return Ok(None);
+        };
+
+        let organizations = sqlx::query_as(OrganizationQueries::FIND_BY_USER).bind(id).fetch_all(&mut *tx).await?;
+        let sessions = sqlx::query_as(PrivacyQueries::FIND_SESSIONS).bind(id).fetch_all(&mut *tx).await?;
+        let consents = sqlx::query_as(PrivacyQueries::FIND_CONSENTS).bind(id).bind(now).fetch_all(&mut *tx).await?;
+        let passkeys = sqlx::query_as(PrivacyQueries::FIND_PASSKEYS).bind(id).fetch_all(&mut *tx).await?;
+        let identities = sqlx::query_as(PrivacyQueries::FIND_IDENTITIES).bind(id).fetch_all(&mut *tx).await?;
+        let oauth_clients = sqlx::query_as(PrivacyQueries::FIND_OAUTH_CLIENTS).bind(id).fetch_all(&mut *tx).await?;
+        let invitations = sqlx::query_as(PrivacyQueries::FIND_INVITATIONS)
+            .bind(id)
+            .bind(&profile.email)
+            .fetch_all(&mut *tx)
+            .await?;
+        let email_changes = sqlx::query_as(PrivacyQueries::FIND_EMAIL_CHANGES).bind(id).fetch_all(&mut *tx).await?;
+        let export_jobs = sqlx::query_as(PrivacyQueries::FIND_EXPORT_JOBS).bind(id).fetch_all(&mut *tx).await?;
+        let status_history = sqlx::query_as(PrivacyQueries::FIND_STATUS_HISTORY).bind(id).fetch_all(&mut *tx).await?;
+        let audit = sqlx::query_as(PrivacyQueries::FIND_AUDIT).bind(id).fetch_all(&mut *tx).await?;
+        let login_activity = sqlx::query_as(PrivacyQueries::FIND_LOGIN_ATTEMPTS).bind(id).fetch_all(&mut *tx).await?;
+        let devices = sqlx::query_as(PrivacyQueries::FIND_KNOWN_DEVICES).bind(id).fetch_all(&mut *tx).await?;
+
+        tx.commit().await?;
+        Ok(Some(UserDataExport {
+            version: USER_DATA_EXPORT_VERSION,
+            generated_at: now,
+            profile,
+            organizations,
+            sessions,
+            consents,
+            passkeys,
+            identities,
+            oauth_clients,
+            invitations,
+            email_changes,
+            export_jobs,
+            status_history,
+            audit,
+            login_activity,
+            devices,
+        }))
+    }
+
+    // Em todas as organizações: a visibilidade do usuário já foi conferida, e os convites de outras
+    // organizações também precisam ser anonimizados
+    async fn erase_user(&self, id: Uuid, password_hash: &str, erased_by: Uuid, audit: AuditTrail<UserErasureReport>) -> Result<Option<UserErasureReport>, sqlx::Error> {
+        info!("[Repository] Executing SQL queries to erase personal data of user with ID: {}", id);
+        let mut tx = begin_tenant_transaction(&self.pool, TenantScope::All).await?;
+        let now = chrono::Utc::now().naive_utc();
+
+        let Some(candidate) = sqlx::query_as::<_, ErasureCandidate>(PrivacyQueries::LOCK_USER_FOR_ERASURE)
+            .bind(id)
+            .fetch_optional(&mut *tx)
+            .await?
+        else {
+            return Ok(None);
+        };
+
+        let anonymized_email = erased_email(id);
+        sqlx::query(PrivacyQueries::ERASE_USER)
+            .bind(id)
+            .bind(ERASED_FULL_NAME)
+            .bind(&anonymized_email)
+            .bind(password_hash)
+            .bind(STATUS_DEACTIVATED)
+            .bind(now)
+            .execute(&mut *tx)
+            .await?;
+
+        let mut changed_fields = vec!["full_name", "email", "password"];
+        if candidate.has_attributes {
+            changed_fields.push("attributes");
+        }
+        if candidate.has_avatar {
+            changed_fields.push("avatar");
+        }
+        if candidate.status != STATUS_DEACTIVATED {
+            changed_fields.push("status");
+        }
+
+        sqlx::query(UserQueries::CREATE_STATUS_HISTORY)
+            .bind(Uuid::new_v4())
+            .bind(id)
+            .bind(&candidate.status)
+            .bind(STATUS_DEACTIVATED)
+            .bind("Personal data erased")
+            .bind(erased_by)
+            .bind(now)
+            .execute(&mut *tx)
+            .await?;
+
+        let mut changes = vec![
+            ErasureChange { resource: "users", action: "anonymized", count: 1 },
+            ErasureChange { resource: "user_status_history", action: "created", count: 1 },
+        ];
+
+        let steps: [(&str, &'static str, &'static str); 11] = [
+            (PrivacyQueries::DELETE_IDENTITIES, "user_identities", "deleted"),
+            (PrivacyQueries::DELETE_OIDC_LOGIN_STATES, "oidc_login_states", "deleted"),
+            (PrivacyQueries::DELETE_PASSKEYS, "webauthn_credentials", "deleted"),
+            (PrivacyQueries::DELETE_PASSKEY_CHALLENGES, "webauthn_challenges", "deleted"),
+            (PrivacyQueries::DELETE_AUTHORIZATION_CODES, "oauth_authorization_codes", "deleted"),
+            (PrivacyQueries::DELETE_EMAIL_CHANGES, "email_changes", "deleted"),
+            (PrivacyQueries::DELETE_LOGIN_ATTEMPTS, "login_attempts", "deleted"),
+            (PrivacyQueries::DELETE_DEVICE_ALERTS, "device_alerts", "deleted"),
+            (PrivacyQueries::DELETE_KNOWN_DEVICES, "known_devices", "deleted"),
+            (PrivacyQueries::DELETE_PASSWORD_HISTORY, "password_history", "deleted"),
+            (PrivacyQueries::DELETE_AUDIT_KEY, "user_audit_keys", "deleted"),
+        ];
+        for (query, resource, action) in steps {
+            let count = sqlx::query(query).bind(id).execute(&mut *tx).await?.rows_affected();
+            if count > 0 {
+                changes.push(ErasureChange { resource, action, count });
+            }
+        }
+
+        let revoked = sqlx::query(PrivacyQueries::REVOKE_SESSIONS)
+            .bind(id)
+            .bind(now)
+            .execute(&mut *tx)
+            .await?
+            .rows_affected();
+        if revoked > 0 {
+            changes.push(ErasureChange { resource: "oauth_refresh_tokens", action: "revoked", count: revoked });
+        }
+
+        let invitations = sqlx::query(PrivacyQueries::ANONYMIZE_USER_INVITATIONS)
+            .bind(id)
+            .bind(&candidate.email)
+            .bind(&anonymized_email)
+            .execute(&mut *tx)
+            .await?
+            .rows_affected();
+        if invitations > 0 {
+            changes.push(ErasureChange { resource: "user_invitations", action: "anonymized", count: invitations });
+        }
+
+        let invitations = sqlx::query(PrivacyQueries::ANONYMIZE_ORGANIZATION_INVITATIONS)
+            .bind(&candidate.email)
+            .bind(&anonymized_email)
+            .execute(&mut *tx)
+            .await?
+            .rows_affected();
+        if invitations > 0 {
+            changes.push(ErasureChange { resource: "organization_invitations", action: "anonymized", count: invitations });
+        }
+
+        let report = UserErasureReport {
+            user_id: id,
+            erased_at: now,
+            changed_fields,
+            changes,
+            retained: RETAINED_RECORDS.to_vec(),
+        };
+        write_audit_entries(&mut tx, audit(&report)).await?;
+        tx.commit().await?;
+        Ok(Some(report))
+    }
+}
@@ -0,0 +1,97 @@
+use async_trait::async_trait;
+use log::info;
+use sqlx::PgPool;
+use crate::config::querys::user_attribute::UserAttributeQueries;
+use crate::config::tenant::{begin_tenant_transaction, TenantScope};
+use crate::core::contracts::repository::user_attributes::UserAttributeRepository;
+use crate::core::entities::audit::AuditTrail;
+use crate::core::entities::user_attribute::{
+    CreateUserAttributeDefinition, RemovedUserAttribute, UpdateUserAttributeDefinition, UserAttributeDefinition,
+};
+use crate::repositories::audit_repository::write_audit_entries;
+
+#[derive(Clone)]
+pub struct PgUserAttributeRepository {
+    pool: PgPool,
+}
+
+impl PgUserAttributeRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl UserAttributeRepository for PgUserAttributeRepository {
+    async fn create_definition(&self, data: CreateUserAttributeDefinition) -> Result<UserAttributeDefinition, sqlx::Error> {
+        info!("[Repository] Executing SQL query to create user attribute: {}", data.key);
+        let label = data.label.unwrap_or_else(|| data.key.clone());
+        let definition: UserAttributeDefinition = sqlx::query_as(UserAttributeQueries::CREATE_DEFINITION)
+            .bind(data.key)
+            .bind(label)
+            .bind(data.attribute_type)
+            .bind(data.required)
+            .bind(data.allowed_values)
+            .bind(chrono::Utc::now().naive_utc())
+            .fetch_one(&self.pool)
+            .await?;
+
+        Ok(definition)
+    }
+
+    async fn find_definitions(&self) -> Result<Vec<UserAttributeDefinition>, sqlx::Error> {
+        let definitions: Vec<UserAttributeDefinition> = sqlx::query_as(UserAttributeQueries::FIND_ALL)
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(definitions)
+    }
+
+    async fn find_definition(&self, key: &str) -> Result<Option<UserAttributeDefinition>, sqlx::Error> {
+        let definition: Option<UserAttributeDefinition> = sqlx::query_as(UserAttributeQueries::FIND_BY_KEY)
+            .bind(key)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        Ok(definition)
+    }
+
+    async fn update_definition(&self, key: &str, data: UpdateUserAttributeDefinition) -> Result<UserAttributeDefinition, sqlx::Error> {
+        info!("[Repository] Executing SQL query to update user attribute: {}", key);
+        let definition: UserAttributeDefinition = sqlx::query_as(UserAttributeQueries::UPDATE_DEFINITION)
+            .bind(key)
+            .bind(data.label)
+            .bind(data.required)
+            .bind(data.allowed_values)
+            .bind(chrono::Utc::now().naive_utc())
+            .fetch_one(&self.pool)
+            .await?;
+
+        Ok(definition)
+    }
+
+    // A definição e os valores gravados nos usuários de todas as organizações saem juntos
+    async fn delete_definition(&self, key: &str, audit: AuditTrail<Vec<RemovedUserAttribute>>) -> Result<bool, sqlx::Error> {
+        info!("[Repository] Executing SQL query to delete user attribute: {}", key);
+        let mut tx = begin_tenant_transaction(&self.pool, TenantScope::All).await?;
+
+        let result = sqlx::query(UserAttributeQueries::DELETE_DEFINITION)
+            .bind(key)
+            .execute(&mut *tx)
+            .await?;
+        if result.rows_affected() == 0 {
+            return Ok(false);
+        }
+
+        let removed: Vec<RemovedUserAttribute> = sqlx::query_as(UserAttributeQueries::REMOVE_FROM_USERS)
+            .bind(key)
+            .bind(chrono::Utc::now().naive_utc())
+            .fetch_all(&mut *tx)
+            .await?;
+        info!("[Repository] Removed attribute {} from {} users", key, removed.len());
+
+        write_audit_entries(&mut tx, audit(&removed)).await?;
+        tx.commit().await?;
+        Ok(true)
+    }
+}
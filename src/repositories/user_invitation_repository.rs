@@ -0,0 +1,177 @@
+use async_trait::async_trait;
+use chrono::NaiveDateTime;
+use log::info;
+use sqlx::{PgPool, Postgres, Transaction};
+use uuid::Uuid;
+use crate::config::querys::user_invitation::UserInvitationQueries;
+use crate::config::tenant::{begin_tenant_transaction, current_scope};
+use crate::core::contracts::repository::user_invitations::UserInvitationRepository;
+use crate::core::entities::audit::AuditTrail;
+use crate::core::entities::organization::ORG_ROLE_MEMBER;
+use crate::core::entities::user::UserDataCreated;
+use crate::core::entities::user_invitation::UserInvitation;
+use crate::repositories::audit_repository::write_audit_entries;
+
+#[derive(Clone)]
+pub struct PgUserInvitationRepository {
+    pool: PgPool,
+}
+
+impl PgUserInvitationRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    // Convites de uma organização passam pelas políticas de RLS da organização ativa
+    async fn begin(&self) -> Result<Transaction<'static, Postgres>, sqlx::Error> {
+        begin_tenant_transaction(&self.pool, current_scope()).await
+    }
+}
+
+#[async_trait]
+impl UserInvitationRepository for PgUserInvitationRepository {
+    async fn create_invitation(
+        &self,
+        email: &str,
+        role: &str,
+        organization_id: Option<Uuid>,
+        organization_role: Option<String>,
+        invited_by: Uuid,
+        expires_at: NaiveDateTime,
+    ) -> Result<UserInvitation, sqlx::Error> {
+        let id = Uuid::new_v4();
+        let created_at = chrono::Utc::now().naive_utc();
+
+        info!("[Repository] Executing SQL query to create user invitation with ID: {}", id);
+        let mut tx = self.begin().await?;
+
+        sqlx::query(UserInvitationQueries::REVOKE_EXPIRED_FOR_EMAIL)
+            .bind(email)
+            .bind(created_at)
+            .execute(&mut *tx)
+            .await?;
+
+        let invitation: UserInvitation = sqlx::query_as(UserInvitationQueries::CREATE_INVITATION)
+            .bind(id)
+            .bind(email)
+            .bind(role)
+            .bind(organization_id)
+            .bind(organization_role)
+            .bind(invited_by)
+            .bind(created_at)
+            .bind(expires_at)
+            .fetch_one(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+        Ok(invitation)
+    }
+
+    async fn find_invitations(&self) -> Result<Vec<UserInvitation>, sqlx::Error> {
+        let mut tx = self.begin().await?;
+        let invitations: Vec<UserInvitation> = sqlx::query_as(UserInvitationQueries::FIND_ALL)
+            .fetch_all(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+        Ok(invitations)
+    }
+
+    async fn find_invitation_by_id(&self, id: Uuid) -> Result<Option<UserInvitation>, sqlx::Error> {
+        let mut tx = self.begin().await?;
+        let invitation: Option<UserInvitation> = sqlx::query_as(UserInvitationQueries::FIND_BY_ID)
+            .bind(id)
+            .fetch_optional(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+        Ok(invitation)
+    }
+
+    async fn resend_invitation(&self, id: Uuid, expires_at: NaiveDateTime) -> Result<Option<UserInvitation>, sqlx::Error> {
+        info!("[Repository] Executing SQL query to resend user invitation with ID: {}", id);
+        let mut tx = self.begin().await?;
+        let invitation: Option<UserInvitation> = sqlx::query_as(UserInvitationQueries::RESEND_INVITATION)
+            .bind(id)
+            .bind(chrono::Utc::now().naive_utc())
+            .bind(expires_at)
+            .fetch_optional(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+        Ok(invitation)
+    }
+
+    async fn revoke_invitation(&self, id: Uuid) -> Result<Option<UserInvitation>, sqlx::Error> {
+        info!("[Repository] Executing SQL query to revoke user invitation with ID: {}", id);
+        let mut tx = self.begin().await?;
+        let invitation: Option<UserInvitation> = sqlx::query_as(UserInvitationQueries::REVOKE_INVITATION)
+            .bind(id)
+            .bind(chrono::Utc::now().naive_utc())
+            .fetch_optional(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+        Ok(invitation)
+    }
+
+    async fn claim_invitation(&self, invitation: &UserInvitation) -> Result<bool, sqlx::Error> {
+        let mut tx = self.begin().await?;
+        let result = sqlx::query(UserInvitationQueries::CLAIM_INVITATION)
+            .bind(invitation.id)
+            .bind(invitation.send_count)
+            .bind(chrono::Utc::now().naive_utc())
+            .execute(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn release_invitation(&self, id: Uuid) -> Result<(), sqlx::Error> {
+        let mut tx = self.begin().await?;
+        sqlx::query(UserInvitationQueries::RELEASE_INVITATION)
+            .bind(id)
+            .execute(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    // Aplica à conta criada os papéis definidos no convite; a mudança de papel é auditada na mesma transação
+    async fn complete_invitation(&self, invitation: &UserInvitation, user_id: Uuid, audit: AuditTrail<UserDataCreated>) -> Result<(), sqlx::Error> {
+        info!("[Repository] Executing SQL query to complete user invitation with ID: {}", invitation.id);
+        let date = chrono::Utc::now().naive_utc();
+        let mut tx = self.begin().await?;
+
+        sqlx::query(UserInvitationQueries::COMPLETE_INVITATION)
+            .bind(invitation.id)
+            .bind(user_id)
+            .execute(&mut *tx)
+            .await?;
+
+        let promoted: Option<UserDataCreated> = sqlx::query_as(UserInvitationQueries::ASSIGN_USER_ROLE)
+            .bind(user_id)
+            .bind(&invitation.role)
+            .bind(date)
+            .fetch_optional(&mut *tx)
+            .await?;
+
+        if let Some(organization_id) = invitation.organization_id {
+            sqlx::query(UserInvitationQueries::ADD_ORGANIZATION_MEMBERSHIP)
+                .bind(organization_id)
+                .bind(user_id)
+                .bind(invitation.organization_role.as_deref().unwrap_or(ORG_ROLE_MEMBER))
+                .bind(date)
+                .execute(&mut *tx)
+                .await?;
+        }
+
+        if let Some(user) = promoted {
+            write_audit_entries(&mut tx, audit(&user)).await?;
+        }
+        tx.commit().await?;
+        Ok(())
+    }
+}
@@ -1,12 +1,25 @@
+use async_stream::try_stream;
 use async_trait::async_trait;
+use futures::stream::BoxStream;
+use futures::TryStreamExt;
+use chrono::NaiveDateTime;
 use log::info;
-use sqlx::{PgPool, Row};
+use sqlx::types::Json;
+use sqlx::{PgPool, Postgres, QueryBuilder, Row, Transaction};
 use uuid::Uuid;
 use crate::core::contracts::repository::users::UserRepository;
+use crate::core::entities::audit::{AuditTrail, NewAuditEntry};
 use crate::core::entities::user::{
-    CreateUser, UpdatePasswordUser, UpdateUser, UserCompleteData, UserDataCreated
+    CreateUser, PatchUser, SortDirection, UpdateUser, UserCompleteData, UserDataCreated, UserListParams,
+    UserSearchRow, UserSessionState, UserSortField, UserStatusChange
 };
 use crate::config::querys::user::UserQueries;
+use crate::config::tenant::{begin_tenant_transaction, current_scope, current_tenant, TenantScope};
+use crate::core::entities::patch::Patch;
+use crate::core::entities::user_attribute::{is_blank_attribute, UserAttributes};
+use crate::repositories::audit_repository::write_audit_entries;
+
+const IMPORT_BATCH_SIZE: usize = 1000;
 
 #[derive(Clone)]
 pub struct PgUserRepository {
@@ -17,101 +30,533 @@ impl PgUserRepository {
     pub fn new(pool: PgPool) -> Self {
         Self { pool }
     }
+
+    // Leituras e alterações de usuários passam pelas políticas de RLS da organização ativa
+    async fn begin(&self) -> Result<Transaction<'static, Postgres>, sqlx::Error> {
+        begin_tenant_transaction(&self.pool, current_scope()).await
+    }
+
+    // Autenticação, unicidade de email e jobs consultam todas as organizações
+    async fn begin_unscoped(&self) -> Result<Transaction<'static, Postgres>, sqlx::Error> {
+        begin_tenant_transaction(&self.pool, TenantScope::All).await
+    }
 }
 
 #[async_trait]
 impl UserRepository for PgUserRepository {
-    async fn create_user(&self, user: CreateUser) -> Result<UserDataCreated, sqlx::Error> {
+    async fn create_user(&self, user: CreateUser, audit: AuditTrail<UserDataCreated>) -> Result<UserDataCreated, sqlx::Error> {
         let id = Uuid::new_v4();
         let date = chrono::Utc::now().naive_utc();
 
         info!("[Repository] Executing SQL query to create user with email: {} and ID: {}", user.email, id);
+        // Fora do escopo da organização: a nova linha ainda não tem vínculo e o RETURNING seria barrado pela RLS
+        let mut tx = self.begin_unscoped().await?;
         let user_created: UserDataCreated = sqlx::query_as(UserQueries::CREATE_USER)
             .bind(id)
             .bind(user.full_name)
             .bind(user.email)
             .bind(user.password)
+            .bind(Json(user.attributes))
             .bind(date)
             .bind(date)
-            .fetch_one(&self.pool)
+            .fetch_one(&mut *tx)
             .await?;
 
+        // Usuário criado dentro de uma organização passa a ser membro dela
+        if let Some(organization_id) = current_tenant() {
+            sqlx::query(UserQueries::ADD_USERS_TO_ORGANIZATION)
+                .bind(organization_id)
+                .bind(vec![id])
+                .bind(date)
+                .execute(&mut *tx)
+                .await?;
+        }
+
+        write_audit_entries(&mut tx, audit(&user_created)).await?;
+        tx.commit().await?;
         info!("[Repository] User successfully inserted into database with ID: {}", user_created.id);
         Ok(user_created)
     }
 
+    async fn import_users(&self, users: Vec<CreateUser>, skip_conflicts: bool, audit: AuditTrail<Vec<UserDataCreated>>) -> Result<Vec<UserDataCreated>, sqlx::Error> {
+        let date = chrono::Utc::now().naive_utc();
+        let mut created = Vec::with_capacity(users.len());
+
+        info!("[Repository] Executing SQL query to import {} users", users.len());
+        let mut tx = self.begin_unscoped().await?;
+
+        // Lotes limitados para não exceder o máximo de parâmetros por comando do Postgres
+        let mut users = users.into_iter().peekable();
+        while users.peek().is_some() {
+            let mut query = QueryBuilder::<Postgres>::new(UserQueries::IMPORT_USERS);
+            query.push_values(users.by_ref().take(IMPORT_BATCH_SIZE), |mut row, user| {
+                row.push_bind(Uuid::new_v4())
+                    .push_bind(user.full_name)
+                    .push_bind(user.email)
+                    .push_bind(user.password)
+                    .push_bind(Json(user.attributes))
+                    .push_bind(date)
+                    .push_bind(date);
+            });
+            if skip_conflicts {
+                query.push(UserQueries::IMPORT_USERS_SKIP_CONFLICTS);
+            }
+            query.push(UserQueries::USER_RETURNING);
+
+            let batch: Vec<UserDataCreated> = query
+                .build_query_as()
+                .fetch_all(&mut *tx)
+                .await?;
+            created.extend(batch);
+        }
+
+        if let Some(organization_id) = current_tenant() {
+            sqlx::query(UserQueries::ADD_USERS_TO_ORGANIZATION)
+                .bind(organization_id)
+                .bind(created.iter().map(|user| user.id).collect::<Vec<Uuid>>())
+                .bind(date)
+                .execute(&mut *tx)
+                .await?;
+        }
+
+        write_audit_entries(&mut tx, audit(&created)).await?;
+        tx.commit().await?;
+        info!("[Repository] {} users inserted into database", created.len());
+        Ok(created)
+    }
+
+    // Unicidade de email é global, então a verificação não fica restrita à organização
+    async fn find_existing_emails(&self, emails: &[String]) -> Result<Vec<String>, sqlx::Error> {
+        let mut tx = self.begin_unscoped().await?;
+        let rows = sqlx::query(UserQueries::FIND_EXISTING_EMAILS)
+            .bind(emails)
+            .fetch_all(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+        Ok(rows.iter().map(|row| row.get("email")).collect())
+    }
+
     async fn find_user_by_id(&self, id: Uuid) -> Result<UserDataCreated, sqlx::Error> {
+        let mut tx = self.begin().await?;
         let user: UserDataCreated = sqlx::query_as(UserQueries::FIND_BY_ID)
             .bind(id)
-            .fetch_one(&self.pool)
+            .fetch_one(&mut *tx)
             .await?;
 
+        tx.commit().await?;
         Ok(user)
     }
 
+    // Usada na autenticação, antes de existir uma organização ativa
     async fn find_user_by_email(&self, email: String) -> Result<UserCompleteData, sqlx::Error> {
         info!("[Repository] Executing SQL query to find user by email: {}", email);
+        let mut tx = self.begin_unscoped().await?;
         let user: UserCompleteData = sqlx::query_as(UserQueries::FIND_BY_EMAIL)
             .bind(email)
-            .fetch_one(&self.pool)
+            .fetch_one(&mut *tx)
             .await?;
 
+        tx.commit().await?;
         info!("[Repository] User found with ID: {}", user.id);
         Ok(user)
     }
 
-    async fn find_all_users(&self) -> Result<Vec<UserDataCreated>, sqlx::Error> {
-        let users: Vec<UserDataCreated> = sqlx::query_as(UserQueries::FIND_ALL)
-            .fetch_all(&self.pool)
+    // Usada nos logins sem senha (passkey e OIDC), que também precisam das regras de senha
+    async fn find_complete_user_by_id(&self, id: Uuid) -> Result<UserCompleteData, sqlx::Error> {
+        let mut tx = self.begin_unscoped().await?;
+        let user: UserCompleteData = sqlx::query_as(UserQueries::FIND_COMPLETE_BY_ID)
+            .bind(id)
+            .fetch_one(&mut *tx)
             .await?;
 
+        tx.commit().await?;
+        Ok(user)
+    }
+
+    async fn find_all_users(&self, params: &UserListParams) -> Result<Vec<UserDataCreated>, sqlx::Error> {
+        let mut tx = self.begin().await?;
+        let mut query = user_list_query(params);
+        query.push(" LIMIT ").push_bind(params.limit);
+
+        let users: Vec<UserDataCreated> = query
+            .build_query_as()
+            .fetch_all(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
         Ok(users)
     }
 
-    async fn update_user(&self, id: Uuid, data: UpdateUser) -> Result<UserDataCreated, sqlx::Error> {
+    async fn count_users(&self, params: &UserListParams) -> Result<i64, sqlx::Error> {
+        let mut tx = self.begin().await?;
+        let base = if params.only_deleted { UserQueries::COUNT_ALL_DELETED } else { UserQueries::COUNT_ALL };
+        let mut query = QueryBuilder::<Postgres>::new(base);
+        push_user_filters(&mut query, params);
+
+        let row = query
+            .build()
+            .fetch_one(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+        Ok(row.get("total"))
+    }
+
+    // Sem LIMIT: as linhas são entregues conforme chegam do banco, sem carregar tudo em memória
+    fn stream_users(&self, params: UserListParams) -> BoxStream<'static, Result<UserDataCreated, sqlx::Error>> {
+        let pool = self.pool.clone();
+        // O corpo é consumido depois do handler retornar, fora do escopo da requisição
+        let scope = current_scope();
+
+        Box::pin(try_stream! {
+            info!("[Repository] Executing SQL query to stream users");
+            let mut tx = begin_tenant_transaction(&pool, scope).await?;
+            let mut query = user_list_query(&params);
+            {
+                let mut rows = query.build_query_as::<UserDataCreated>().fetch(&mut *tx);
+                while let Some(user) = rows.try_next().await? {
+                    yield user;
+                }
+            }
+            tx.commit().await?;
+        })
+    }
+
+    async fn search_users(&self, term: &str, limit: i64, organization_id: Option<Uuid>) -> Result<Vec<UserSearchRow>, sqlx::Error> {
+        info!("[Repository] Executing SQL query to search users with term: {}", term);
+        let mut tx = self.begin().await?;
+
+        sqlx::query(UserQueries::SET_SEARCH_THRESHOLD)
+            .execute(&mut *tx)
+            .await?;
+
+        let rows: Vec<UserSearchRow> = sqlx::query_as(UserQueries::SEARCH_USERS)
+            .bind(term)
+            .bind(limit)
+            .bind(organization_id)
+            .fetch_all(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+        Ok(rows)
+    }
+
+    async fn update_user(&self, id: Uuid, data: UpdateUser, expected_versions: Option<Vec<i64>>, audit: AuditTrail<UserDataCreated>) -> Result<UserDataCreated, sqlx::Error> {
         let updated_at = chrono::Utc::now().naive_utc();
 
+        let mut tx = self.begin().await?;
         let user: UserDataCreated = sqlx::query_as(UserQueries::UPDATE_USER)
             .bind(id)
             .bind(data.full_name)
             .bind(data.email)
+            .bind(Json(data.attributes))
             .bind(updated_at)
-            .fetch_one(&self.pool)
+            .bind(expected_versions)
+            .fetch_one(&mut *tx)
             .await?;
 
+        write_audit_entries(&mut tx, audit(&user)).await?;
+        tx.commit().await?;
         Ok(user)
     }
 
-    async fn update_password_user(&self, id: Uuid, data: UpdatePasswordUser) -> Result<UserDataCreated, sqlx::Error> {
+    async fn patch_user(&self, id: Uuid, data: PatchUser, expected_versions: Option<Vec<i64>>, audit: AuditTrail<UserDataCreated>) -> Result<UserDataCreated, sqlx::Error> {
         let updated_at = chrono::Utc::now().naive_utc();
 
+        let mut tx = self.begin().await?;
+        let mut query = QueryBuilder::<Postgres>::new(UserQueries::PATCH_USER);
+        query.push_bind(updated_at);
+        // Campos ausentes ficam fora do SET; null já foi recusado pelo serviço
+        if let Patch::Value(full_name) = data.full_name {
+            query.push(", full_name = ").push_bind(full_name);
+        }
+        if let Patch::Value(email) = data.email {
+            query.push(", email = ").push_bind(email);
+        }
+        // Merge dos atributos: chaves com valor substituem as atuais, chaves vazias são removidas
+        if let Patch::Value(attributes) = data.attributes {
+            let (removed, changed): (Vec<_>, Vec<_>) = attributes.into_iter().partition(|(_, value)| is_blank_attribute(value));
+            query.push(", attributes = (attributes || ")
+                .push_bind(Json(changed.into_iter().collect::<UserAttributes>()))
+                .push(") - ")
+                .push_bind(removed.into_iter().map(|(key, _)| key).collect::<Vec<String>>())
+                .push("::text[]");
+        }
+        query.push(" WHERE id = ").push_bind(id).push(" AND deleted_at IS NULL");
+        // Compare-and-swap: só atualiza se a versão atual for uma das informadas no If-Match
+        if let Some(versions) = expected_versions {
+            query.push(" AND version = ANY(").push_bind(versions).push(")");
+        }
+        query.push(UserQueries::USER_RETURNING);
+
+        let user: UserDataCreated = query
+            .build_query_as()
+            .fetch_one(&mut *tx)
+            .await?;
+
+        write_audit_entries(&mut tx, audit(&user)).await?;
+        tx.commit().await?;
+        Ok(user)
+    }
+
+    // `history_size` é a quantidade de senhas anteriores mantidas no histórico
+    async fn update_password_user(&self, id: Uuid, password_hash: &str, history_size: i64, audit: Vec<NewAuditEntry>) -> Result<UserDataCreated, sqlx::Error> {
+        let updated_at = chrono::Utc::now().naive_utc();
+
+        info!("[Repository] Executing SQL query to update password of user ID: {}", id);
+        let mut tx = self.begin().await?;
+        if history_size > 0 {
+            sqlx::query(UserQueries::ARCHIVE_PASSWORD)
+                .bind(id)
+                .bind(Uuid::new_v4())
+                .bind(updated_at)
+                .execute(&mut *tx)
+                .await?;
+        }
+
         let user: UserDataCreated = sqlx::query_as(UserQueries::UPDATE_PASSWORD)
             .bind(id)
-            .bind(data.new_password)
+            .bind(password_hash)
             .bind(updated_at)
-            .fetch_one(&self.pool)
+            .fetch_one(&mut *tx)
+            .await?;
+
+        sqlx::query(UserQueries::PRUNE_PASSWORD_HISTORY)
+            .bind(id)
+            .bind(history_size.max(0))
+            .execute(&mut *tx)
             .await?;
 
+        write_audit_entries(&mut tx, audit).await?;
+        tx.commit().await?;
         Ok(user)
     }
 
-    async fn delete_user(&self, id: Uuid) -> Result<bool, sqlx::Error> {
+    async fn find_password_history(&self, id: Uuid, limit: i64) -> Result<Vec<String>, sqlx::Error> {
+        let mut tx = self.begin().await?;
+        let hashes = sqlx::query_scalar(UserQueries::FIND_PASSWORD_HISTORY)
+            .bind(id)
+            .bind(limit)
+            .fetch_all(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+        Ok(hashes)
+    }
+
+    async fn find_password_hash(&self, id: Uuid) -> Result<Option<String>, sqlx::Error> {
+        let mut tx = self.begin().await?;
+        let row = sqlx::query(UserQueries::FIND_PASSWORD)
+            .bind(id)
+            .fetch_optional(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+        Ok(row.map(|row| row.get("password")))
+    }
+
+    async fn change_role(&self, id: Uuid, role: &str, audit: AuditTrail<UserDataCreated>) -> Result<UserDataCreated, sqlx::Error> {
+        let updated_at = chrono::Utc::now().naive_utc();
+
+        info!("[Repository] Executing SQL query to change role of user ID: {} to {}", id, role);
+        let mut tx = self.begin().await?;
+        let user: UserDataCreated = sqlx::query_as(UserQueries::CHANGE_ROLE)
+            .bind(id)
+            .bind(role)
+            .bind(updated_at)
+            .fetch_one(&mut *tx)
+            .await?;
+
+        write_audit_entries(&mut tx, audit(&user)).await?;
+        tx.commit().await?;
+        Ok(user)
+    }
+
+    async fn set_avatar(&self, id: Uuid, updated_at: NaiveDateTime) -> Result<UserDataCreated, sqlx::Error> {
+        info!("[Repository] Executing SQL query to set avatar for user ID: {}", id);
+        let mut tx = self.begin().await?;
+        let user: UserDataCreated = sqlx::query_as(UserQueries::SET_AVATAR)
+            .bind(id)
+            .bind(updated_at)
+            .fetch_one(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+        Ok(user)
+    }
+
+    async fn find_user_status(&self, id: Uuid) -> Result<Option<String>, sqlx::Error> {
+        let mut tx = self.begin().await?;
+        let row = sqlx::query(UserQueries::FIND_STATUS)
+            .bind(id)
+            .fetch_optional(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+        Ok(row.map(|row| row.get("status")))
+    }
+
+    // Conferida pelo AuthMiddleware antes de o escopo da requisição existir
+    async fn find_session_state(&self, id: Uuid) -> Result<Option<UserSessionState>, sqlx::Error> {
+        let mut tx = self.begin_unscoped().await?;
+        let state = sqlx::query_as(UserQueries::FIND_SESSION_STATE)
+            .bind(id)
+            .fetch_optional(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+        Ok(state)
+    }
+
+    async fn change_status(&self, id: Uuid, from: &str, to: &str, reason: &str, changed_by: Uuid, audit: Vec<NewAuditEntry>) -> Result<UserDataCreated, sqlx::Error> {
+        let date = chrono::Utc::now().naive_utc();
+
+        info!("[Repository] Executing SQL query to change status of user {} from {} to {}", id, from, to);
+        let mut tx = self.begin().await?;
+
+        let user: UserDataCreated = sqlx::query_as(UserQueries::CHANGE_STATUS)
+            .bind(id)
+            .bind(from)
+            .bind(to)
+            .bind(date)
+            .fetch_one(&mut *tx)
+            .await?;
+
+        sqlx::query(UserQueries::CREATE_STATUS_HISTORY)
+            .bind(Uuid::new_v4())
+            .bind(id)
+            .bind(from)
+            .bind(to)
+            .bind(reason)
+            .bind(changed_by)
+            .bind(date)
+            .execute(&mut *tx)
+            .await?;
+
+        write_audit_entries(&mut tx, audit).await?;
+        tx.commit().await?;
+        Ok(user)
+    }
+
+    async fn find_status_history(&self, id: Uuid) -> Result<Vec<UserStatusChange>, sqlx::Error> {
+        let mut tx = self.begin().await?;
+        let history: Vec<UserStatusChange> = sqlx::query_as(UserQueries::FIND_STATUS_HISTORY)
+            .bind(id)
+            .fetch_all(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+        Ok(history)
+    }
+
+    async fn delete_user(&self, id: Uuid, expected_versions: Option<Vec<i64>>, audit: Vec<NewAuditEntry>) -> Result<bool, sqlx::Error> {
+        let deleted_at = chrono::Utc::now().naive_utc();
+
+        let mut tx = self.begin().await?;
         let result = sqlx::query(UserQueries::DELETE_USER)
             .bind(id)
-            .execute(&self.pool)
+            .bind(deleted_at)
+            .bind(expected_versions)
+            .execute(&mut *tx)
             .await?;
 
-        Ok(result.rows_affected() > 0)
+        let deleted = result.rows_affected() > 0;
+        if deleted {
+            write_audit_entries(&mut tx, audit).await?;
+        }
+        tx.commit().await?;
+        Ok(deleted)
     }
 
+    async fn restore_user(&self, id: Uuid, audit: AuditTrail<UserDataCreated>) -> Result<UserDataCreated, sqlx::Error> {
+        let updated_at = chrono::Utc::now().naive_utc();
+
+        info!("[Repository] Executing SQL query to restore user with ID: {}", id);
+        let mut tx = self.begin().await?;
+        let user: UserDataCreated = sqlx::query_as(UserQueries::RESTORE_USER)
+            .bind(id)
+            .bind(updated_at)
+            .fetch_one(&mut *tx)
+            .await?;
+
+        write_audit_entries(&mut tx, audit(&user)).await?;
+        tx.commit().await?;
+        Ok(user)
+    }
+
+    // Job de manutenção, executado para todas as organizações
+    async fn purge_deleted_users(&self, deleted_before: NaiveDateTime) -> Result<u64, sqlx::Error> {
+        info!("[Repository] Executing SQL query to purge users deleted before: {}", deleted_before);
+        let mut tx = self.begin_unscoped().await?;
+        let result = sqlx::query(UserQueries::PURGE_DELETED_USERS)
+            .bind(deleted_before)
+            .execute(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+        Ok(result.rows_affected())
+    }
+
+    // Unicidade de email é global, então a verificação não fica restrita à organização
     async fn email_exists_for_other_user(&self, email: &str, id: Uuid) -> Result<bool, sqlx::Error> {
+        let mut tx = self.begin_unscoped().await?;
         let result = sqlx::query(UserQueries::EMAIL_EXIST_FOR_OTHER_USER)
             .bind(email)
             .bind(id)
-            .fetch_one(&self.pool)
+            .fetch_one(&mut *tx)
             .await?;
 
+        tx.commit().await?;
+
         let exists: bool = result.get("exists");
         Ok(exists)
     }
 }
+
+// Filtros, cursor e ordenação compartilhados entre a listagem paginada e a exportação
+fn user_list_query(params: &UserListParams) -> QueryBuilder<'static, Postgres> {
+    let base = if params.only_deleted { UserQueries::FIND_ALL_DELETED } else { UserQueries::FIND_ALL };
+    let mut query = QueryBuilder::<Postgres>::new(base);
+    push_user_filters(&mut query, params);
+
+    let column = params.sort.column();
+    let direction = params.direction.keyword();
+
+    // Paginação por keyset: (coluna, id) estritamente depois da última linha entregue
+    if let Some(cursor) = &params.after {
+        let operator = match params.direction {
+            SortDirection::Asc => ">",
+            SortDirection::Desc => "<",
+        };
+        query.push(format!(" AND ({}, id) {} (", column, operator));
+        match params.sort {
+            UserSortField::CreatedAt => query.push_bind(cursor.value.clone()).push("::timestamp"),
+            UserSortField::FullName | UserSortField::Email => query.push_bind(cursor.value.clone()),
+        };
+        query.push(", ").push_bind(cursor.id).push(")");
+    }
+
+    query.push(format!(" ORDER BY {} {}, id {}", column, direction, direction));
+    query
+}
+
+fn push_user_filters(query: &mut QueryBuilder<'static, Postgres>, params: &UserListParams) {
+    if let Some(organization_id) = params.organization_id {
+        query.push(" AND id IN (SELECT user_id FROM organization_memberships WHERE organization_id = ")
+            .push_bind(organization_id)
+            .push(")");
+    }
+    if let Some(email) = &params.email_contains {
+        query.push(" AND strpos(lower(email), lower(").push_bind(email.clone()).push(")) > 0");
+    }
+    if let Some(from) = params.created_from {
+        query.push(" AND created_at >= ").push_bind(from);
+    }
+    if let Some(to) = params.created_to {
+        query.push(" AND created_at <= ").push_bind(to);
+    }
+    if let Some(attributes) = &params.attributes {
+        query.push(" AND attributes @> ").push_bind(Json(attributes.clone()));
+    }
+}
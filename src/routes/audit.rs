@@ -0,0 +1,16 @@
+use actix_web::web;
+use crate::controllers::audit;
+
+pub fn configure_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("/audit")
+            .service(
+                web::resource("")
+                    .route(web::get().to(audit::find_audit_entries))
+            )
+            .service(
+                web::resource("/verify")
+                    .route(web::get().to(audit::verify_audit_chain))
+            )
+    );
+}
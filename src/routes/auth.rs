@@ -1,5 +1,5 @@
 use actix_web::web;
-use crate::controllers::auth;
+use crate::controllers::{auth, device_alerts, oidc};
 
 pub fn configure_routes(cfg: &mut web::ServiceConfig) {
     cfg.service(
@@ -8,5 +8,58 @@ pub fn configure_routes(cfg: &mut web::ServiceConfig) {
                 web::resource("/login")
                     .route(web::post().to(auth::login))
             )
+            .service(
+                web::resource("/activity")
+                    .route(web::get().to(auth::find_login_activity))
+            )
+            .service(
+                web::resource("/device-alerts/report")
+                    .route(web::post().to(device_alerts::report_device_alert))
+            )
+            .service(
+                web::resource("/device-alerts/reset-password")
+                    .route(web::post().to(device_alerts::reset_password))
+            )
+            .service(
+                web::resource("/passkeys/login/options")
+                    .route(web::post().to(auth::passkey_login_options))
+            )
+            .service(
+                web::resource("/passkeys/login")
+                    .route(web::post().to(auth::login_with_passkey))
+            )
+            .service(
+                web::resource("/passkeys/register/options")
+                    .route(web::post().to(auth::passkey_registration_options))
+            )
+            .service(
+                web::resource("/passkeys/register")
+                    .route(web::post().to(auth::register_passkey))
+            )
+            .service(
+                web::resource("/passkeys")
+                    .route(web::get().to(auth::find_passkeys))
+            )
+            .service(
+                web::resource("/passkeys/{id}")
+                    .route(web::delete().to(auth::delete_passkey))
+            )
+            .service(
+                web::resource("/oidc/login")
+                    .route(web::get().to(oidc::start_login))
+            )
+            .service(
+                web::resource("/oidc/callback")
+                    .route(web::post().to(oidc::callback))
+            )
+            .service(
+                web::resource("/oidc/identities")
+                    .route(web::get().to(oidc::find_identities))
+                    .route(web::post().to(oidc::start_link))
+            )
+            .service(
+                web::resource("/oidc/identities/{id}")
+                    .route(web::delete().to(oidc::unlink_identity))
+            )
     );
 }
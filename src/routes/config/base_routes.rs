@@ -1,10 +1,17 @@
 use actix_web::web;
-use crate::routes::{users, auth};
+use crate::routes::{users, auth, oauth, exports, organizations, user_attributes, invitations, email_changes, audit};
 
 pub fn configure_routes(cfg: &mut web::ServiceConfig) {
     cfg.service(
         web::scope("/api/v1")
             .configure(users::configure_routes)
             .configure(auth::configure_routes)
+            .configure(oauth::configure_routes)
+            .configure(exports::configure_routes)
+            .configure(organizations::configure_routes)
+            .configure(user_attributes::configure_routes)
+            .configure(invitations::configure_routes)
+            .configure(email_changes::configure_routes)
+            .configure(audit::configure_routes)
     );
 }
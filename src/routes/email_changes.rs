@@ -0,0 +1,16 @@
+use actix_web::web;
+use crate::controllers::email_changes;
+
+pub fn configure_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("/email-changes")
+            .service(
+                web::resource("/confirm")
+                    .route(web::post().to(email_changes::confirm_email_change))
+            )
+            .service(
+                web::resource("/revert")
+                    .route(web::post().to(email_changes::revert_email_change))
+            )
+    );
+}
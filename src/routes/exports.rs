@@ -0,0 +1,20 @@
+use actix_web::web;
+use crate::controllers::exports;
+
+pub fn configure_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("/exports")
+            .service(
+                web::resource("")
+                    .route(web::post().to(exports::create_export))
+            )
+            .service(
+                web::resource("/{id}")
+                    .route(web::get().to(exports::find_export))
+            )
+            .service(
+                web::resource("/{id}/download")
+                    .route(web::get().to(exports::download_export))
+            )
+    );
+}
@@ -0,0 +1,25 @@
+use actix_web::web;
+use crate::controllers::invitations;
+
+pub fn configure_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("/invitations")
+            .service(
+                web::resource("")
+                    .route(web::post().to(invitations::create_invitation))
+                    .route(web::get().to(invitations::find_invitations))
+            )
+            .service(
+                web::resource("/accept")
+                    .route(web::post().to(invitations::accept_invitation))
+            )
+            .service(
+                web::resource("/{id}/resend")
+                    .route(web::post().to(invitations::resend_invitation))
+            )
+            .service(
+                web::resource("/{id}")
+                    .route(web::delete().to(invitations::revoke_invitation))
+            )
+    );
+}
@@ -1,3 +1,10 @@
 pub mod auth;
+pub mod exports;
+pub mod oauth;
 pub mod users;
 pub mod config;
+pub mod organizations;
+pub mod user_attributes;
+pub mod invitations;
+pub mod email_changes;
+pub mod audit;
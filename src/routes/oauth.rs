@@ -0,0 +1,34 @@
+use actix_web::web;
+use crate::controllers::oauth;
+
+pub fn configure_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("/oauth")
+            .service(
+                web::resource("/clients")
+                    .route(web::post().to(oauth::create_client))
+                    .route(web::get().to(oauth::find_clients))
+            )
+            .service(
+                web::resource("/clients/{id}")
+                    .route(web::delete().to(oauth::delete_client))
+            )
+            .service(
+                web::resource("/authorize")
+                    .route(web::get().to(oauth::consent_details))
+                    .route(web::post().to(oauth::authorize))
+            )
+            .service(
+                web::resource("/token")
+                    .route(web::post().to(oauth::token))
+            )
+            .service(
+                web::resource("/introspect")
+                    .route(web::post().to(oauth::introspect))
+            )
+            .service(
+                web::resource("/revoke")
+                    .route(web::post().to(oauth::revoke))
+            )
+    );
+}
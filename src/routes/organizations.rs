@@ -0,0 +1,45 @@
+use actix_web::web;
+use crate::controllers::organizations;
+
+pub fn configure_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("/organizations")
+            .service(
+                web::resource("")
+                    .route(web::post().to(organizations::create_organization))
+                    .route(web::get().to(organizations::find_user_organizations))
+            )
+            .service(
+                web::resource("/invitations/accept")
+                    .route(web::post().to(organizations::accept_invitation))
+            )
+            .service(
+                web::resource("/{id}/switch")
+                    .route(web::post().to(organizations::switch_organization))
+            )
+            .service(
+                web::resource("/{id}/members")
+                    .route(web::get().to(organizations::find_members))
+            )
+            .service(
+                web::resource("/{id}/members/{user_id}")
+                    .route(web::put().to(organizations::update_member_role))
+                    .route(web::delete().to(organizations::remove_member))
+            )
+            .service(
+                web::resource("/{id}/invitations")
+                    .route(web::post().to(organizations::create_invitation))
+                    .route(web::get().to(organizations::find_invitations))
+            )
+            .service(
+                web::resource("/{id}/invitations/{invitation_id}")
+                    .route(web::delete().to(organizations::revoke_invitation))
+            )
+            .service(
+                web::resource("/{id}")
+                    .route(web::get().to(organizations::find_organization))
+                    .route(web::put().to(organizations::update_organization))
+                    .route(web::delete().to(organizations::delete_organization))
+            )
+    );
+}
@@ -0,0 +1,18 @@
+use actix_web::web;
+use crate::controllers::user_attributes;
+
+pub fn configure_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("/user-attributes")
+            .service(
+                web::resource("")
+                    .route(web::post().to(user_attributes::create_attribute))
+                    .route(web::get().to(user_attributes::find_attributes))
+            )
+            .service(
+                web::resource("/{key}")
+                    .route(web::put().to(user_attributes::update_attribute))
+                    .route(web::delete().to(user_attributes::delete_attribute))
+            )
+    );
+}
@@ -9,11 +9,76 @@ pub fn configure_routes(cfg: &mut web::ServiceConfig) {
                     .route(web::post().to(users::create_user))
                     .route(web::get().to(users::find_all_users))
             )
+            .service(
+                web::resource("/search")
+                    .route(web::get().to(users::search_users))
+            )
+            .service(
+                web::resource("/export")
+                    .route(web::get().to(users::export_users))
+            )
+            .service(
+                web::resource("/import")
+                    // Arquivos de importação podem ter milhares de linhas
+                    .app_data(web::PayloadConfig::new(5 * 1024 * 1024))
+                    .route(web::post().to(users::import_users))
+            )
+            .service(
+                web::resource("/deleted")
+                    .route(web::get().to(users::find_deleted_users))
+            )
+            .service(
+                web::resource("/{id}/avatar")
+                    .route(web::put().to(users::upload_avatar))
+                    .route(web::get().to(users::find_avatar))
+            )
+            .service(
+                web::resource("/{id}/restore")
+                    .route(web::post().to(users::restore_user))
+            )
+            .service(
+                web::resource("/{id}/suspend")
+                    .route(web::post().to(users::suspend_user))
+            )
+            .service(
+                web::resource("/{id}/reactivate")
+                    .route(web::post().to(users::reactivate_user))
+            )
+            .service(
+                web::resource("/{id}/deactivate")
+                    .route(web::post().to(users::deactivate_user))
+            )
+            .service(
+                web::resource("/{id}/password")
+                    .route(web::put().to(users::change_password))
+            )
+            .service(
+                web::resource("/{id}/role")
+                    .route(web::put().to(users::change_role))
+            )
+            .service(
+                web::resource("/{id}/history")
+                    .route(web::get().to(users::find_user_history))
+            )
+            .service(
+                web::resource("/{id}/status-history")
+                    .route(web::get().to(users::find_status_history))
+            )
+            .service(
+                web::resource("/{id}/data-export")
+                    .route(web::get().to(users::export_user_data))
+            )
+            .service(
+                web::resource("/{id}/erasure")
+                    .route(web::post().to(users::erase_user))
+            )
             .service(
                 web::resource("/{id}")
                     .route(web::put().to(users::update_user))
+                    .route(web::patch().to(users::patch_user))
                     .route(web::get().to(users::find_user_by_id))
                     .route(web::delete().to(users::delete_user_by_id))
             )
     );
 }
+
@@ -0,0 +1,178 @@
+use actix_web::{http::header, web, HttpResponse};
+use futures::{stream, StreamExt};
+use log::{error, info};
+use uuid::Uuid;
+use crate::core::contracts::repository::audits::AuditRepository;
+use crate::core::entities::audit::{
+    AuditChainVerification, AuditCursor, AuditFilters, AuditQuery, AUDIT_GENESIS_HASH, AUDIT_RESOURCE_USER,
+};
+use crate::repositories::audit_repository::PgAuditRepository;
+use crate::utils::errors::AppError;
+use crate::utils::export::{audit_csv_header, audit_csv_row};
+use crate::utils::pagination::{decode_cursor, encode_cursor, page_size};
+use crate::utils::response::ApiResponse;
+
+const VERIFY_BATCH_SIZE: i64 = 1000;
+
+pub struct AuditService {
+    audit_repo: web::Data<PgAuditRepository>,
+}
+
+impl AuditService {
+    pub fn new(audit_repo: web::Data<PgAuditRepository>) -> Self {
+        Self { audit_repo }
+    }
+
+    // Percorre a cadeia inteira conferindo o vínculo com a entrada anterior e o hash do conteúdo
+    pub async fn verify_chain(&self) -> Result<AuditChainVerification, AppError> {
+        info!("[Service] Starting audit chain verification");
+
+        let mut expected_prev = AUDIT_GENESIS_HASH.to_string();
+        let mut last_seq = 0;
+        let mut checked = 0;
+        loop {
+            let links = self.audit_repo.find_chain_links(last_seq, VERIFY_BATCH_SIZE).await?;
+            if links.is_empty() {
+                break;
+            }
+            for link in links {
+                let reason = if link.prev_hash != expected_prev {
+                    Some("Entry does not point to the previous entry; entries were removed or reordered")
+                } else if link.digest != link.hash {
+                    Some("Entry content does not match its hash; the entry was modified")
+                } else {
+                    None
+                };
+                if let Some(reason) = reason {
+                    error!("[Service] Audit chain broken at seq {}: {}", link.seq, reason);
+                    return Ok(AuditChainVerification {
+                        valid: false,
+                        checked,
+                        last_hash: None,
+                        broken_at_seq: Some(link.seq),
+                        reason: Some(reason.to_string()),
+                    });
+                }
+                checked += 1;
+                last_seq = link.seq;
+                expected_prev = link.hash;
+            }
+        }
+
+        info!("[Service] Audit chain verified successfully with {} entries", checked);
+        Ok(AuditChainVerification {
+            valid: true,
+            checked,
+            last_hash: (checked > 0).then_some(expected_prev),
+            broken_at_seq: None,
+            reason: None,
+        })
+    }
+
+    pub async fn verify_chain_response(&self) -> Result<HttpResponse, AppError> {
+        let verification = self.verify_chain().await?;
+        Ok(ApiResponse::success(verification).into_response())
+    }
+
+    // `organization_id` restringe às entradas geradas na organização ativa de quem consulta
+    pub async fn find_entries(&self, query: AuditQuery, organization_id: Option<Uuid>) -> Result<HttpResponse, AppError> {
+        info!("[Service] Starting find audit entries process");
+        let csv = is_csv(query.format.as_deref())?;
+        let filters = build_filters(query, organization_id)?;
+        if csv {
+            return Ok(self.export_entries(filters, "audit.csv"));
+        }
+        self.list_entries(filters).await
+    }
+
+    pub async fn find_user_history(&self, user_id: Uuid, query: AuditQuery, organization_id: Option<Uuid>) -> Result<HttpResponse, AppError> {
+        info!("[Service] Starting find audit history process for user ID: {}", user_id);
+        let csv = is_csv(query.format.as_deref())?;
+        let mut filters = build_filters(query, organization_id)?;
+        filters.resource_type = Some(AUDIT_RESOURCE_USER.to_string());
+        filters.resource_id = Some(user_id);
+        if csv {
+            return Ok(self.export_entries(filters, &format!("user-{}-history.csv", user_id)));
+        }
+        self.list_entries(filters).await
+    }
+
+    async fn list_entries(&self, mut filters: AuditFilters) -> Result<HttpResponse, AppError> {
+        let limit = filters.limit.unwrap_or_default();
+        // Uma linha extra indica se existe próxima página
+        filters.limit = Some(limit + 1);
+
+        let mut entries = self.audit_repo.find_entries(&filters).await?;
+        let next_cursor = if entries.len() as i64 > limit {
+            entries.truncate(limit as usize);
+            entries.last().map(|last| encode_cursor(&AuditCursor { seq: last.seq }))
+        } else {
+            None
+        };
+        info!("[Service] Found {} audit entries", entries.len());
+        Ok(ApiResponse::paginated(entries, next_cursor).into_response())
+    }
+
+    // Todas as entradas filtradas, sem paginação
+    fn export_entries(&self, mut filters: AuditFilters, filename: &str) -> HttpResponse {
+        filters.limit = None;
+        filters.before_seq = None;
+
+        let rows = self.audit_repo.stream_entries(filters).map(|row| match row {
+            Ok(entry) => audit_csv_row(&entry),
+            Err(e) => {
+                error!("[Service] Database error while exporting audit entries: {:?}", e);
+                Err(AppError::InternalServerError)
+            }
+        });
+        let body = stream::once(async { audit_csv_header() })
+            .chain(rows)
+            .map(|chunk| chunk.map(web::Bytes::from).map_err(actix_web::Error::from));
+
+        HttpResponse::Ok()
+            .content_type("text/csv; charset=utf-8")
+            .insert_header((header::CONTENT_DISPOSITION, format!("attachment; filename=\"{}\"", filename)))
+            .streaming(body)
+    }
+}
+
+fn is_csv(format: Option<&str>) -> Result<bool, AppError> {
+    match format {
+        None | Some("json") => Ok(false),
+        Some("csv") => Ok(true),
+        Some(other) => Err(AppError::BadRequest(format!("Invalid format '{}'; use json or csv", other))),
+    }
+}
+
+fn build_filters(query: AuditQuery, organization_id: Option<Uuid>) -> Result<AuditFilters, AppError> {
+    if let (Some(from), Some(to)) = (query.from, query.to)
+        && from > to
+    {
+        return Err(AppError::BadRequest("from must not be after to".into()));
+    }
+    let before_seq = query.cursor
+        .as_deref()
+        .map(decode_cursor::<AuditCursor>)
+        .transpose()?
+        .map(|cursor| cursor.seq);
+    let actions: Vec<String> = query.action
+        .as_deref()
+        .unwrap_or_default()
+        .split(',')
+        .map(str::trim)
+        .filter(|action| !action.is_empty())
+        .map(str::to_string)
+        .collect();
+
+    Ok(AuditFilters {
+        organization_id,
+        actor_id: query.actor_id,
+        resource_type: query.resource_type.filter(|resource_type| !resource_type.is_empty()),
+        resource_id: query.resource_id,
+        actions: Some(actions).filter(|actions| !actions.is_empty()),
+        from: query.from,
+        to: query.to,
+        before_seq,
+        limit: Some(page_size(query.limit)?),
+    })
+}
@@ -1,42 +1,79 @@
-use actix_web::{web, HttpResponse};
-use log::info;
+use actix_web::{http::StatusCode, web, HttpResponse};
+use log::{error, info};
+use uuid::Uuid;
+use crate::adapters::passkey_verifier::{decode_base64url, encode_base64url, PasskeyVerifierPort};
 use crate::adapters::password_hasher::PasswordEncryptorPort;
-use crate::adapters::token_hasher::TokenGeneratorPort;
+use crate::adapters::token_hasher::{unix_timestamp, TokenGeneratorPort};
 use crate::config::config_env::Config;
+use crate::core::contracts::repository::organizations::OrganizationRepository;
+use crate::core::contracts::repository::passkeys::PasskeyRepository;
 use crate::core::contracts::repository::users::UserRepository;
-use crate::core::entities::auth::{Login, LoginResponse};
+use crate::core::entities::audit::{audit_diff, audit_entry, AuditTrail, NewAuditEntry, AUDIT_PASSKEY_DELETED, AUDIT_PASSKEY_REGISTERED, AUDIT_RESOURCE_PASSKEY};
+use crate::core::entities::auth::{ClaimsToUserToken, Login, LoginResponse};
+use crate::core::entities::login_attempt::{
+    NewLoginAttempt, LOGIN_FAILURE_ACCOUNT_INACTIVE, LOGIN_FAILURE_INVALID_PASSWORD, LOGIN_FAILURE_PASSWORD_EXPIRED,
+    LOGIN_FAILURE_PASSWORD_RESET_REQUIRED, LOGIN_FAILURE_UNKNOWN_EMAIL,
+};
+use crate::core::entities::passkey::{
+    CreatePasskeyCredential, CredentialDescriptor, PasskeyChallenge, PasskeyCredential, PasskeyLogin, PasskeyLoginOptions,
+    PasskeyLoginOptionsRequest, PasskeyRegistration, PasskeyRegistrationOptions, PasskeyUser, PubKeyCredParam,
+    RelyingParty, AUTHENTICATION_CEREMONY, REGISTRATION_CEREMONY,
+};
+use crate::core::entities::user::{is_privileged_role, UserCompleteData};
+use crate::middleware::auth::ensure_active_status;
+use crate::repositories::organization_repository::PgOrganizationRepository;
+use crate::repositories::passkey_repository::PgPasskeyRepository;
 use crate::repositories::user_repository::PgUserRepository;
+use crate::services::device_alert_service::DeviceAlertService;
+use crate::services::login_activity_service::LoginActivityService;
 use crate::utils::errors::AppError;
 use crate::utils::response::ApiResponse;
+use crate::utils::validations::normalize_email;
+
+const PASSKEY_CHALLENGE_TTL_SECONDS: i64 = 300;
+const PASSWORD_CHANGE_TOKEN_TTL_SECONDS: usize = 900;
 
 pub struct AuthService {
     user_repo: web::Data<PgUserRepository>,
+    passkey_repo: web::Data<PgPasskeyRepository>,
+    organization_repo: web::Data<PgOrganizationRepository>,
+    login_activity_service: web::Data<LoginActivityService>,
+    device_alert_service: web::Data<DeviceAlertService>,
     config: web::Data<Config>,
     password_encryptor: Box<dyn PasswordEncryptorPort>,
     token_generator: Box<dyn TokenGeneratorPort>,
+    passkey_verifier: Box<dyn PasskeyVerifierPort>,
 }
 
 impl AuthService {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         user_repo: web::Data<PgUserRepository>,
+        passkey_repo: web::Data<PgPasskeyRepository>,
+        organization_repo: web::Data<PgOrganizationRepository>,
+        login_activity_service: web::Data<LoginActivityService>,
+        device_alert_service: web::Data<DeviceAlertService>,
         config: web::Data<Config>,
         password_encryptor: Box<dyn PasswordEncryptorPort>,
         token_generator: Box<dyn TokenGeneratorPort>,
+        passkey_verifier: Box<dyn PasskeyVerifierPort>,
     ) -> Self {
-        Self { user_repo, config, password_encryptor, token_generator }
+        Self { user_repo, passkey_repo, organization_repo, login_activity_service, device_alert_service, config, password_encryptor, token_generator, passkey_verifier }
     }
 
     pub async fn login(&self, data: Login) -> Result<HttpResponse, AppError> {
         info!("[Service] Starting login process with email: {}", data.email);
+        let email = normalize_email(&data.email);
 
         info!("[Service] Checking if user exists with email: {}", data.email);
-        let user = match self.user_repo.find_user_by_email(data.email.clone()).await {
+        let user = match self.user_repo.find_user_by_email(email.clone()).await {
             Ok(user) => {
                 info!("[Service] User found with email: {}", data.email);
                 user
             },
             Err(sqlx::Error::RowNotFound) => {
                 info!("[Service] User not found with email: {}", data.email);
+                self.record_login_failure(None, email, LOGIN_FAILURE_UNKNOWN_EMAIL).await;
                 return Err(AppError::Unauthorized("Invalid credentials".into()));
             },
             Err(e) => {
@@ -49,29 +86,362 @@ impl AuthService {
         if !self.password_encryptor.verify_password(&user.password, &data.password)
             .map_err(|_| AppError::InternalServerError)? {
             info!("[Service] Incorrect password for user with email: {}", data.email);
+            self.record_login_failure(Some(user.id), email, LOGIN_FAILURE_INVALID_PASSWORD).await;
             return Err(AppError::Unauthorized("Invalid credentials".into()));
         }
         info!("[Service] Password verified successfully for user with email: {}", data.email);
 
-        info!("[Service] Generating token for user with email: {}", data.email);
+        self.issue_login_response(user).await
+    }
+
+    // Apenas contas privilegiadas têm prazo de troca; `PASSWORD_MAX_AGE_DAYS=0` desativa a regra
+    fn is_password_expired(&self, role: &str, password_changed_at: chrono::NaiveDateTime) -> bool {
+        let max_age_days = self.config.password_max_age_days;
+        max_age_days > 0
+            && is_privileged_role(role)
+            && password_changed_at + chrono::Duration::days(max_age_days) <= chrono::Utc::now().naive_utc()
+    }
+
+    // Token curto e sem organização ativa, aceito apenas na troca da própria senha
+    fn issue_password_change_token(&self, id: Uuid, full_name: String, email: String, role: String) -> Result<HttpResponse, AppError> {
+        let issued_at = unix_timestamp();
+        let claims = ClaimsToUserToken {
+            id: id.to_string(),
+            exp: issued_at + PASSWORD_CHANGE_TOKEN_TTL_SECONDS,
+            iat: issued_at,
+            full_name: full_name.clone(),
+            email: email.clone(),
+            role,
+            client_id: None,
+            scope: None,
+            organization_id: None,
+            password_expired: true,
+        };
+        let token = self.token_generator
+            .generate_client_token(&claims, &self.config.jwt_secret)
+            .map_err(|e| {
+                error!("[Service] Error generating password change token: {:?}", e);
+                AppError::InternalServerError
+            })?;
+
+        let response = LoginResponse {
+            token,
+            id,
+            full_name,
+            email,
+            organization_id: None,
+            password_expired: true,
+        };
+        Ok(ApiResponse::new("Password expired; change your password to continue".to_string(), StatusCode::OK, Some(response)).into_response())
+    }
+
+    async fn record_login_failure(&self, user_id: Option<Uuid>, email: String, reason: &'static str) {
+        self.login_activity_service
+            .record(NewLoginAttempt { user_id, email, succeeded: false, failure_reason: Some(reason) })
+            .await;
+    }
+
+    pub async fn passkey_login_options(&self, data: PasskeyLoginOptionsRequest) -> Result<HttpResponse, AppError> {
+        info!("[Service] Starting passkey login options process");
+
+        // Sem email o navegador oferece as credenciais descobríveis do autenticador
+        let (user_id, allow_credentials) = match data.email {
+            Some(email) => match self.user_repo.find_user_by_email(normalize_email(&email)).await {
+                Ok(user) => {
+                    let credentials = self.passkey_repo.find_credentials_by_user(user.id).await?;
+                    (Some(user.id), credentials.into_iter().map(to_descriptor).collect())
+                }
+                Err(sqlx::Error::RowNotFound) => {
+                    info!("[Service] User not found with email: {}", email);
+                    (None, Vec::new())
+                }
+                Err(e) => {
+                    error!("[Service] Database error while finding user: {:?}", e);
+                    return Err(AppError::InternalServerError);
+                }
+            },
+            None => (None, Vec::new()),
+        };
+
+        let challenge = self.create_challenge(user_id, AUTHENTICATION_CEREMONY).await?;
+
+        let options = PasskeyLoginOptions {
+            challenge_id: challenge.id,
+            challenge: challenge.challenge,
+            rp_id: self.config.webauthn_rp_id.clone(),
+            timeout: (PASSKEY_CHALLENGE_TTL_SECONDS * 1000) as u64,
+            user_verification: "required".to_string(),
+            allow_credentials,
+        };
+
+        Ok(ApiResponse::success(options).into_response())
+    }
+
+    pub async fn login_with_passkey(&self, data: PasskeyLogin) -> Result<HttpResponse, AppError> {
+        info!("[Service] Starting passkey login process for credential: {}", data.credential_id);
+
+        let challenge = match self.passkey_repo.take_challenge(data.challenge_id, AUTHENTICATION_CEREMONY).await {
+            Ok(challenge) => challenge,
+            Err(sqlx::Error::RowNotFound) => {
+                info!("[Service] Passkey challenge {} not found or expired", data.challenge_id);
+                return Err(AppError::Unauthorized("Invalid passkey".into()));
+            }
+            Err(e) => {
+                error!("[Service] Database error while loading challenge: {:?}", e);
+                return Err(AppError::InternalServerError);
+            }
+        };
+
+        let credential = match self.passkey_repo.find_credential_by_credential_id(&data.credential_id).await {
+            Ok(credential) => credential,
+            Err(sqlx::Error::RowNotFound) => {
+                info!("[Service] Passkey not found: {}", data.credential_id);
+                return Err(AppError::Unauthorized("Invalid passkey".into()));
+            }
+            Err(e) => {
+                error!("[Service] Database error while finding passkey: {:?}", e);
+                return Err(AppError::InternalServerError);
+            }
+        };
+
+        if challenge.user_id.is_some_and(|user_id| user_id != credential.user_id) {
+            info!("[Service] Passkey does not belong to the user the challenge was issued for");
+            return Err(AppError::Unauthorized("Invalid passkey".into()));
+        }
+
+        let decode = |value: &str| decode_base64url(value).map_err(|_| AppError::Unauthorized("Invalid passkey".into()));
+        let sign_count = self.passkey_verifier
+            .verify_assertion(
+                &challenge.challenge,
+                &credential.public_key,
+                credential.sign_count as u32,
+                &decode(&data.client_data_json)?,
+                &decode(&data.authenticator_data)?,
+                &decode(&data.signature)?,
+            )
+            .map_err(|e| {
+                info!("[Service] Passkey assertion rejected: {}", e);
+                AppError::Unauthorized("Invalid passkey".into())
+            })?;
+        info!("[Service] Passkey assertion verified for user ID: {}", credential.user_id);
+
+        self.passkey_repo.update_sign_count(credential.id, sign_count as i64).await?;
+
+        // Usuário excluído mantém as passkeys até o expurgo, mas não pode mais entrar
+        let user = match self.user_repo.find_complete_user_by_id(credential.user_id).await {
+            Ok(user) => user,
+            Err(sqlx::Error::RowNotFound) => {
+                info!("[Service] Passkey belongs to a deleted user: {}", credential.user_id);
+                return Err(AppError::Unauthorized("Invalid passkey".into()));
+            }
+            Err(e) => return Err(e.into()),
+        };
+        self.issue_login_response(user).await
+    }
+
+    pub async fn passkey_registration_options(&self, user_id: Uuid) -> Result<HttpResponse, AppError> {
+        info!("[Service] Starting passkey registration options process for user ID: {}", user_id);
+
+        let user = self.user_repo.find_user_by_id(user_id).await?;
+        let existing = self.passkey_repo.find_credentials_by_user(user_id).await?;
+        let challenge = self.create_challenge(Some(user_id), REGISTRATION_CEREMONY).await?;
+
+        let options = PasskeyRegistrationOptions {
+            challenge_id: challenge.id,
+            challenge: challenge.challenge,
+            rp: RelyingParty {
+                id: self.config.webauthn_rp_id.clone(),
+                name: self.config.webauthn_rp_name.clone(),
+            },
+            user: PasskeyUser {
+                id: encode_base64url(user.id.as_bytes()),
+                name: user.email,
+                display_name: user.full_name,
+            },
+            pub_key_cred_params: vec![PubKeyCredParam { kind: "public-key".to_string(), alg: -7 }],
+            timeout: (PASSKEY_CHALLENGE_TTL_SECONDS * 1000) as u64,
+            attestation: "none".to_string(),
+            user_verification: "required".to_string(),
+            exclude_credentials: existing.into_iter().map(to_descriptor).collect(),
+        };
+
+        Ok(ApiResponse::success(options).into_response())
+    }
+
+    pub async fn register_passkey(&self, user_id: Uuid, data: PasskeyRegistration) -> Result<HttpResponse, AppError> {
+        info!("[Service] Starting passkey registration process for user ID: {}", user_id);
+
+        let challenge = match self.passkey_repo.take_challenge(data.challenge_id, REGISTRATION_CEREMONY).await {
+            Ok(challenge) if challenge.user_id == Some(user_id) => challenge,
+            Ok(_) | Err(sqlx::Error::RowNotFound) => {
+                info!("[Service] Registration challenge {} not found or expired", data.challenge_id);
+                return Err(AppError::BadRequest("Error registering passkey: invalid or expired challenge".into()));
+            }
+            Err(e) => {
+                error!("[Service] Database error while loading challenge: {:?}", e);
+                return Err(AppError::InternalServerError);
+            }
+        };
+
+        let decode = |value: &str| decode_base64url(value)
+            .map_err(|e| AppError::BadRequest(format!("Error registering passkey: {}", e)));
+        let verified = self.passkey_verifier
+            .verify_registration(
+                &challenge.challenge,
+                &decode(&data.client_data_json)?,
+                &decode(&data.attestation_object)?,
+            )
+            .map_err(|e| {
+                info!("[Service] Passkey registration rejected: {}", e);
+                AppError::BadRequest(format!("Error registering passkey: {}", e))
+            })?;
+
+        let credential_id = encode_base64url(&verified.credential_id);
+        if self.passkey_repo.find_credential_by_credential_id(&credential_id).await.is_ok() {
+            return Err(AppError::BadRequest("Error registering passkey: credential already registered".into()));
+        }
+
+        let new_credential = CreatePasskeyCredential {
+            user_id,
+            credential_id,
+            public_key: verified.public_key,
+            sign_count: verified.sign_count as i64,
+            transports: data.transports,
+        };
+
+        let audit: AuditTrail<PasskeyCredential> = Box::new(|credential| {
+            vec![audit_entry(AUDIT_PASSKEY_REGISTERED, AUDIT_RESOURCE_PASSKEY, credential.id, None, Some(credential))]
+        });
+        match self.passkey_repo.create_credential(new_credential, audit).await {
+            Ok(credential) => {
+                info!("[Service] Passkey registered successfully with ID: {}", credential.id);
+                Ok(ApiResponse::created(credential).into_response())
+            }
+            Err(e) => {
+                error!("[Service] Error saving passkey in database: {:?}", e);
+                Err(AppError::InternalServerError)
+            }
+        }
+    }
+
+    pub async fn find_passkeys(&self, user_id: Uuid) -> Result<HttpResponse, AppError> {
+        info!("[Service] Starting find passkeys process for user ID: {}", user_id);
+
+        let credentials = self.passkey_repo.find_credentials_by_user(user_id).await?;
+        info!("[Service] Found {} passkeys", credentials.len());
+        Ok(ApiResponse::success(credentials).into_response())
+    }
+
+    pub async fn delete_passkey(&self, user_id: Uuid, id: Uuid) -> Result<HttpResponse, AppError> {
+        info!("[Service] Starting delete passkey process for id: {}", id);
+
+        let audit = vec![NewAuditEntry {
+            action: AUDIT_PASSKEY_DELETED,
+            resource_type: AUDIT_RESOURCE_PASSKEY,
+            resource_id: Some(id),
+            changes: audit_diff(&serde_json::json!({ "id": id, "user_id": user_id }), &serde_json::Value::Null),
+        }];
+        match self.passkey_repo.delete_credential(id, user_id, audit).await {
+            Ok(true) => {
+                info!("[Service] Passkey {} deleted for user ID: {}", id, user_id);
+                Ok(ApiResponse::<()>::deleted().into_response())
+            }
+            Ok(false) => Err(AppError::NotFound(format!("Passkey with id '{}' not found", id))),
+            Err(e) => {
+                error!("[Service] Database error while deleting passkey: {:?}", e);
+                Err(AppError::InternalServerError)
+            }
+        }
+    }
+
+    async fn create_challenge(&self, user_id: Option<Uuid>, ceremony: &str) -> Result<PasskeyChallenge, AppError> {
+        let challenge = self.passkey_verifier.generate_challenge().map_err(|e| {
+            error!("[Service] Error generating passkey challenge: {}", e);
+            AppError::InternalServerError
+        })?;
+
+        self.passkey_repo
+            .create_challenge(user_id, challenge, ceremony, PASSKEY_CHALLENGE_TTL_SECONDS)
+            .await
+            .map_err(|e| {
+                error!("[Service] Error saving passkey challenge: {:?}", e);
+                AppError::InternalServerError
+            })
+    }
+
+    // Ponto comum a todos os logins (senha, passkey e OIDC), chamado só depois das credenciais
+    // validadas para não revelar o estado de contas alheias
+    pub async fn issue_login_response(&self, user: UserCompleteData) -> Result<HttpResponse, AppError> {
+        // Depois de um "não fui eu" nenhuma credencial serve para entrar até a redefinição da senha
+        if user.password_reset_required {
+            info!("[Service] Password reset required for user with email: {}", user.email);
+            self.record_login_failure(Some(user.id), user.email, LOGIN_FAILURE_PASSWORD_RESET_REQUIRED).await;
+            return Err(AppError::Forbidden("Password reset required; use the link sent to your email".into()));
+        }
+
+        if let Err(e) = ensure_active_status(&user.status) {
+            info!("[Service] Login refused for {} account with email: {}", user.status, user.email);
+            self.record_login_failure(Some(user.id), user.email, LOGIN_FAILURE_ACCOUNT_INACTIVE).await;
+            return Err(e);
+        }
+
+        // Vale para todos os caminhos: passkey e SSO também não contornam a troca obrigatória
+        if self.is_password_expired(&user.role, user.password_changed_at) {
+            info!("[Service] Password expired for user with email: {}", user.email);
+            self.record_login_failure(Some(user.id), user.email.clone(), LOGIN_FAILURE_PASSWORD_EXPIRED).await;
+            return self.issue_password_change_token(user.id, user.full_name, user.email, user.role);
+        }
+
+        let organization_id = self.organization_repo.find_default_organization(user.id).await?;
+        let response = self.issue_token(user.id, user.full_name, user.email.clone(), user.role, organization_id)?;
+
+        self.login_activity_service
+            .record(NewLoginAttempt { user_id: Some(user.id), email: user.email.clone(), succeeded: true, failure_reason: None })
+            .await;
+        self.device_alert_service.check_login(user.id, &user.email).await;
+        Ok(response)
+    }
+
+    // Emite um token para a organização informada; a verificação de vínculo fica com quem chama
+    pub fn issue_token(
+        &self,
+        id: Uuid,
+        full_name: String,
+        email: String,
+        role: String,
+        organization_id: Option<Uuid>,
+    ) -> Result<HttpResponse, AppError> {
+        info!("[Service] Generating token for user with email: {}", email);
         let token = self.token_generator
             .generate_token(
-                user.id.to_string(),
-                user.full_name.clone(),
-                user.email.to_string(),
+                id.to_string(),
+                full_name.clone(),
+                email.clone(),
+                role,
+                organization_id,
                 &self.config.jwt_secret,
             )
             .map_err(|_| AppError::InternalServerError)?;
 
-        info!("[Service] Token generated successfully for user with email: {}", data.email);
+        info!("[Service] Token generated successfully for user with email: {}", email);
 
         let response = LoginResponse {
             token,
-            id: user.id,
-            full_name: user.full_name,
-            email: user.email
+            id,
+            full_name,
+            email,
+            organization_id,
+            password_expired: false,
         };
 
         Ok(ApiResponse::success(response).into_response())
     }
-}
\ No newline at end of file
+}
+
+fn to_descriptor(credential: PasskeyCredential) -> CredentialDescriptor {
+    CredentialDescriptor {
+        kind: "public-key".to_string(),
+        id: credential.credential_id,
+        transports: credential.transports,
+    }
+}
@@ -0,0 +1,174 @@
+use std::io::Cursor;
+use actix_multipart::Multipart;
+use actix_web::{http::header, web, HttpResponse};
+use futures::TryStreamExt;
+use image::imageops::FilterType;
+use image::{ImageFormat, ImageReader, Limits};
+use log::{error, info};
+use uuid::Uuid;
+use crate::adapters::file_storage::FileStoragePort;
+use crate::core::contracts::repository::users::UserRepository;
+use crate::repositories::user_repository::PgUserRepository;
+use crate::utils::errors::AppError;
+use crate::utils::response::ApiResponse;
+
+// Miniaturas quadradas geradas a partir de cada upload; a maior é a entregue por padrão
+pub const AVATAR_SIZES: [u32; 3] = [64, 128, 256];
+const DEFAULT_AVATAR_SIZE: u32 = 256;
+const MAX_IMAGE_DIMENSION: u32 = 8192;
+const AVATAR_CACHE_CONTROL: &str = "private, max-age=86400";
+
+pub struct AvatarService {
+    user_repo: web::Data<PgUserRepository>,
+    storage: Box<dyn FileStoragePort>,
+    max_bytes: usize,
+}
+
+impl AvatarService {
+    pub fn new(
+        user_repo: web::Data<PgUserRepository>,
+        storage: Box<dyn FileStoragePort>,
+        max_bytes: usize,
+    ) -> Self {
+        Self { user_repo, storage, max_bytes }
+    }
+
+    pub async fn upload_avatar(&self, id: Uuid, payload: Multipart) -> Result<HttpResponse, AppError> {
+        info!("[Service] Starting avatar upload for user ID: {}", id);
+
+        self.user_repo.find_user_by_id(id).await?;
+        let data = self.read_upload(payload).await?;
+        info!("[Service] Received avatar with {} bytes", data.len());
+
+        let thumbnails = web::block(move || render_thumbnails(&data))
+            .await
+            .map_err(|e| {
+                error!("[Service] Error running avatar processing: {:?}", e);
+                AppError::InternalServerError
+            })??;
+
+        for (size, thumbnail) in thumbnails {
+            self.storage.put(&avatar_key(id, size), thumbnail).await.map_err(|e| {
+                error!("[Service] Error storing avatar: {:?}", e);
+                AppError::InternalServerError
+            })?;
+        }
+
+        let user = self.user_repo.set_avatar(id, chrono::Utc::now().naive_utc()).await?;
+        info!("[Service] Avatar updated successfully for user ID: {}", id);
+        Ok(ApiResponse::updated(user).into_response())
+    }
+
+    pub async fn find_avatar(&self, id: Uuid, size: Option<u32>, if_none_match: Option<&str>) -> Result<HttpResponse, AppError> {
+        info!("[Service] Starting find avatar process for user ID: {}", id);
+
+        let size = size.unwrap_or(DEFAULT_AVATAR_SIZE);
+        if !AVATAR_SIZES.contains(&size) {
+            return Err(AppError::BadRequest(format!("Avatar size must be one of {:?}", AVATAR_SIZES)));
+        }
+
+        let user = self.user_repo.find_user_by_id(id).await?;
+        let Some(updated_at) = user.avatar_updated_at else {
+            return Err(AppError::NotFound(format!("User with id '{}' has no avatar", id)));
+        };
+
+        // A data do upload entra na ETag: um novo avatar invalida os caches na hora
+        let etag = format!("\"{}-{}\"", updated_at.and_utc().timestamp_millis(), size);
+        if if_none_match.is_some_and(|value| value.split(',').any(|tag| tag.trim() == etag || tag.trim() == "*")) {
+            return Ok(HttpResponse::NotModified()
+                .insert_header((header::ETAG, etag))
+                .insert_header((header::CACHE_CONTROL, AVATAR_CACHE_CONTROL))
+                .finish());
+        }
+
+        let image = self.storage.get(&avatar_key(id, size)).await.map_err(|e| {
+            error!("[Service] Error reading avatar: {:?}", e);
+            AppError::InternalServerError
+        })?;
+        let Some(image) = image else {
+            error!("[Service] Avatar file missing for user ID: {}", id);
+            return Err(AppError::NotFound(format!("User with id '{}' has no avatar", id)));
+        };
+
+        Ok(HttpResponse::Ok()
+            .content_type("image/png")
+            .insert_header((header::ETAG, etag))
+            .insert_header((header::CACHE_CONTROL, AVATAR_CACHE_CONTROL))
+            .body(image))
+    }
+
+    // Usado na anonimização do titular; o registro do avatar no usuário já foi limpo
+    pub async fn delete_avatar_files(&self, id: Uuid) -> Result<(), AppError> {
+        info!("[Service] Deleting avatar files for user ID: {}", id);
+
+        for size in AVATAR_SIZES {
+            self.storage.delete(&avatar_key(id, size)).await.map_err(|e| {
+                error!("[Service] Error deleting avatar: {:?}", e);
+                AppError::InternalServerError
+            })?;
+        }
+        Ok(())
+    }
+
+    // Lê o primeiro campo de arquivo do multipart, interrompendo assim que o limite é ultrapassado
+    async fn read_upload(&self, mut payload: Multipart) -> Result<Vec<u8>, AppError> {
+        while let Some(mut field) = payload.try_next().await.map_err(invalid_multipart)? {
+            if !matches!(field.name(), Some("avatar") | Some("file")) {
+                continue;
+            }
+
+            let mut data = Vec::new();
+            while let Some(chunk) = field.try_next().await.map_err(invalid_multipart)? {
+                if data.len() + chunk.len() > self.max_bytes {
+                    return Err(AppError::PayloadTooLarge(format!("Avatar must be at most {} bytes", self.max_bytes)));
+                }
+                data.extend_from_slice(&chunk);
+            }
+            return Ok(data);
+        }
+
+        Err(AppError::BadRequest("Multipart body must contain an 'avatar' file field".into()))
+    }
+}
+
+fn invalid_multipart(error: actix_multipart::MultipartError) -> AppError {
+    AppError::BadRequest(format!("Invalid multipart body: {}", error))
+}
+
+fn avatar_key(id: Uuid, size: u32) -> String {
+    format!("avatars/{}/{}.png", id, size)
+}
+
+// O formato é identificado pelo conteúdo (magic bytes), nunca pelo Content-Type enviado pelo cliente
+fn render_thumbnails(data: &[u8]) -> Result<Vec<(u32, Vec<u8>)>, AppError> {
+    let format = image::guess_format(data)
+        .ok()
+        .filter(|format| matches!(format, ImageFormat::Png | ImageFormat::Jpeg | ImageFormat::WebP))
+        .ok_or_else(|| AppError::BadRequest("Avatar must be a PNG, JPEG or WebP image".into()))?;
+
+    let mut limits = Limits::default();
+    limits.max_image_width = Some(MAX_IMAGE_DIMENSION);
+    limits.max_image_height = Some(MAX_IMAGE_DIMENSION);
+
+    let mut reader = ImageReader::with_format(Cursor::new(data), format);
+    reader.limits(limits);
+    let image = reader.decode().map_err(|e| {
+        info!("[Service] Avatar could not be decoded: {}", e);
+        AppError::BadRequest("Avatar image is corrupted or too large".into())
+    })?;
+
+    AVATAR_SIZES
+        .iter()
+        .map(|&size| {
+            let mut encoded = Vec::new();
+            image
+                .resize_to_fill(size, size, FilterType::Lanczos3)
+                .write_to(&mut Cursor::new(&mut encoded), ImageFormat::Png)
+                .map_err(|e| {
+                    error!("[Service] Error encoding avatar thumbnail: {:?}", e);
+                    AppError::InternalServerError
+                })?;
+            Ok((size, encoded))
+        })
+        .collect()
+}
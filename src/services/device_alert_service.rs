@@ -0,0 +1,178 @@
+use std::sync::Arc;
+use actix_web::{web, HttpResponse};
+use log::{error, info};
+use serde_json::json;
+use uuid::Uuid;
+use crate::adapters::mailer::{EmailMessage, MailerPort};
+use crate::config::config_env::Config;
+use crate::config::request_context::current_request;
+use crate::core::contracts::repository::devices::DeviceRepository;
+use crate::core::entities::audit::{
+    audit_redacted_changes, AuditTrail, NewAuditEntry, AUDIT_RESOURCE_USER, AUDIT_USER_PASSWORD_CHANGED, AUDIT_USER_SESSIONS_REVOKED,
+};
+use crate::core::entities::device::{
+    device_fingerprint, CreateDeviceAlert, DeviceAlert, DeviceAlertPasswordReset, DeviceAlertReport, DeviceAlertToken,
+};
+use crate::repositories::device_repository::PgDeviceRepository;
+use crate::services::user_service::UserService;
+use crate::utils::crypto::{random_token, sha256_hex};
+use crate::utils::errors::AppError;
+use crate::utils::response::ApiResponse;
+use crate::utils::validations::validate_required_fields;
+
+pub struct DeviceAlertService {
+    device_repo: web::Data<PgDeviceRepository>,
+    user_service: web::Data<UserService>,
+    mailer: Arc<dyn MailerPort>,
+    config: web::Data<Config>,
+}
+
+impl DeviceAlertService {
+    pub fn new(
+        device_repo: web::Data<PgDeviceRepository>,
+        user_service: web::Data<UserService>,
+        mailer: Arc<dyn MailerPort>,
+        config: web::Data<Config>,
+    ) -> Self {
+        Self { device_repo, user_service, mailer, config }
+    }
+
+    // Chamado após um login bem-sucedido; falhas ficam no log e não impedem o acesso
+    pub async fn check_login(&self, user_id: Uuid, email: &str) {
+        let context = current_request();
+        let fingerprint = device_fingerprint(context.user_agent.as_deref(), context.ip.as_deref());
+
+        let sighting = match self.device_repo.record_device(user_id, &fingerprint).await {
+            Ok(sighting) => sighting,
+            Err(e) => {
+                error!("[Service] Database error while recording device of user {}: {:?}", user_id, e);
+                return;
+            }
+        };
+        // O primeiro dispositivo da conta não tem com o que ser comparado
+        if !sighting.new_device || sighting.first_device {
+            return;
+        }
+        info!("[Service] Login from a new device for user ID: {}", user_id);
+
+        let token = random_token();
+        let alert = match self.device_repo.create_alert(CreateDeviceAlert {
+            user_id,
+            device_id: sighting.device.id,
+            token_hash: sha256_hex(token.as_bytes()),
+            ip: context.ip.clone(),
+            user_agent: context.user_agent.clone(),
+            expires_at: chrono::Utc::now().naive_utc() + chrono::Duration::hours(self.config.device_alert_ttl_hours),
+        }).await {
+            Ok(alert) => alert,
+            Err(e) => {
+                error!("[Service] Database error while creating device alert for user {}: {:?}", user_id, e);
+                return;
+            }
+        };
+
+        let message = EmailMessage {
+            to: email.to_string(),
+            subject: "New sign-in to your account".to_string(),
+            body: format!(
+                "Your account was accessed from a new device.\n\nDevice: {}\nIP address: {}\nTime: {} UTC\n\nIf this was you, no action is needed. If it wasn't you, secure your account: {}?token={}\n\nThe link signs out every session and requires a new password. It expires at {} UTC.",
+                alert.user_agent.as_deref().unwrap_or("unknown"),
+                alert.ip.as_deref().unwrap_or("unknown"),
+                alert.created_at.format("%Y-%m-%d %H:%M"),
+                self.config.device_alert_report_url,
+                token,
+                alert.expires_at.format("%Y-%m-%d %H:%M"),
+            ),
+        };
+        if let Err(e) = self.mailer.send(message).await {
+            error!("[Service] Error sending new device alert to {}: {:?}", email, e);
+        }
+    }
+
+    // "Não fui eu": encerra todas as sessões, remove passkeys e identidades cadastradas desde o
+    // login denunciado e bloqueia qualquer login até a redefinição da senha
+    pub async fn report_alert(&self, data: DeviceAlertToken) -> Result<HttpResponse, AppError> {
+        info!("[Service] Starting device alert report process");
+
+        let alert = self.find_alert(&data.token).await?;
+        if alert.reported_at.is_some() {
+            return Err(AppError::Conflict("This sign-in was already reported".into()));
+        }
+        if alert.expires_at <= chrono::Utc::now().naive_utc() {
+            info!("[Service] Device alert {} has expired", alert.id);
+            return Err(AppError::BadRequest("Device alert link has expired".into()));
+        }
+
+        let user_id = alert.user_id;
+        let audit: AuditTrail<DeviceAlertReport> = Box::new(move |report| {
+            vec![NewAuditEntry {
+                action: AUDIT_USER_SESSIONS_REVOKED,
+                resource_type: AUDIT_RESOURCE_USER,
+                resource_id: Some(user_id),
+                changes: json!({
+                    "password_reset_required": { "before": false, "after": true },
+                    "refresh_tokens_revoked": { "before": null, "after": report.refresh_tokens_revoked },
+                    "passkeys_removed": { "before": null, "after": report.passkeys_removed },
+                    "identities_removed": { "before": null, "after": report.identities_removed },
+                }),
+            }]
+        });
+        let report = match self.device_repo.report_alert(&alert, audit).await {
+            Ok(Some(report)) => report,
+            Ok(None) => return Err(AppError::Conflict("This sign-in was already reported".into())),
+            Err(sqlx::Error::RowNotFound) => return Err(AppError::NotFound("Device alert not found".into())),
+            Err(e) => return Err(e.into()),
+        };
+        info!("[Service] Sessions of user {} revoked through device alert {}", alert.user_id, alert.id);
+
+        Ok(ApiResponse::success(report).into_response())
+    }
+
+    // O mesmo link redefine a senha depois da denúncia, dentro do prazo de validade
+    pub async fn reset_password(&self, data: DeviceAlertPasswordReset) -> Result<HttpResponse, AppError> {
+        info!("[Service] Starting password reset through device alert");
+
+        validate_required_fields(&[
+            ("token", data.token.is_empty()),
+            ("new_password", data.new_password.is_empty()),
+        ], "Error resetting password")?;
+
+        let alert = self.find_alert(&data.token).await?;
+        let Some(reported_at) = alert.reported_at else {
+            return Err(AppError::BadRequest("Report the sign-in before resetting the password".into()));
+        };
+        if alert.password_reset_at.is_some() {
+            return Err(AppError::Conflict("Password was already reset with this link".into()));
+        }
+        if reported_at + chrono::Duration::hours(self.config.device_alert_ttl_hours) <= chrono::Utc::now().naive_utc() {
+            info!("[Service] Password reset window of device alert {} has expired", alert.id);
+            return Err(AppError::BadRequest("Device alert link has expired".into()));
+        }
+
+        // Mesmas regras da troca de senha: as senhas recentes não podem ser reutilizadas
+        let password_hash = self.user_service.hash_new_password(alert.user_id, data.new_password).await?;
+
+        let audit = vec![NewAuditEntry {
+            action: AUDIT_USER_PASSWORD_CHANGED,
+            resource_type: AUDIT_RESOURCE_USER,
+            resource_id: Some(alert.user_id),
+            changes: audit_redacted_changes(&["password"]),
+        }];
+        match self.device_repo.reset_password(&alert, &password_hash, self.user_service.kept_password_history(), audit).await {
+            Ok(true) => {}
+            Ok(false) => return Err(AppError::Conflict("Password was already reset with this link".into())),
+            Err(sqlx::Error::RowNotFound) => return Err(AppError::NotFound("Device alert not found".into())),
+            Err(e) => return Err(e.into()),
+        }
+        info!("[Service] Password reset through device alert {} for user {}", alert.id, alert.user_id);
+
+        Ok(ApiResponse::<()>::updated_password().into_response())
+    }
+
+    async fn find_alert(&self, token: &str) -> Result<DeviceAlert, AppError> {
+        self.device_repo
+            .find_alert_by_token(&sha256_hex(token.as_bytes()))
+            .await?
+            .ok_or_else(|| AppError::NotFound("Device alert not found".into()))
+    }
+}
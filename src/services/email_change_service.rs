@@ -0,0 +1,163 @@
+use std::sync::Arc;
+use actix_web::{web, HttpResponse};
+use log::{error, info};
+use uuid::Uuid;
+use crate::adapters::mailer::{EmailMessage, MailerPort};
+use crate::config::config_env::Config;
+use crate::core::contracts::repository::email_changes::EmailChangeRepository;
+use crate::core::contracts::repository::users::UserRepository;
+use crate::core::entities::audit::{audit_diff, NewAuditEntry, AUDIT_RESOURCE_USER, AUDIT_USER_EMAIL_CHANGED};
+use crate::core::entities::email_change::{CreateEmailChange, EmailChange, EmailChangeToken};
+use crate::core::entities::user::UserDataCreated;
+use crate::repositories::email_change_repository::PgEmailChangeRepository;
+use crate::repositories::user_repository::PgUserRepository;
+use crate::utils::crypto::{random_token, sha256_hex};
+use crate::utils::errors::{is_unique_violation, AppError};
+use crate::utils::etag::etag;
+use crate::utils::response::ApiResponse;
+
+pub struct EmailChangeService {
+    email_change_repo: web::Data<PgEmailChangeRepository>,
+    user_repo: web::Data<PgUserRepository>,
+    mailer: Arc<dyn MailerPort>,
+    config: web::Data<Config>,
+}
+
+impl EmailChangeService {
+    pub fn new(
+        email_change_repo: web::Data<PgEmailChangeRepository>,
+        user_repo: web::Data<PgUserRepository>,
+        mailer: Arc<dyn MailerPort>,
+        config: web::Data<Config>,
+    ) -> Self {
+        Self { email_change_repo, user_repo, mailer, config }
+    }
+
+    // O email só muda após a confirmação pelo novo endereço; o antigo recebe um link para desfazer
+    pub async fn request_change(&self, user: &UserDataCreated, new_email: &str) -> Result<EmailChange, AppError> {
+        info!("[Service] Starting email change request for user ID: {}", user.id);
+
+        let confirm_token = random_token();
+        let revert_token = random_token();
+        let now = chrono::Utc::now().naive_utc();
+        let change = self.email_change_repo.create_email_change(CreateEmailChange {
+            user_id: user.id,
+            old_email: user.email.clone(),
+            new_email: new_email.to_string(),
+            confirm_token_hash: sha256_hex(confirm_token.as_bytes()),
+            revert_token_hash: sha256_hex(revert_token.as_bytes()),
+            confirm_expires_at: now + chrono::Duration::hours(self.config.email_change_ttl_hours),
+            revert_expires_at: now + chrono::Duration::days(self.config.email_change_revert_days),
+        }).await?;
+        info!("[Service] Email change created successfully with ID: {}", change.id);
+
+        self.deliver(EmailMessage {
+            to: change.new_email.clone(),
+            subject: "Confirm your new email address".to_string(),
+            body: format!(
+                "Confirm that this address should be used to sign in to your account: {}?token={}\n\nThis link expires at {} UTC.",
+                self.config.email_change_confirm_url,
+                confirm_token,
+                change.confirm_expires_at.format("%Y-%m-%d %H:%M"),
+            ),
+        }).await;
+        self.deliver(EmailMessage {
+            to: change.old_email.clone(),
+            subject: "Your email address is being changed".to_string(),
+            body: format!(
+                "A request was made to change the email of your account to {}.\n\nIf you did not request this, undo the change: {}?token={}\n\nThis link expires at {} UTC.",
+                change.new_email,
+                self.config.email_change_revert_url,
+                revert_token,
+                change.revert_expires_at.format("%Y-%m-%d %H:%M"),
+            ),
+        }).await;
+
+        Ok(change)
+    }
+
+    pub async fn confirm_change(&self, data: EmailChangeToken) -> Result<HttpResponse, AppError> {
+        info!("[Service] Starting email change confirmation process");
+
+        let not_found = || AppError::NotFound("Email change not found".into());
+        let change = self.email_change_repo
+            .find_by_confirm_token(&sha256_hex(data.token.as_bytes()))
+            .await?
+            .filter(EmailChange::is_pending)
+            .ok_or_else(not_found)?;
+        if change.confirm_expires_at <= chrono::Utc::now().naive_utc() {
+            info!("[Service] Email change {} has expired", change.id);
+            return Err(AppError::BadRequest("Email change confirmation has expired".into()));
+        }
+
+        if self.user_repo.email_exists_for_other_user(&change.new_email, change.user_id).await? {
+            return Err(AppError::Conflict(format!("Email '{}' is already in use by another user", change.new_email)));
+        }
+
+        let audit = vec![email_change_audit_entry(change.user_id, &change.old_email, &change.new_email)];
+        match self.email_change_repo.confirm_email_change(&change, audit).await {
+            Ok(true) => {}
+            Ok(false) => return Err(not_found()),
+            Err(e) if is_unique_violation(&e) => {
+                info!("[Service] Email was taken concurrently by another request");
+                return Err(AppError::Conflict("Email is already in use by another user".into()));
+            }
+            Err(e) => return Err(e.into()),
+        }
+        info!("[Service] Email change {} confirmed for user {}", change.id, change.user_id);
+
+        let user = self.user_repo.find_user_by_id(change.user_id).await?;
+        let tag = etag(user.version);
+        Ok(ApiResponse::updated(user).with_etag(tag).into_response())
+    }
+
+    pub async fn revert_change(&self, data: EmailChangeToken) -> Result<HttpResponse, AppError> {
+        info!("[Service] Starting email change revert process");
+
+        let change = self.email_change_repo
+            .find_by_revert_token(&sha256_hex(data.token.as_bytes()))
+            .await?
+            .filter(|change| change.reverted_at.is_none() && change.cancelled_at.is_none())
+            .ok_or_else(|| AppError::NotFound("Email change not found".into()))?;
+        if change.revert_expires_at <= chrono::Utc::now().naive_utc() {
+            info!("[Service] Revert link of email change {} has expired", change.id);
+            return Err(AppError::BadRequest("Revert link has expired".into()));
+        }
+
+        // Na reversão o antes e o depois se invertem
+        let audit = vec![email_change_audit_entry(change.user_id, &change.new_email, &change.old_email)];
+        match self.email_change_repo.revert_email_change(&change, audit).await {
+            Ok(true) => {}
+            Ok(false) => {
+                return Err(AppError::Conflict("Email was changed again and this change can no longer be reverted".into()));
+            }
+            Err(e) if is_unique_violation(&e) => {
+                info!("[Service] Previous email is now used by another user");
+                return Err(AppError::Conflict(format!("Email '{}' is already in use by another user", change.old_email)));
+            }
+            Err(e) => return Err(e.into()),
+        }
+        info!("[Service] Email change {} reverted for user {}", change.id, change.user_id);
+
+        let user = self.user_repo.find_user_by_id(change.user_id).await?;
+        let tag = etag(user.version);
+        Ok(ApiResponse::updated(user).with_etag(tag).into_response())
+    }
+
+    // Falhas de envio ficam no log; o pedido continua válido e pode ser refeito
+    async fn deliver(&self, message: EmailMessage) {
+        let to = message.to.clone();
+        if let Err(e) = self.mailer.send(message).await {
+            error!("[Service] Error sending email change message to {}: {:?}", to, e);
+        }
+    }
+}
+
+fn email_change_audit_entry(user_id: Uuid, before: &str, after: &str) -> NewAuditEntry {
+    NewAuditEntry {
+        action: AUDIT_USER_EMAIL_CHANGED,
+        resource_type: AUDIT_RESOURCE_USER,
+        resource_id: Some(user_id),
+        changes: audit_diff(&serde_json::json!({ "email": before }), &serde_json::json!({ "email": after })),
+    }
+}
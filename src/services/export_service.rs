@@ -0,0 +1,250 @@
+use std::path::{Path, PathBuf};
+use actix_web::{http::{header, StatusCode}, rt, web, HttpResponse};
+use futures::TryStreamExt;
+use log::{error, info};
+use thiserror::Error;
+use tokio::io::{AsyncWriteExt, BufWriter};
+use tokio_util::io::ReaderStream;
+use uuid::Uuid;
+use crate::adapters::token_hasher::unix_timestamp;
+use crate::config::config_env::Config;
+use crate::config::tenant::{current_scope, CURRENT_TENANT};
+use crate::core::contracts::repository::export_jobs::ExportJobRepository;
+use crate::core::contracts::repository::users::UserRepository;
+use crate::core::entities::export_job::{
+    CreateExportJob, ExportDownloadQuery, ExportJob, ExportJobResponse, EXPORT_STATUS_COMPLETED,
+};
+use crate::core::entities::user::{UserExportFormat, UserListParams, ROLE_ADMIN};
+use crate::repositories::export_job_repository::PgExportJobRepository;
+use crate::repositories::user_repository::PgUserRepository;
+use crate::services::user_service::UserService;
+use crate::utils::crypto::{constant_time_eq, hmac_sha256_hex};
+use crate::utils::errors::AppError;
+use crate::utils::export::{export_header, export_row};
+use crate::utils::response::ApiResponse;
+
+const DOWNLOAD_LINK_TTL_SECONDS: i64 = 900;
+const PROGRESS_UPDATE_INTERVAL: i64 = 1000;
+
+#[derive(Debug, Error)]
+enum ExportError {
+    #[error("database error: {0}")]
+    Database(#[from] sqlx::Error),
+    #[error("file error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("encoding error: {0}")]
+    Encoding(#[from] AppError),
+}
+
+pub struct ExportService {
+    export_repo: web::Data<PgExportJobRepository>,
+    user_repo: web::Data<PgUserRepository>,
+    user_service: web::Data<UserService>,
+    config: web::Data<Config>,
+}
+
+impl ExportService {
+    pub fn new(
+        export_repo: web::Data<PgExportJobRepository>,
+        user_repo: web::Data<PgUserRepository>,
+        user_service: web::Data<UserService>,
+        config: web::Data<Config>,
+    ) -> Self {
+        Self { export_repo, user_repo, user_service, config }
+    }
+
+    pub async fn create_job(&self, user_id: Uuid, organization_id: Option<Uuid>, data: CreateExportJob) -> Result<HttpResponse, AppError> {
+        info!("[Service] Starting export job creation for user ID: {}", user_id);
+
+        // Filtros inválidos são recusados já na criação, não no processamento
+        let params = self.user_service.export_params(data.filters.clone(), organization_id)?;
+        let job = self.export_repo.create_job(user_id, organization_id, data.format.as_str(), data.filters).await?;
+        info!("[Service] Export job created with ID: {}", job.id);
+
+        self.spawn_job(job.id, data.format, params);
+        Ok(ApiResponse::new(
+            "Export job accepted".to_string(),
+            StatusCode::ACCEPTED,
+            Some(ExportJobResponse { job, download_url: None, download_url_expires_at: None }),
+        ).into_response())
+    }
+
+    pub async fn find_job(&self, id: Uuid, user_id: Uuid, role: &str) -> Result<HttpResponse, AppError> {
+        info!("[Service] Starting find export job process for id: {}", id);
+
+        let job = match self.export_repo.find_job_by_id(id).await {
+            Ok(job) if job.requested_by == Some(user_id) || role == ROLE_ADMIN => job,
+            Ok(_) | Err(sqlx::Error::RowNotFound) => {
+                return Err(AppError::NotFound(format!("Export job with id '{}' not found", id)));
+            }
+            Err(e) => {
+                error!("[Service] Database error while finding export job: {:?}", e);
+                return Err(AppError::InternalServerError);
+            }
+        };
+
+        let (download_url, download_url_expires_at) = match self.download_link(&job) {
+            Some((url, expires_at)) => (Some(url), Some(expires_at)),
+            None => (None, None),
+        };
+        Ok(ApiResponse::success(ExportJobResponse { job, download_url, download_url_expires_at }).into_response())
+    }
+
+    pub async fn download(&self, id: Uuid, query: ExportDownloadQuery) -> Result<HttpResponse, AppError> {
+        info!("[Service] Starting export download for job id: {}", id);
+
+        let expected = self.sign(id, query.expires);
+        if query.expires < unix_timestamp() as i64 || !constant_time_eq(expected.as_bytes(), query.signature.as_bytes()) {
+            info!("[Service] Invalid or expired download link for job id: {}", id);
+            return Err(AppError::Forbidden("Invalid or expired download link".into()));
+        }
+
+        let job = self.export_repo.find_job_by_id(id).await?;
+        let (Some(path), Some(format)) = (job.file_path.as_deref(), UserExportFormat::from_name(&job.format)) else {
+            return Err(AppError::NotFound("Export file is no longer available".into()));
+        };
+
+        let file = tokio::fs::File::open(path).await.map_err(|e| {
+            error!("[Service] Error opening export file {}: {:?}", path, e);
+            AppError::NotFound("Export file is no longer available".into())
+        })?;
+
+        Ok(HttpResponse::Ok()
+            .content_type(format.content_type())
+            .insert_header((header::CONTENT_DISPOSITION, format!("attachment; filename=\"users-{}.{}\"", id, format.as_str())))
+            .streaming(ReaderStream::new(file)))
+    }
+
+    // Jobs interrompidos por uma reinicialização não são retomados; os arquivos parciais são removidos
+    pub async fn fail_interrupted_jobs(&self) -> Result<(), AppError> {
+        let jobs = self.export_repo.fail_interrupted_jobs().await?;
+        for job in &jobs {
+            if let Some(format) = UserExportFormat::from_name(&job.format) {
+                remove_file(&partial_path(&self.job_path(job.id, format))).await;
+            }
+        }
+        if !jobs.is_empty() {
+            info!("[Service] Marked {} interrupted export jobs as failed", jobs.len());
+        }
+        Ok(())
+    }
+
+    pub async fn cleanup_expired_exports(&self) -> Result<(), AppError> {
+        let jobs = self.export_repo.find_expired_jobs(chrono::Utc::now().naive_utc()).await?;
+        for job in jobs {
+            if let Some(path) = &job.file_path {
+                remove_file(Path::new(path)).await;
+            }
+            self.export_repo.expire_job(job.id).await?;
+            info!("[Service] Export file removed for expired job ID: {}", job.id);
+        }
+        Ok(())
+    }
+
+    fn spawn_job(&self, id: Uuid, format: UserExportFormat, params: UserListParams) {
+        let export_repo = self.export_repo.clone();
+        let user_repo = self.user_repo.clone();
+        let path = self.job_path(id, format);
+        let retention = chrono::Duration::hours(self.config.export_retention_hours);
+
+        // O job roda fora da requisição, mas continua restrito à organização de quem o criou
+        rt::spawn(CURRENT_TENANT.scope(current_scope(), async move {
+            info!("[Service] Running export job ID: {}", id);
+            let part = partial_path(&path);
+            let result = match write_export(&export_repo, &user_repo, id, format, params, &part).await {
+                Ok(processed) => tokio::fs::rename(&part, &path).await.map(|_| processed).map_err(ExportError::from),
+                Err(e) => Err(e),
+            };
+
+            let saved = match result {
+                Ok(processed) => {
+                    info!("[Service] Export job {} completed with {} rows", id, processed);
+                    let expires_at = chrono::Utc::now().naive_utc() + retention;
+                    export_repo.complete_job(id, processed, &path.to_string_lossy(), expires_at).await
+                }
+                Err(e) => {
+                    error!("[Service] Export job {} failed: {}", id, e);
+                    remove_file(&part).await;
+                    export_repo.fail_job(id, "Export failed; please try again").await
+                }
+            };
+            if let Err(e) = saved {
+                error!("[Service] Error saving export job {} result: {:?}", id, e);
+            }
+        }));
+    }
+
+    fn job_path(&self, id: Uuid, format: UserExportFormat) -> PathBuf {
+        Path::new(&self.config.export_storage_dir).join(format!("{}.{}", id, format.as_str()))
+    }
+
+    fn download_link(&self, job: &ExportJob) -> Option<(String, chrono::NaiveDateTime)> {
+        if job.status != EXPORT_STATUS_COMPLETED {
+            return None;
+        }
+
+        // O link nunca dura mais que o próprio arquivo
+        let file_expires_at = job.expires_at?;
+        let link_expires_at = (chrono::Utc::now().naive_utc() + chrono::Duration::seconds(DOWNLOAD_LINK_TTL_SECONDS))
+            .min(file_expires_at);
+        let expires = link_expires_at.and_utc().timestamp();
+
+        Some((
+            format!("/api/v1/exports/{}/download?expires={}&signature={}", job.id, expires, self.sign(job.id, expires)),
+            link_expires_at,
+        ))
+    }
+
+    fn sign(&self, id: Uuid, expires: i64) -> String {
+        hmac_sha256_hex(self.config.jwt_secret.as_bytes(), format!("export:{}:{}", id, expires).as_bytes())
+    }
+}
+
+async fn write_export(
+    export_repo: &PgExportJobRepository,
+    user_repo: &PgUserRepository,
+    id: Uuid,
+    format: UserExportFormat,
+    params: UserListParams,
+    path: &Path,
+) -> Result<i64, ExportError> {
+    let total = user_repo.count_users(&params).await?;
+    export_repo.start_job(id, total).await?;
+
+    if let Some(dir) = path.parent() {
+        tokio::fs::create_dir_all(dir).await?;
+    }
+    let mut file = BufWriter::new(tokio::fs::File::create(path).await?);
+    if let Some(header_row) = export_header(format) {
+        file.write_all(&header_row?).await?;
+    }
+
+    let mut rows = user_repo.stream_users(params);
+    let mut processed = 0;
+    while let Some(user) = rows.try_next().await? {
+        file.write_all(&export_row(format, &user)?).await?;
+        processed += 1;
+        if processed % PROGRESS_UPDATE_INTERVAL == 0 {
+            export_repo.update_progress(id, processed).await?;
+        }
+    }
+
+    file.flush().await?;
+    file.into_inner().sync_all().await?;
+    Ok(processed)
+}
+
+// O arquivo só recebe o nome final quando está completo
+fn partial_path(path: &Path) -> PathBuf {
+    let mut partial = path.as_os_str().to_owned();
+    partial.push(".part");
+    PathBuf::from(partial)
+}
+
+async fn remove_file(path: &Path) {
+    match tokio::fs::remove_file(path).await {
+        Ok(()) => {}
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+        Err(e) => error!("[Service] Error removing export file {}: {:?}", path.display(), e),
+    }
+}
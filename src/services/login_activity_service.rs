@@ -0,0 +1,70 @@
+use actix_web::{web, HttpResponse};
+use log::{error, info};
+use uuid::Uuid;
+use crate::config::request_context::current_request;
+use crate::core::contracts::repository::login_attempts::LoginAttemptRepository;
+use crate::core::contracts::repository::users::UserRepository;
+use crate::core::entities::login_attempt::{LoginActivityCursor, LoginActivityQuery, NewLoginAttempt};
+use crate::repositories::login_attempt_repository::PgLoginAttemptRepository;
+use crate::repositories::user_repository::PgUserRepository;
+use crate::utils::errors::AppError;
+use crate::utils::pagination::{decode_cursor, encode_cursor, page_size};
+use crate::utils::response::ApiResponse;
+
+pub struct LoginActivityService {
+    login_attempt_repo: web::Data<PgLoginAttemptRepository>,
+    user_repo: web::Data<PgUserRepository>,
+}
+
+impl LoginActivityService {
+    pub fn new(login_attempt_repo: web::Data<PgLoginAttemptRepository>, user_repo: web::Data<PgUserRepository>) -> Self {
+        Self { login_attempt_repo, user_repo }
+    }
+
+    // Uma falha ao registrar não muda o resultado do login, fica apenas no log
+    pub async fn record(&self, attempt: NewLoginAttempt) {
+        let context = current_request();
+        let email = attempt.email.clone();
+        if let Err(e) = self.login_attempt_repo.create_attempt(attempt, &context).await {
+            error!("[Service] Error recording login attempt for email {} in request {:?}: {:?}", email, context.request_id, e);
+        }
+    }
+
+    pub async fn find_activity(&self, user_id: Uuid, query: LoginActivityQuery) -> Result<HttpResponse, AppError> {
+        info!("[Service] Starting find login activity process for user ID: {}", user_id);
+        let limit = page_size(query.limit)?;
+        let after = query.cursor.as_deref().map(decode_cursor::<LoginActivityCursor>).transpose()?;
+
+        if self.user_repo.find_user_status(user_id).await?.is_none() {
+            return Err(AppError::NotFound(format!("User with id '{}' not found", user_id)));
+        }
+
+        // Uma linha extra indica se existe próxima página
+        let mut attempts = self.login_attempt_repo.find_attempts_by_user(user_id, after, limit + 1).await?;
+        let next_cursor = if attempts.len() as i64 > limit {
+            attempts.truncate(limit as usize);
+            attempts.last().map(|last| encode_cursor(&LoginActivityCursor { created_at: last.created_at, id: last.id }))
+        } else {
+            None
+        };
+        info!("[Service] Found {} login attempts", attempts.len());
+        Ok(ApiResponse::paginated(attempts, next_cursor).into_response())
+    }
+
+    // Remove as tentativas registradas há mais de `retention_days` dias
+    pub async fn purge_old_attempts(&self, retention_days: i64) -> Result<u64, AppError> {
+        info!("[Service] Starting purge of login attempts older than {} days", retention_days);
+
+        let cutoff = chrono::Utc::now().naive_utc() - chrono::Duration::days(retention_days);
+        match self.login_attempt_repo.delete_attempts_older_than(cutoff).await {
+            Ok(purged) => {
+                info!("[Service] Purged {} login attempts", purged);
+                Ok(purged)
+            }
+            Err(e) => {
+                error!("[Service] Database error while purging login attempts: {:?}", e);
+                Err(AppError::InternalServerError)
+            }
+        }
+    }
+}
@@ -1,2 +1,14 @@
 pub mod auth_service;
+pub mod oauth_service;
+pub mod oidc_service;
 pub mod user_service;
+pub mod export_service;
+pub mod avatar_service;
+pub mod organization_service;
+pub mod user_attribute_service;
+pub mod user_invitation_service;
+pub mod privacy_service;
+pub mod email_change_service;
+pub mod audit_service;
+pub mod login_activity_service;
+pub mod device_alert_service;
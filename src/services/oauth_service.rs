@@ -0,0 +1,401 @@
+use actix_web::{web, HttpResponse};
+use log::{error, info};
+use uuid::Uuid;
+use crate::adapters::password_hasher::PasswordEncryptorPort;
+use crate::adapters::token_hasher::{unix_timestamp, TokenGeneratorPort};
+use crate::config::config_env::Config;
+use crate::core::contracts::repository::oauth::OAuthRepository;
+use crate::core::contracts::repository::users::UserRepository;
+use crate::core::entities::auth::ClaimsToUserToken;
+use crate::core::entities::user::{ROLE_USER, STATUS_ACTIVE};
+use crate::core::entities::oauth::{
+    AuthorizeDecision, AuthorizeRedirect, AuthorizeRequest, ConsentDetails, CreateOAuthClient, NewOAuthClient,
+    OAuthAuthorizationCode, OAuthClient, OAuthClientCreated, TokenIntrospection, TokenIntrospectionRequest,
+    TokenRequest, TokenResponse,
+};
+use crate::repositories::oauth_repository::PgOAuthRepository;
+use crate::repositories::user_repository::PgUserRepository;
+use crate::utils::crypto::{constant_time_eq, random_token, sha256_base64url, sha256_hex};
+use crate::utils::errors::{AppError, OAuthError};
+use crate::utils::response::ApiResponse;
+
+const AUTHORIZATION_CODE_TTL_SECONDS: i64 = 60;
+const ACCESS_TOKEN_TTL_SECONDS: u64 = 900;
+const REFRESH_TOKEN_TTL_SECONDS: i64 = 30 * 24 * 3600;
+
+pub struct ClientCredentials {
+    pub client_id: String,
+    pub client_secret: Option<String>,
+}
+
+pub struct OAuthService {
+    oauth_repo: web::Data<PgOAuthRepository>,
+    user_repo: web::Data<PgUserRepository>,
+    config: web::Data<Config>,
+    password_encryptor: Box<dyn PasswordEncryptorPort>,
+    token_generator: Box<dyn TokenGeneratorPort>,
+}
+
+impl OAuthService {
+    pub fn new(
+        oauth_repo: web::Data<PgOAuthRepository>,
+        user_repo: web::Data<PgUserRepository>,
+        config: web::Data<Config>,
+        password_encryptor: Box<dyn PasswordEncryptorPort>,
+        token_generator: Box<dyn TokenGeneratorPort>,
+    ) -> Self {
+        Self { oauth_repo, user_repo, config, password_encryptor, token_generator }
+    }
+
+    pub async fn create_client(&self, owner_id: Uuid, data: CreateOAuthClient) -> Result<HttpResponse, AppError> {
+        info!("[Service] Starting OAuth client registration for owner: {}", owner_id);
+
+        if data.name.trim().is_empty() {
+            return Err(AppError::BadRequest("Error registering client: name cannot be empty".into()));
+        }
+        if data.redirect_uris.is_empty() {
+            return Err(AppError::BadRequest("Error registering client: redirect_uris cannot be empty".into()));
+        }
+        if let Some(uri) = data.redirect_uris.iter().find(|uri| !is_valid_redirect_uri(uri)) {
+            return Err(AppError::BadRequest(format!("Error registering client: '{}' is not a valid redirect uri", uri)));
+        }
+
+        let client_secret = data.confidential.then(random_token);
+        let client_secret_hash = match &client_secret {
+            Some(secret) => Some(self.password_encryptor.hash_password(secret).map_err(|e| {
+                error!("[Service] Error hashing client secret: {:?}", e);
+                AppError::InternalServerError
+            })?),
+            None => None,
+        };
+
+        let new_client = NewOAuthClient {
+            client_id: Uuid::new_v4().simple().to_string(),
+            client_secret_hash,
+            name: data.name,
+            redirect_uris: data.redirect_uris,
+            owner_id,
+        };
+
+        match self.oauth_repo.create_client(new_client).await {
+            Ok(client) => {
+                info!("[Service] OAuth client registered successfully with ID: {}", client.id);
+                Ok(ApiResponse::created(OAuthClientCreated { client, client_secret }).into_response())
+            }
+            Err(e) => {
+                error!("[Service] Error creating OAuth client in database: {:?}", e);
+                Err(AppError::InternalServerError)
+            }
+        }
+    }
+
+    pub async fn find_clients(&self, owner_id: Uuid) -> Result<HttpResponse, AppError> {
+        info!("[Service] Starting find OAuth clients process for owner: {}", owner_id);
+
+        let clients = self.oauth_repo.find_clients_by_owner(owner_id).await?;
+        info!("[Service] Found {} OAuth clients", clients.len());
+        Ok(ApiResponse::success(clients).into_response())
+    }
+
+    pub async fn delete_client(&self, owner_id: Uuid, id: Uuid) -> Result<HttpResponse, AppError> {
+        info!("[Service] Starting delete OAuth client process for id: {}", id);
+
+        match self.oauth_repo.delete_client(id, owner_id).await {
+            Ok(true) => Ok(ApiResponse::<()>::deleted().into_response()),
+            Ok(false) => Err(AppError::NotFound(format!("Client with id '{}' not found", id))),
+            Err(e) => {
+                error!("[Service] Database error while deleting OAuth client: {:?}", e);
+                Err(AppError::InternalServerError)
+            }
+        }
+    }
+
+    pub async fn consent_details(&self, data: AuthorizeRequest) -> Result<HttpResponse, AppError> {
+        info!("[Service] Loading consent details for client: {}", data.client_id);
+
+        let client = self.validate_authorize_request(&data).await?;
+        let details = ConsentDetails {
+            client_id: client.client_id,
+            client_name: client.name,
+            redirect_uri: data.redirect_uri,
+            scope: data.scope,
+        };
+
+        Ok(ApiResponse::success(details).into_response())
+    }
+
+    pub async fn authorize(&self, user_id: Uuid, data: AuthorizeDecision) -> Result<HttpResponse, AppError> {
+        info!("[Service] Processing authorization decision of user {} for client: {}", user_id, data.request.client_id);
+
+        self.validate_authorize_request(&data.request).await?;
+        let request = data.request;
+
+        if !data.approve {
+            info!("[Service] User {} denied access to client: {}", user_id, request.client_id);
+            let redirect_to = redirect_with(&request.redirect_uri, &[("error", Some("access_denied")), ("state", request.state.as_deref())])?;
+            return Ok(ApiResponse::success(AuthorizeRedirect { redirect_to }).into_response());
+        }
+
+        let code = random_token();
+        let authorization_code = OAuthAuthorizationCode {
+            code_hash: sha256_hex(code.as_bytes()),
+            client_id: request.client_id.clone(),
+            user_id,
+            redirect_uri: request.redirect_uri.clone(),
+            scope: request.scope.clone(),
+            code_challenge: request.code_challenge.clone(),
+            expires_at: chrono::Utc::now().naive_utc() + chrono::Duration::seconds(AUTHORIZATION_CODE_TTL_SECONDS),
+        };
+        self.oauth_repo.create_authorization_code(authorization_code).await?;
+        info!("[Service] Authorization code issued for client: {}", request.client_id);
+
+        let redirect_to = redirect_with(&request.redirect_uri, &[("code", Some(&code)), ("state", request.state.as_deref())])?;
+        Ok(ApiResponse::success(AuthorizeRedirect { redirect_to }).into_response())
+    }
+
+    pub async fn token(&self, credentials: ClientCredentials, data: TokenRequest) -> Result<HttpResponse, OAuthError> {
+        info!("[Service] Starting token request with grant type '{}' for client: {}", data.grant_type, credentials.client_id);
+
+        let client = self.authenticate_client(&credentials).await?;
+
+        let (user_id, scope) = match data.grant_type.as_str() {
+            "authorization_code" => self.exchange_authorization_code(&client, &data).await?,
+            "refresh_token" => self.rotate_refresh_token(&client, &data).await?,
+            _ => return Err(OAuthError::new("unsupported_grant_type", "Only authorization_code and refresh_token are supported")),
+        };
+
+        let response = self.issue_tokens(&client, user_id, scope).await?;
+        info!("[Service] Tokens issued for client: {}", client.client_id);
+        Ok(HttpResponse::Ok()
+            .insert_header(("Cache-Control", "no-store"))
+            .json(response))
+    }
+
+    pub async fn introspect(&self, credentials: ClientCredentials, data: TokenIntrospectionRequest) -> Result<HttpResponse, OAuthError> {
+        info!("[Service] Starting token introspection for client: {}", credentials.client_id);
+
+        let client = self.authenticate_client(&credentials).await?;
+
+        // Cada cliente só enxerga os próprios tokens; tokens de login e de outros clientes aparecem como inativos
+        let introspection = match self.oauth_repo.find_refresh_token(&sha256_hex(data.token.as_bytes())).await {
+            Ok(token) if token.client_id == client.client_id
+                && token.revoked_at.is_none()
+                && token.expires_at > chrono::Utc::now().naive_utc()
+                && self.is_session_valid(token.user_id, token.created_at.and_utc().timestamp()).await? => TokenIntrospection {
+                active: true,
+                client_id: Some(token.client_id),
+                sub: Some(token.user_id.to_string()),
+                scope: Some(token.scope),
+                exp: Some(token.expires_at.and_utc().timestamp() as usize),
+                token_type: Some("refresh_token".to_string()),
+            },
+            Ok(_) | Err(sqlx::Error::RowNotFound) => match self.token_generator.decode_token(&data.token, &self.config.jwt_secret) {
+                Ok(claims) if claims.client_id.as_deref() == Some(client.client_id.as_str()) => match claims.user_id() {
+                    Some(user_id) if self.is_session_valid(user_id, claims.iat as i64).await? => TokenIntrospection {
+                        active: true,
+                        client_id: claims.client_id,
+                        sub: Some(claims.id),
+                        scope: claims.scope,
+                        exp: Some(claims.exp),
+                        token_type: Some("access_token".to_string()),
+                    },
+                    _ => TokenIntrospection::default(),
+                },
+                _ => TokenIntrospection::default(),
+            },
+            Err(e) => return Err(e.into()),
+        };
+
+        Ok(HttpResponse::Ok()
+            .insert_header(("Cache-Control", "no-store"))
+            .json(introspection))
+    }
+
+    pub async fn revoke(&self, credentials: ClientCredentials, data: TokenIntrospectionRequest) -> Result<HttpResponse, OAuthError> {
+        info!("[Service] Starting token revocation for client: {}", credentials.client_id);
+
+        let client = self.authenticate_client(&credentials).await?;
+
+        // RFC 7009: tokens desconhecidos ou de outro cliente também respondem 200.
+        // Access tokens são JWT de vida curta e expiram sozinhos
+        if self.oauth_repo.revoke_refresh_token(&sha256_hex(data.token.as_bytes()), &client.client_id).await? {
+            info!("[Service] Refresh token revoked for client: {}", client.client_id);
+        }
+
+        Ok(HttpResponse::Ok().finish())
+    }
+
+    // Mesmas regras do AuthMiddleware: conta ativa e token emitido depois da última revogação das sessões
+    async fn is_session_valid(&self, user_id: Uuid, issued_at: i64) -> Result<bool, OAuthError> {
+        match self.user_repo.find_session_state(user_id).await? {
+            Some(state) => Ok(state.status == STATUS_ACTIVE
+                && state.sessions_revoked_at.is_none_or(|revoked_at| issued_at > revoked_at.and_utc().timestamp())),
+            None => Ok(false),
+        }
+    }
+
+    async fn validate_authorize_request(&self, data: &AuthorizeRequest) -> Result<OAuthClient, AppError> {
+        let client = match self.oauth_repo.find_client_by_client_id(&data.client_id).await {
+            Ok(client) => client,
+            Err(sqlx::Error::RowNotFound) => {
+                return Err(AppError::BadRequest(format!("Unknown client '{}'", data.client_id)));
+            }
+            Err(e) => return Err(e.into()),
+        };
+
+        if !client.redirect_uris.contains(&data.redirect_uri) {
+            return Err(AppError::BadRequest("redirect_uri is not registered for this client".into()));
+        }
+        if data.response_type != "code" {
+            return Err(AppError::BadRequest("response_type must be 'code'".into()));
+        }
+        if data.code_challenge_method != "S256" {
+            return Err(AppError::BadRequest("code_challenge_method must be 'S256'".into()));
+        }
+        if !(43..=128).contains(&data.code_challenge.len()) {
+            return Err(AppError::BadRequest("code_challenge must be a base64url encoded SHA-256 hash".into()));
+        }
+
+        Ok(client)
+    }
+
+    async fn authenticate_client(&self, credentials: &ClientCredentials) -> Result<OAuthClient, OAuthError> {
+        let client = match self.oauth_repo.find_client_by_client_id(&credentials.client_id).await {
+            Ok(client) => client,
+            Err(sqlx::Error::RowNotFound) => return Err(OAuthError::new("invalid_client", "Client authentication failed")),
+            Err(e) => return Err(e.into()),
+        };
+
+        // Clientes públicos não têm segredo e dependem apenas do PKCE
+        if let Some(hash) = &client.client_secret_hash {
+            let secret = credentials.client_secret.as_deref().unwrap_or_default();
+            let valid = self.password_encryptor.verify_password(hash, secret).map_err(|e| {
+                error!("[Service] Error verifying client secret: {:?}", e);
+                OAuthError::new("server_error", "Unexpected error while processing the request")
+            })?;
+            if !valid {
+                info!("[Service] Invalid secret for client: {}", client.client_id);
+                return Err(OAuthError::new("invalid_client", "Client authentication failed"));
+            }
+        }
+
+        Ok(client)
+    }
+
+    async fn exchange_authorization_code(&self, client: &OAuthClient, data: &TokenRequest) -> Result<(Uuid, String), OAuthError> {
+        let (code, redirect_uri, verifier) = match (&data.code, &data.redirect_uri, &data.code_verifier) {
+            (Some(code), Some(redirect_uri), Some(verifier)) => (code, redirect_uri, verifier),
+            _ => return Err(OAuthError::new("invalid_request", "code, redirect_uri and code_verifier are required")),
+        };
+
+        let authorization_code = match self.oauth_repo.take_authorization_code(&sha256_hex(code.as_bytes())).await {
+            Ok(authorization_code) => authorization_code,
+            Err(sqlx::Error::RowNotFound) => return Err(OAuthError::new("invalid_grant", "Authorization code is invalid or expired")),
+            Err(e) => return Err(e.into()),
+        };
+
+        if authorization_code.client_id != client.client_id || &authorization_code.redirect_uri != redirect_uri {
+            return Err(OAuthError::new("invalid_grant", "Authorization code was issued to another client or redirect_uri"));
+        }
+
+        if !constant_time_eq(sha256_base64url(verifier.as_bytes()).as_bytes(), authorization_code.code_challenge.as_bytes()) {
+            info!("[Service] PKCE verification failed for client: {}", client.client_id);
+            return Err(OAuthError::new("invalid_grant", "PKCE verification failed"));
+        }
+
+        Ok((authorization_code.user_id, authorization_code.scope))
+    }
+
+    async fn rotate_refresh_token(&self, client: &OAuthClient, data: &TokenRequest) -> Result<(Uuid, String), OAuthError> {
+        let refresh_token = data.refresh_token.as_deref()
+            .ok_or_else(|| OAuthError::new("invalid_request", "refresh_token is required"))?;
+        let token_hash = sha256_hex(refresh_token.as_bytes());
+
+        let token = match self.oauth_repo.find_refresh_token(&token_hash).await {
+            Ok(token) if token.client_id == client.client_id
+                && token.revoked_at.is_none()
+                && token.expires_at > chrono::Utc::now().naive_utc() => token,
+            Ok(_) | Err(sqlx::Error::RowNotFound) => return Err(OAuthError::new("invalid_grant", "Refresh token is invalid or expired")),
+            Err(e) => return Err(e.into()),
+        };
+
+        let scope = match &data.scope {
+            Some(requested) => {
+                let granted: Vec<&str> = token.scope.split_whitespace().collect();
+                if !requested.split_whitespace().all(|scope| granted.contains(&scope)) {
+                    return Err(OAuthError::new("invalid_scope", "Requested scope exceeds the original grant"));
+                }
+                requested.clone()
+            }
+            None => token.scope.clone(),
+        };
+
+        // Rotação: o refresh token usado deixa de valer imediatamente
+        if !self.oauth_repo.revoke_refresh_token(&token_hash, &client.client_id).await? {
+            return Err(OAuthError::new("invalid_grant", "Refresh token is invalid or expired"));
+        }
+
+        Ok((token.user_id, scope))
+    }
+
+    async fn issue_tokens(&self, client: &OAuthClient, user_id: Uuid, scope: String) -> Result<TokenResponse, OAuthError> {
+        let user = match self.user_repo.find_user_by_id(user_id).await {
+            Ok(user) => user,
+            Err(sqlx::Error::RowNotFound) => return Err(OAuthError::new("invalid_grant", "Resource owner no longer exists")),
+            Err(e) => return Err(e.into()),
+        };
+        if user.status != STATUS_ACTIVE {
+            info!("[Service] Refusing tokens for {} user ID: {}", user.status, user.id);
+            return Err(OAuthError::new("invalid_grant", "Account is not active"));
+        }
+
+        let claims = ClaimsToUserToken {
+            id: user.id.to_string(),
+            exp: unix_timestamp() + ACCESS_TOKEN_TTL_SECONDS as usize,
+            iat: unix_timestamp(),
+            full_name: user.full_name,
+            email: user.email,
+            // Tokens delegados a clientes OAuth nunca carregam privilégios administrativos
+            role: ROLE_USER.to_string(),
+            client_id: Some(client.client_id.clone()),
+            scope: Some(scope.clone()),
+            organization_id: None,
+            password_expired: false,
+        };
+        let access_token = self.token_generator
+            .generate_client_token(&claims, &self.config.jwt_secret)
+            .map_err(|e| {
+                error!("[Service] Error generating access token: {:?}", e);
+                OAuthError::new("server_error", "Unexpected error while processing the request")
+            })?;
+
+        let refresh_token = random_token();
+        self.oauth_repo
+            .create_refresh_token(&sha256_hex(refresh_token.as_bytes()), &client.client_id, user.id, &scope, REFRESH_TOKEN_TTL_SECONDS)
+            .await?;
+
+        Ok(TokenResponse {
+            access_token,
+            token_type: "Bearer".to_string(),
+            expires_in: ACCESS_TOKEN_TTL_SECONDS,
+            refresh_token,
+            scope,
+        })
+    }
+}
+
+fn is_valid_redirect_uri(uri: &str) -> bool {
+    let allowed_scheme = uri.starts_with("https://")
+        || uri.starts_with("http://localhost")
+        || uri.starts_with("http://127.0.0.1");
+    allowed_scheme && !uri.contains('#')
+}
+
+fn redirect_with(redirect_uri: &str, params: &[(&str, Option<&str>)]) -> Result<String, AppError> {
+    let params: Vec<(&str, &str)> = params.iter()
+        .filter_map(|(key, value)| value.map(|value| (*key, value)))
+        .collect();
+    let query = serde_urlencoded::to_string(params).map_err(|_| AppError::InternalServerError)?;
+    let separator = if redirect_uri.contains('?') { '&' } else { '?' };
+    Ok(format!("{}{}{}", redirect_uri, separator, query))
+}
@@ -0,0 +1,248 @@
+use std::sync::Arc;
+use actix_web::{web, HttpResponse};
+use log::{error, info};
+use uuid::Uuid;
+use crate::adapters::oidc_provider::{OidcIdentity, OidcProviderPort};
+use crate::adapters::password_hasher::PasswordEncryptorPort;
+use crate::core::contracts::repository::identities::IdentityRepository;
+use crate::core::contracts::repository::users::UserRepository;
+use crate::core::entities::audit::{
+    audit_diff, audit_entry, AuditTrail, NewAuditEntry, AUDIT_IDENTITY_UNLINKED, AUDIT_RESOURCE_IDENTITY, AUDIT_RESOURCE_USER,
+    AUDIT_USER_CREATED,
+};
+use crate::core::entities::identity::{CreateUserIdentity, OidcAuthorization, OidcCallback, OidcLoginState};
+use crate::core::entities::user::{CreateUser, UserDataCreated};
+use crate::repositories::identity_repository::PgIdentityRepository;
+use crate::repositories::user_repository::PgUserRepository;
+use crate::services::auth_service::AuthService;
+use crate::utils::crypto::{random_token, sha256_base64url, sha256_hex};
+use crate::utils::errors::AppError;
+use crate::utils::response::ApiResponse;
+use crate::utils::validations::normalize_email;
+
+const LOGIN_STATE_TTL_SECONDS: i64 = 600;
+
+pub struct OidcService {
+    identity_repo: web::Data<PgIdentityRepository>,
+    user_repo: web::Data<PgUserRepository>,
+    auth_service: web::Data<AuthService>,
+    password_encryptor: Arc<dyn PasswordEncryptorPort>,
+    provider: Option<Box<dyn OidcProviderPort>>,
+}
+
+impl OidcService {
+    pub fn new(
+        identity_repo: web::Data<PgIdentityRepository>,
+        user_repo: web::Data<PgUserRepository>,
+        auth_service: web::Data<AuthService>,
+        password_encryptor: Arc<dyn PasswordEncryptorPort>,
+        provider: Option<Box<dyn OidcProviderPort>>,
+    ) -> Self {
+        Self { identity_repo, user_repo, auth_service, password_encryptor, provider }
+    }
+
+    pub async fn start_login(&self) -> Result<HttpResponse, AppError> {
+        info!("[Service] Starting OIDC login");
+        let authorization_url = self.start_authorization(None).await?;
+        Ok(ApiResponse::success(OidcAuthorization { authorization_url }).into_response())
+    }
+
+    pub async fn start_link(&self, user_id: Uuid) -> Result<HttpResponse, AppError> {
+        info!("[Service] Starting OIDC identity link for user ID: {}", user_id);
+        let authorization_url = self.start_authorization(Some(user_id)).await?;
+        Ok(ApiResponse::success(OidcAuthorization { authorization_url }).into_response())
+    }
+
+    pub async fn callback(&self, data: OidcCallback) -> Result<HttpResponse, AppError> {
+        info!("[Service] Processing OIDC callback");
+        let provider = self.provider()?;
+
+        let state = match self.identity_repo.take_login_state(&sha256_hex(data.state.as_bytes())).await {
+            Ok(state) => state,
+            Err(sqlx::Error::RowNotFound) => {
+                info!("[Service] OIDC state not found or expired");
+                return Err(AppError::Unauthorized("Invalid or expired login state".into()));
+            }
+            Err(e) => {
+                error!("[Service] Database error while loading OIDC state: {:?}", e);
+                return Err(AppError::InternalServerError);
+            }
+        };
+
+        let id_token = provider.exchange_code(&data.code, &state.code_verifier).await.map_err(|e| {
+            info!("[Service] OIDC code exchange failed: {}", e);
+            AppError::Unauthorized("Identity provider rejected the authorization code".into())
+        })?;
+        let identity = provider.validate_id_token(&id_token, &state.nonce).await.map_err(|e| {
+            info!("[Service] OIDC id token rejected: {}", e);
+            AppError::Unauthorized("Invalid id token".into())
+        })?;
+        info!("[Service] OIDC id token validated for subject: {}", identity.subject);
+
+        match state.link_user_id {
+            Some(user_id) => self.link_identity(user_id, identity).await,
+            None => {
+                let user = self.resolve_user(identity).await?;
+                let user = self.user_repo.find_complete_user_by_id(user.id).await?;
+                self.auth_service.issue_login_response(user).await
+            }
+        }
+    }
+
+    pub async fn find_identities(&self, user_id: Uuid) -> Result<HttpResponse, AppError> {
+        info!("[Service] Starting find identities process for user ID: {}", user_id);
+
+        let identities = self.identity_repo.find_identities_by_user(user_id).await?;
+        info!("[Service] Found {} linked identities", identities.len());
+        Ok(ApiResponse::success(identities).into_response())
+    }
+
+    pub async fn unlink_identity(&self, user_id: Uuid, id: Uuid) -> Result<HttpResponse, AppError> {
+        info!("[Service] Starting unlink identity process for id: {}", id);
+
+        let audit = vec![NewAuditEntry {
+            action: AUDIT_IDENTITY_UNLINKED,
+            resource_type: AUDIT_RESOURCE_IDENTITY,
+            resource_id: Some(id),
+            changes: audit_diff(&serde_json::json!({ "id": id, "user_id": user_id }), &serde_json::Value::Null),
+        }];
+        match self.identity_repo.delete_identity(id, user_id, audit).await {
+            Ok(true) => {
+                info!("[Service] Identity {} unlinked for user ID: {}", id, user_id);
+                Ok(ApiResponse::<()>::deleted().into_response())
+            }
+            Ok(false) => Err(AppError::NotFound(format!("Identity with id '{}' not found", id))),
+            Err(e) => {
+                error!("[Service] Database error while unlinking identity: {:?}", e);
+                Err(AppError::InternalServerError)
+            }
+        }
+    }
+
+    fn provider(&self) -> Result<&dyn OidcProviderPort, AppError> {
+        self.provider.as_deref().ok_or_else(|| AppError::NotFound("OIDC login is not configured".into()))
+    }
+
+    async fn start_authorization(&self, link_user_id: Option<Uuid>) -> Result<String, AppError> {
+        let provider = self.provider()?;
+
+        let state = random_token();
+        let nonce = random_token();
+        let code_verifier = random_token();
+
+        self.identity_repo.create_login_state(OidcLoginState {
+            state_hash: sha256_hex(state.as_bytes()),
+            nonce: nonce.clone(),
+            code_verifier: code_verifier.clone(),
+            link_user_id,
+            expires_at: chrono::Utc::now().naive_utc() + chrono::Duration::seconds(LOGIN_STATE_TTL_SECONDS),
+        }).await?;
+
+        provider
+            .authorization_url(&state, &nonce, &sha256_base64url(code_verifier.as_bytes()))
+            .await
+            .map_err(|e| {
+                error!("[Service] Error building OIDC authorization url: {}", e);
+                AppError::InternalServerError
+            })
+    }
+
+    async fn link_identity(&self, user_id: Uuid, identity: OidcIdentity) -> Result<HttpResponse, AppError> {
+        let issuer = self.provider()?.issuer().to_string();
+
+        match self.identity_repo.find_identity_by_subject(&issuer, &identity.subject).await {
+            Ok(existing) if existing.user_id == user_id => {
+                return Ok(ApiResponse::success(existing).into_response());
+            }
+            Ok(_) => {
+                return Err(AppError::BadRequest("This identity is already linked to another account".into()));
+            }
+            Err(sqlx::Error::RowNotFound) => {}
+            Err(e) => return Err(e.into()),
+        }
+
+        let linked = self.identity_repo.create_identity(CreateUserIdentity {
+            user_id,
+            issuer,
+            subject: identity.subject,
+            email: identity.email,
+        }).await?;
+
+        info!("[Service] Identity linked successfully with ID: {}", linked.id);
+        Ok(ApiResponse::created(linked).into_response())
+    }
+
+    // Ordem de resolução: identidade já vinculada, email verificado, e por fim criação do usuário
+    async fn resolve_user(&self, identity: OidcIdentity) -> Result<UserDataCreated, AppError> {
+        let issuer = self.provider()?.issuer().to_string();
+
+        match self.identity_repo.find_identity_by_subject(&issuer, &identity.subject).await {
+            Ok(linked) => {
+                return match self.user_repo.find_user_by_id(linked.user_id).await {
+                    Ok(user) => Ok(user),
+                    Err(sqlx::Error::RowNotFound) => {
+                        info!("[Service] OIDC subject is linked to a deleted user: {}", linked.user_id);
+                        Err(AppError::Unauthorized("Account is not available".into()))
+                    }
+                    Err(e) => Err(e.into()),
+                };
+            }
+            Err(sqlx::Error::RowNotFound) => {}
+            Err(e) => return Err(e.into()),
+        }
+
+        let email = match (&identity.email, identity.email_verified) {
+            (Some(email), true) => normalize_email(email),
+            _ => {
+                info!("[Service] OIDC subject {} has no verified email", identity.subject);
+                return Err(AppError::Unauthorized("Identity provider did not supply a verified email".into()));
+            }
+        };
+
+        let user = match self.user_repo.find_user_by_email(email.clone()).await {
+            Ok(user) => {
+                info!("[Service] Linking OIDC subject to existing user ID: {}", user.id);
+                self.user_repo.find_user_by_id(user.id).await?
+            }
+            Err(sqlx::Error::RowNotFound) => {
+                // O email de uma conta excluída continua reservado até o expurgo
+                if self.user_repo.email_exists_for_other_user(&email, Uuid::nil()).await? {
+                    info!("[Service] Email belongs to a deleted user: {}", email);
+                    return Err(AppError::Unauthorized("Account is not available".into()));
+                }
+                info!("[Service] Creating user for OIDC subject with email: {}", email);
+                // Conta criada via SSO recebe uma senha aleatória que ninguém conhece
+                let encryptor = self.password_encryptor.clone();
+                let password = web::block(move || encryptor.hash_password(&random_token()))
+                    .await
+                    .map_err(|e| {
+                        error!("[Service] Error running password hashing: {:?}", e);
+                        AppError::InternalServerError
+                    })?
+                    .map_err(|e| {
+                        error!("[Service] Error hashing password: {:?}", e);
+                        AppError::InternalServerError
+                    })?;
+                let audit: AuditTrail<UserDataCreated> = Box::new(|user| {
+                    vec![audit_entry(AUDIT_USER_CREATED, AUDIT_RESOURCE_USER, user.id, None, Some(user))]
+                });
+                self.user_repo.create_user(CreateUser {
+                    full_name: identity.name.clone().unwrap_or_else(|| email.clone()),
+                    email: email.clone(),
+                    password,
+                    attributes: Default::default(),
+                }, audit).await?
+            }
+            Err(e) => return Err(e.into()),
+        };
+
+        self.identity_repo.create_identity(CreateUserIdentity {
+            user_id: user.id,
+            issuer,
+            subject: identity.subject,
+            email: Some(email),
+        }).await?;
+
+        Ok(user)
+    }
+}
@@ -0,0 +1,282 @@
+use actix_web::{web, HttpResponse};
+use log::{error, info};
+use uuid::Uuid;
+use crate::core::contracts::repository::organizations::OrganizationRepository;
+use crate::core::contracts::repository::users::UserRepository;
+use crate::core::entities::organization::{
+    is_valid_org_role, AcceptOrganizationInvitation, CreateOrganization, CreateOrganizationInvitation, MembershipChange,
+    OrganizationInvitationCreated, OrganizationMembership, UpdateMembership, UpdateOrganization, ORG_ROLE_ADMIN,
+    ORG_ROLE_MEMBER, ORG_ROLE_OWNER,
+};
+use crate::repositories::organization_repository::PgOrganizationRepository;
+use crate::repositories::user_repository::PgUserRepository;
+use crate::services::auth_service::AuthService;
+use crate::utils::crypto::{random_token, sha256_hex};
+use crate::utils::errors::{is_unique_violation, AppError};
+use crate::utils::response::ApiResponse;
+use crate::utils::validations::{is_valid_email, is_valid_slug, normalize_email, validate_required_fields};
+
+const INVITATION_TTL_DAYS: i64 = 7;
+const MANAGER_ROLES: [&str; 2] = [ORG_ROLE_OWNER, ORG_ROLE_ADMIN];
+
+pub struct OrganizationService {
+    organization_repo: web::Data<PgOrganizationRepository>,
+    user_repo: web::Data<PgUserRepository>,
+    auth_service: web::Data<AuthService>,
+}
+
+impl OrganizationService {
+    pub fn new(
+        organization_repo: web::Data<PgOrganizationRepository>,
+        user_repo: web::Data<PgUserRepository>,
+        auth_service: web::Data<AuthService>,
+    ) -> Self {
+        Self { organization_repo, user_repo, auth_service }
+    }
+
+    pub async fn create_organization(&self, user_id: Uuid, mut data: CreateOrganization) -> Result<HttpResponse, AppError> {
+        info!("[Service] Starting organization creation process with slug: {}", data.slug);
+
+        data.name = data.name.trim().to_string();
+        data.slug = data.slug.trim().to_string();
+        validate_required_fields(&[("name", data.name.is_empty()), ("slug", data.slug.is_empty())], "Error adding organization")?;
+        if !is_valid_slug(&data.slug) {
+            return Err(AppError::BadRequest(format!(
+                "Error adding organization: '{}' is not a valid slug (lowercase letters, digits and hyphens)", data.slug
+            )));
+        }
+
+        match self.organization_repo.create_organization(data, user_id).await {
+            Ok(organization) => {
+                info!("[Service] Organization created successfully with ID: {}", organization.id);
+                Ok(ApiResponse::created(organization).into_response())
+            }
+            Err(e) if is_unique_violation(&e) => {
+                info!("[Service] Organization slug already in use");
+                Err(AppError::Conflict("Error adding organization: slug already in use".into()))
+            }
+            Err(e) => {
+                error!("[Service] Error creating organization in database: {:?}", e);
+                Err(AppError::InternalServerError)
+            }
+        }
+    }
+
+    pub async fn find_user_organizations(&self, user_id: Uuid) -> Result<HttpResponse, AppError> {
+        info!("[Service] Starting find organizations process for user ID: {}", user_id);
+
+        let organizations = self.organization_repo.find_organizations_by_user(user_id).await?;
+        info!("[Service] Found {} organizations", organizations.len());
+        Ok(ApiResponse::success(organizations).into_response())
+    }
+
+    pub async fn find_organization(&self, id: Uuid, user_id: Uuid) -> Result<HttpResponse, AppError> {
+        info!("[Service] Starting find organization process for id: {}", id);
+
+        self.require_membership(id, user_id, None).await?;
+        let organization = self.organization_repo.find_organization_by_id(id).await?;
+        Ok(ApiResponse::success(organization).into_response())
+    }
+
+    pub async fn update_organization(&self, id: Uuid, user_id: Uuid, data: UpdateOrganization) -> Result<HttpResponse, AppError> {
+        info!("[Service] Starting update organization process for id: {}", id);
+
+        let name = data.name.trim().to_string();
+        validate_required_fields(&[("name", name.is_empty())], "Error updating organization")?;
+        self.require_membership(id, user_id, Some(&MANAGER_ROLES)).await?;
+
+        let organization = self.organization_repo.update_organization(id, name).await?;
+        info!("[Service] Organization updated successfully with ID: {}", organization.id);
+        Ok(ApiResponse::updated(organization).into_response())
+    }
+
+    pub async fn delete_organization(&self, id: Uuid, user_id: Uuid) -> Result<HttpResponse, AppError> {
+        info!("[Service] Starting delete organization process for id: {}", id);
+
+        self.require_membership(id, user_id, Some(&[ORG_ROLE_OWNER])).await?;
+        if !self.organization_repo.delete_organization(id).await? {
+            return Err(AppError::NotFound(format!("Organization with id '{}' not found", id)));
+        }
+        info!("[Service] Organization deleted successfully with ID: {}", id);
+        Ok(ApiResponse::<()>::deleted().into_response())
+    }
+
+    pub async fn find_members(&self, id: Uuid, user_id: Uuid) -> Result<HttpResponse, AppError> {
+        info!("[Service] Starting find members process for organization ID: {}", id);
+
+        self.require_membership(id, user_id, None).await?;
+        let members = self.organization_repo.find_members(id).await?;
+        info!("[Service] Found {} members", members.len());
+        Ok(ApiResponse::success(members).into_response())
+    }
+
+    pub async fn update_member_role(&self, id: Uuid, user_id: Uuid, member_id: Uuid, data: UpdateMembership) -> Result<HttpResponse, AppError> {
+        info!("[Service] Starting change member role process for user {} in organization {}", member_id, id);
+
+        if !is_valid_org_role(&data.role) {
+            return Err(AppError::BadRequest(format!("'{}' is not a valid organization role", data.role)));
+        }
+        let caller = self.require_membership(id, user_id, Some(&MANAGER_ROLES)).await?;
+
+        // Apenas proprietários concedem ou retiram o papel de proprietário
+        if caller.role != ORG_ROLE_OWNER {
+            let target = self.organization_repo.find_membership(id, member_id).await?;
+            if data.role == ORG_ROLE_OWNER || target.is_some_and(|target| target.role == ORG_ROLE_OWNER) {
+                return Err(AppError::Forbidden("Only owners can manage the owner role".into()));
+            }
+        }
+
+        match self.organization_repo.update_membership_role(id, member_id, &data.role).await? {
+            MembershipChange::Done(membership) => {
+                info!("[Service] Member {} is now {} in organization {}", member_id, membership.role, id);
+                Ok(ApiResponse::updated(membership).into_response())
+            }
+            MembershipChange::NotFound => Err(AppError::NotFound(format!("Member with id '{}' not found", member_id))),
+            MembershipChange::LastOwner => Err(AppError::BadRequest("The organization must keep at least one owner".into())),
+        }
+    }
+
+    pub async fn remove_member(&self, id: Uuid, user_id: Uuid, member_id: Uuid) -> Result<HttpResponse, AppError> {
+        info!("[Service] Starting remove member process for user {} in organization {}", member_id, id);
+
+        // Qualquer membro pode sair; remover outra pessoa exige gestão da organização
+        if member_id != user_id {
+            let caller = self.require_membership(id, user_id, Some(&MANAGER_ROLES)).await?;
+            if caller.role != ORG_ROLE_OWNER {
+                let target = self.organization_repo.find_membership(id, member_id).await?;
+                if target.is_some_and(|target| target.role == ORG_ROLE_OWNER) {
+                    return Err(AppError::Forbidden("Only owners can remove an owner".into()));
+                }
+            }
+        }
+
+        match self.organization_repo.delete_membership(id, member_id).await? {
+            MembershipChange::Done(()) => {
+                info!("[Service] Member {} removed from organization {}", member_id, id);
+                Ok(ApiResponse::<()>::deleted().into_response())
+            }
+            MembershipChange::NotFound => Err(AppError::NotFound(format!("Member with id '{}' not found", member_id))),
+            MembershipChange::LastOwner => Err(AppError::BadRequest("The organization must keep at least one owner".into())),
+        }
+    }
+
+    pub async fn create_invitation(&self, id: Uuid, user_id: Uuid, data: CreateOrganizationInvitation) -> Result<HttpResponse, AppError> {
+        info!("[Service] Starting create invitation process for organization ID: {}", id);
+
+        let email = normalize_email(&data.email);
+        if !is_valid_email(&email) {
+            return Err(AppError::BadRequest(format!("'{}' is not a valid email", email)));
+        }
+        let role = data.role.unwrap_or_else(|| ORG_ROLE_MEMBER.to_string());
+        if !is_valid_org_role(&role) {
+            return Err(AppError::BadRequest(format!("'{}' is not a valid organization role", role)));
+        }
+
+        let caller = self.require_membership(id, user_id, Some(&MANAGER_ROLES)).await?;
+        if role == ORG_ROLE_OWNER && caller.role != ORG_ROLE_OWNER {
+            return Err(AppError::Forbidden("Only owners can invite new owners".into()));
+        }
+
+        match self.user_repo.find_user_by_email(email.clone()).await {
+            Ok(user) if self.organization_repo.find_membership(id, user.id).await?.is_some() => {
+                return Err(AppError::Conflict(format!("'{}' is already a member of this organization", email)));
+            }
+            Ok(_) | Err(sqlx::Error::RowNotFound) => {}
+            Err(e) => return Err(e.into()),
+        }
+
+        let token = random_token();
+        let expires_at = chrono::Utc::now().naive_utc() + chrono::Duration::days(INVITATION_TTL_DAYS);
+        match self.organization_repo
+            .create_invitation(id, &email, &role, &sha256_hex(token.as_bytes()), user_id, expires_at)
+            .await
+        {
+            Ok(invitation) => {
+                info!("[Service] Invitation created successfully with ID: {}", invitation.id);
+                Ok(ApiResponse::created(OrganizationInvitationCreated { invitation, token }).into_response())
+            }
+            Err(e) if is_unique_violation(&e) => {
+                Err(AppError::Conflict(format!("There is already a pending invitation for '{}'", email)))
+            }
+            Err(e) => {
+                error!("[Service] Error creating invitation in database: {:?}", e);
+                Err(AppError::InternalServerError)
+            }
+        }
+    }
+
+    pub async fn find_invitations(&self, id: Uuid, user_id: Uuid) -> Result<HttpResponse, AppError> {
+        info!("[Service] Starting find invitations process for organization ID: {}", id);
+
+        self.require_membership(id, user_id, Some(&MANAGER_ROLES)).await?;
+        let invitations = self.organization_repo.find_pending_invitations(id).await?;
+        info!("[Service] Found {} pending invitations", invitations.len());
+        Ok(ApiResponse::success(invitations).into_response())
+    }
+
+    pub async fn revoke_invitation(&self, id: Uuid, user_id: Uuid, invitation_id: Uuid) -> Result<HttpResponse, AppError> {
+        info!("[Service] Starting revoke invitation process for id: {}", invitation_id);
+
+        self.require_membership(id, user_id, Some(&MANAGER_ROLES)).await?;
+        if !self.organization_repo.delete_invitation(invitation_id, id).await? {
+            return Err(AppError::NotFound(format!("Invitation with id '{}' not found", invitation_id)));
+        }
+        info!("[Service] Invitation revoked successfully with ID: {}", invitation_id);
+        Ok(ApiResponse::<()>::deleted().into_response())
+    }
+
+    pub async fn accept_invitation(&self, user_id: Uuid, data: AcceptOrganizationInvitation) -> Result<HttpResponse, AppError> {
+        info!("[Service] Starting accept invitation process for user ID: {}", user_id);
+
+        let invitation = match self.organization_repo.find_invitation_by_token(&sha256_hex(data.token.as_bytes())).await? {
+            Some(invitation) if invitation.accepted_at.is_none() => invitation,
+            _ => return Err(AppError::NotFound("Invitation not found".into())),
+        };
+        if invitation.expires_at < chrono::Utc::now().naive_utc() {
+            info!("[Service] Invitation {} has expired", invitation.id);
+            return Err(AppError::BadRequest("Invitation has expired".into()));
+        }
+
+        // O convite vale apenas para a conta do email convidado
+        let user = self.user_repo.find_user_by_id(user_id).await?;
+        if normalize_email(&user.email) != normalize_email(&invitation.email) {
+            info!("[Service] Invitation {} belongs to a different email", invitation.id);
+            return Err(AppError::Forbidden("This invitation was sent to a different email".into()));
+        }
+
+        match self.organization_repo.accept_invitation(&invitation, user_id).await {
+            Ok(membership) => {
+                info!("[Service] User {} joined organization {}", user_id, membership.organization_id);
+                Ok(ApiResponse::created(membership).into_response())
+            }
+            Err(sqlx::Error::RowNotFound) => Err(AppError::NotFound("Invitation not found".into())),
+            Err(e) if is_unique_violation(&e) => {
+                Err(AppError::Conflict("You are already a member of this organization".into()))
+            }
+            Err(e) => {
+                error!("[Service] Error accepting invitation in database: {:?}", e);
+                Err(AppError::InternalServerError)
+            }
+        }
+    }
+
+    // Troca a organização ativa emitindo um novo token com a claim correspondente
+    pub async fn switch_organization(&self, id: Uuid, user_id: Uuid) -> Result<HttpResponse, AppError> {
+        info!("[Service] Starting switch organization process to {} for user ID: {}", id, user_id);
+
+        self.require_membership(id, user_id, None).await?;
+        let user = self.user_repo.find_user_by_id(user_id).await?;
+        self.auth_service.issue_token(user.id, user.full_name, user.email, user.role, Some(id))
+    }
+
+    // Quem não é membro recebe 404, sem revelar que a organização existe
+    async fn require_membership(&self, id: Uuid, user_id: Uuid, roles: Option<&[&str]>) -> Result<OrganizationMembership, AppError> {
+        let Some(membership) = self.organization_repo.find_membership(id, user_id).await? else {
+            return Err(AppError::NotFound(format!("Organization with id '{}' not found", id)));
+        };
+        if roles.is_some_and(|roles| !roles.contains(&membership.role.as_str())) {
+            return Err(AppError::Forbidden("You do not have permission to perform this action in this organization".into()));
+        }
+        Ok(membership)
+    }
+}
@@ -0,0 +1,92 @@
+use std::sync::Arc;
+use actix_web::{http::header, web, HttpResponse};
+use log::{error, info};
+use uuid::Uuid;
+use crate::adapters::password_hasher::PasswordEncryptorPort;
+use crate::core::contracts::repository::privacy::PrivacyRepository;
+use crate::core::entities::audit::{audit_redacted_changes, AuditTrail, NewAuditEntry, AUDIT_RESOURCE_USER, AUDIT_USER_ERASED};
+use crate::core::entities::privacy::UserErasureReport;
+use crate::repositories::privacy_repository::PgPrivacyRepository;
+use crate::services::avatar_service::AvatarService;
+use crate::utils::crypto::random_token;
+use crate::utils::errors::AppError;
+use crate::utils::response::ApiResponse;
+
+pub struct PrivacyService {
+    privacy_repo: web::Data<PgPrivacyRepository>,
+    avatar_service: web::Data<AvatarService>,
+    password_encryptor: Arc<dyn PasswordEncryptorPort>,
+}
+
+impl PrivacyService {
+    pub fn new(
+        privacy_repo: web::Data<PgPrivacyRepository>,
+        avatar_service: web::Data<AvatarService>,
+        password_encryptor: Arc<dyn PasswordEncryptorPort>,
+    ) -> Self {
+        Self { privacy_repo, avatar_service, password_encryptor }
+    }
+
+    pub async fn export_user_data(&self, id: Uuid) -> Result<HttpResponse, AppError> {
+        info!("[Service] Starting data export process for user ID: {}", id);
+
+        let Some(data) = self.privacy_repo.find_user_data(id).await? else {
+            info!("[Service] User with id {} not found", id);
+            return Err(AppError::NotFound(format!("User with id '{}' not found", id)));
+        };
+        info!("[Service] Data export generated successfully for user ID: {}", id);
+
+        Ok(HttpResponse::Ok()
+            .insert_header((header::CONTENT_DISPOSITION, format!("attachment; filename=\"user-{}-data.json\"", id)))
+            .json(data))
+    }
+
+    // Anonimiza os dados pessoais no próprio registro: ids, vínculos e histórico continuam válidos
+    pub async fn erase_user(&self, id: Uuid, erased_by: Uuid) -> Result<HttpResponse, AppError> {
+        info!("[Service] Starting erasure process for user ID: {}", id);
+
+        let Some(profile) = self.privacy_repo.find_profile(id).await? else {
+            info!("[Service] User with id {} not found", id);
+            return Err(AppError::NotFound(format!("User with id '{}' not found", id)));
+        };
+        if profile.erased_at.is_some() {
+            return Err(AppError::Conflict(format!("Personal data of user '{}' was already erased", id)));
+        }
+
+        // Senha aleatória descartada: a conta não volta a aceitar login por senha
+        let encryptor = self.password_encryptor.clone();
+        let password_hash = web::block(move || encryptor.hash_password(&random_token()))
+            .await
+            .map_err(|e| {
+                error!("[Service] Error running password hashing: {:?}", e);
+                AppError::InternalServerError
+            })?
+            .map_err(|e| {
+                error!("[Service] Error hashing password: {:?}", e);
+                AppError::InternalServerError
+            })?;
+
+        // Só os nomes dos campos: a trilha não pode guardar os dados que acabaram de ser apagados
+        let audit: AuditTrail<UserErasureReport> = Box::new(move |report| {
+            vec![NewAuditEntry {
+                action: AUDIT_USER_ERASED,
+                resource_type: AUDIT_RESOURCE_USER,
+                resource_id: Some(id),
+                changes: audit_redacted_changes(&report.changed_fields),
+            }]
+        });
+        let Some(report) = self.privacy_repo.erase_user(id, &password_hash, erased_by, audit).await? else {
+            return Err(AppError::Conflict(format!("Personal data of user '{}' was already erased", id)));
+        };
+
+        if report.changed_fields.contains(&"avatar") {
+            // Os dados já foram anonimizados; uma falha aqui só deixa arquivos órfãos no storage
+            if let Err(e) = self.avatar_service.delete_avatar_files(id).await {
+                error!("[Service] Avatar files of erased user {} could not be deleted: {:?}", id, e);
+            }
+        }
+
+        info!("[Service] Personal data of user {} erased with {} changes", id, report.changes.len());
+        Ok(ApiResponse::success(report).into_response())
+    }
+}
@@ -0,0 +1,121 @@
+use std::collections::HashSet;
+use actix_web::{web, HttpResponse};
+use log::{error, info};
+use crate::core::contracts::repository::user_attributes::UserAttributeRepository;
+use crate::core::entities::audit::{audit_entry, AuditTrail, AUDIT_RESOURCE_USER, AUDIT_USER_UPDATED};
+use crate::core::entities::user_attribute::{
+    is_valid_attribute_type, CreateUserAttributeDefinition, RemovedUserAttribute, UpdateUserAttributeDefinition,
+    ATTRIBUTE_TYPE_ENUM,
+};
+use crate::repositories::user_attribute_repository::PgUserAttributeRepository;
+use crate::utils::errors::{is_unique_violation, AppError};
+use crate::utils::response::ApiResponse;
+use crate::utils::validations::{is_valid_attribute_key, validate_required_fields};
+
+pub struct UserAttributeService {
+    attribute_repo: web::Data<PgUserAttributeRepository>,
+}
+
+impl UserAttributeService {
+    pub fn new(attribute_repo: web::Data<PgUserAttributeRepository>) -> Self {
+        Self { attribute_repo }
+    }
+
+    pub async fn create_definition(&self, mut data: CreateUserAttributeDefinition) -> Result<HttpResponse, AppError> {
+        info!("[Service] Starting user attribute creation process for key: {}", data.key);
+
+        data.key = data.key.trim().to_string();
+        if !is_valid_attribute_key(&data.key) {
+            return Err(AppError::BadRequest(format!(
+                "Error adding attribute: '{}' is not a valid key (lowercase letters, digits and underscores, starting with a letter)", data.key
+            )));
+        }
+        if !is_valid_attribute_type(&data.attribute_type) {
+            return Err(AppError::BadRequest(format!(
+                "Error adding attribute: '{}' is not a valid type (string, number, boolean or enum)", data.attribute_type
+            )));
+        }
+        data.label = data.label.map(|label| label.trim().to_string()).filter(|label| !label.is_empty());
+        data.allowed_values = allowed_values(&data.attribute_type, data.allowed_values, "Error adding attribute")?;
+
+        match self.attribute_repo.create_definition(data).await {
+            Ok(definition) => {
+                info!("[Service] User attribute created successfully with key: {}", definition.key);
+                Ok(ApiResponse::created(definition).into_response())
+            }
+            Err(e) if is_unique_violation(&e) => {
+                info!("[Service] User attribute key already in use");
+                Err(AppError::Conflict("Error adding attribute: key already in use".into()))
+            }
+            Err(e) => {
+                error!("[Service] Error creating user attribute in database: {:?}", e);
+                Err(AppError::InternalServerError)
+            }
+        }
+    }
+
+    pub async fn find_definitions(&self) -> Result<HttpResponse, AppError> {
+        info!("[Service] Starting find user attributes process");
+
+        let definitions = self.attribute_repo.find_definitions().await?;
+        info!("[Service] Found {} user attributes", definitions.len());
+        Ok(ApiResponse::success(definitions).into_response())
+    }
+
+    pub async fn update_definition(&self, key: String, mut data: UpdateUserAttributeDefinition) -> Result<HttpResponse, AppError> {
+        info!("[Service] Starting update user attribute process for key: {}", key);
+
+        data.label = data.label.trim().to_string();
+        validate_required_fields(&[("label", data.label.is_empty())], "Error updating attribute")?;
+
+        let Some(current) = self.attribute_repo.find_definition(&key).await? else {
+            return Err(AppError::NotFound(format!("Attribute '{}' not found", key)));
+        };
+        data.allowed_values = allowed_values(&current.attribute_type, data.allowed_values, "Error updating attribute")?;
+
+        let definition = self.attribute_repo.update_definition(&key, data).await?;
+        info!("[Service] User attribute updated successfully with key: {}", definition.key);
+        Ok(ApiResponse::updated(definition).into_response())
+    }
+
+    pub async fn delete_definition(&self, key: String) -> Result<HttpResponse, AppError> {
+        info!("[Service] Starting delete user attribute process for key: {}", key);
+
+        // Cada usuário que tinha o atributo recebe uma entrada de atualização
+        let audit: AuditTrail<Vec<RemovedUserAttribute>> = Box::new(|removed| {
+            removed
+                .iter()
+                .map(|user| {
+                    let before = serde_json::json!({ "attributes": user.before });
+                    let after = serde_json::json!({ "attributes": user.after });
+                    audit_entry(AUDIT_USER_UPDATED, AUDIT_RESOURCE_USER, user.user_id, Some(&before), Some(&after))
+                })
+                .collect()
+        });
+        if !self.attribute_repo.delete_definition(&key, audit).await? {
+            return Err(AppError::NotFound(format!("Attribute '{}' not found", key)));
+        }
+        info!("[Service] User attribute deleted successfully with key: {}", key);
+        Ok(ApiResponse::<()>::deleted().into_response())
+    }
+}
+
+// Apenas atributos `enum` têm lista de valores, que não pode ser vazia nem repetir valores
+fn allowed_values(attribute_type: &str, values: Option<Vec<String>>, error_context: &str) -> Result<Option<Vec<String>>, AppError> {
+    if attribute_type != ATTRIBUTE_TYPE_ENUM {
+        return match values {
+            Some(_) => Err(AppError::BadRequest(format!("{}: allowed_values is only accepted for enum attributes", error_context))),
+            None => Ok(None),
+        };
+    }
+
+    let values: Vec<String> = values.unwrap_or_default().into_iter().map(|value| value.trim().to_string()).collect();
+    if values.is_empty() || values.iter().any(|value| value.is_empty()) {
+        return Err(AppError::BadRequest(format!("{}: enum attributes need a list of non-empty allowed_values", error_context)));
+    }
+    let mut seen = HashSet::new();
+    if let Some(duplicate) = values.iter().find(|value| !seen.insert(value.as_str())) {
+        return Err(AppError::BadRequest(format!("{}: '{}' appears more than once in allowed_values", error_context, duplicate)));
+    }
+    Ok(Some(values))
+}
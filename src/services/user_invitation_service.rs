@@ -0,0 +1,223 @@
+use std::sync::Arc;
+use actix_web::{web, HttpResponse};
+use log::{error, info};
+use uuid::Uuid;
+use crate::adapters::mailer::{EmailMessage, MailerPort};
+use crate::config::config_env::Config;
+use crate::core::contracts::repository::user_invitations::UserInvitationRepository;
+use crate::core::contracts::repository::users::UserRepository;
+use crate::core::entities::organization::is_valid_org_role;
+use crate::core::entities::audit::{audit_entry, AuditTrail, AUDIT_RESOURCE_USER, AUDIT_USER_ROLE_CHANGED};
+use crate::core::entities::user::{is_valid_role, CreateUser, UserDataCreated, ROLE_USER};
+use crate::core::entities::user_invitation::{
+    AcceptUserInvitation, CreateUserInvitation, UserInvitation, UserInvitationResponse, INVITATION_STATUS_EXPIRED,
+    INVITATION_STATUS_PENDING,
+};
+use crate::repositories::user_invitation_repository::PgUserInvitationRepository;
+use crate::repositories::user_repository::PgUserRepository;
+use crate::services::user_service::UserService;
+use crate::utils::crypto::{constant_time_eq, hmac_sha256_hex};
+use crate::utils::errors::{is_unique_violation, AppError};
+use crate::utils::response::ApiResponse;
+use crate::utils::validations::{is_valid_email, normalize_email};
+
+pub struct UserInvitationService {
+    invitation_repo: web::Data<PgUserInvitationRepository>,
+    user_repo: web::Data<PgUserRepository>,
+    user_service: web::Data<UserService>,
+    mailer: Arc<dyn MailerPort>,
+    config: web::Data<Config>,
+}
+
+impl UserInvitationService {
+    pub fn new(
+        invitation_repo: web::Data<PgUserInvitationRepository>,
+        user_repo: web::Data<PgUserRepository>,
+        user_service: web::Data<UserService>,
+        mailer: Arc<dyn MailerPort>,
+        config: web::Data<Config>,
+    ) -> Self {
+        Self { invitation_repo, user_repo, user_service, mailer, config }
+    }
+
+    pub async fn create_invitation(&self, invited_by: Uuid, organization_id: Option<Uuid>, data: CreateUserInvitation) -> Result<HttpResponse, AppError> {
+        let email = normalize_email(&data.email);
+        info!("[Service] Starting user invitation process for email: {}", email);
+
+        if !is_valid_email(&email) {
+            return Err(AppError::BadRequest(format!("Error inviting user: '{}' is not a valid email", email)));
+        }
+        let role = data.role.unwrap_or_else(|| ROLE_USER.to_string());
+        if !is_valid_role(&role) {
+            return Err(AppError::BadRequest(format!("Error inviting user: '{}' is not a valid role", role)));
+        }
+        if let Some(organization_role) = &data.organization_role {
+            if !is_valid_org_role(organization_role) {
+                return Err(AppError::BadRequest(format!("Error inviting user: '{}' is not a valid organization role", organization_role)));
+            }
+            if organization_id.is_none() {
+                return Err(AppError::BadRequest("Error inviting user: organization_role requires an active organization".into()));
+            }
+        }
+
+        if self.user_repo.email_exists_for_other_user(&email, Uuid::nil()).await? {
+            info!("[Service] User already exists with email: {}", email);
+            return Err(AppError::Conflict(format!("Error inviting user: email '{}' already exists", email)));
+        }
+
+        let expires_at = chrono::Utc::now().naive_utc() + chrono::Duration::hours(self.config.invitation_ttl_hours);
+        let invitation = match self.invitation_repo
+            .create_invitation(&email, &role, organization_id, data.organization_role, invited_by, expires_at)
+            .await
+        {
+            Ok(invitation) => invitation,
+            Err(e) if is_unique_violation(&e) => {
+                info!("[Service] A pending invitation already exists for email: {}", email);
+                return Err(AppError::Conflict(format!("Error inviting user: a pending invitation already exists for '{}'", email)));
+            }
+            Err(e) => {
+                error!("[Service] Error creating user invitation in database: {:?}", e);
+                return Err(AppError::InternalServerError);
+            }
+        };
+        info!("[Service] User invitation created successfully with ID: {}", invitation.id);
+
+        let email_sent = self.deliver(&invitation).await;
+        Ok(ApiResponse::created(invitation_response(invitation, Some(email_sent))).into_response())
+    }
+
+    pub async fn find_invitations(&self) -> Result<HttpResponse, AppError> {
+        info!("[Service] Starting find user invitations process");
+
+        let invitations = self.invitation_repo.find_invitations().await?;
+        info!("[Service] Found {} user invitations", invitations.len());
+        let invitations: Vec<UserInvitationResponse> = invitations
+            .into_iter()
+            .map(|invitation| invitation_response(invitation, None))
+            .collect();
+        Ok(ApiResponse::success(invitations).into_response())
+    }
+
+    // Reenvia com novo prazo; o link anterior deixa de valer
+    pub async fn resend_invitation(&self, id: Uuid) -> Result<HttpResponse, AppError> {
+        info!("[Service] Starting resend user invitation process for id: {}", id);
+
+        self.find_open_invitation(id).await?;
+        let expires_at = chrono::Utc::now().naive_utc() + chrono::Duration::hours(self.config.invitation_ttl_hours);
+        let Some(invitation) = self.invitation_repo.resend_invitation(id, expires_at).await? else {
+            return Err(AppError::BadRequest("Invitation was already accepted or revoked".into()));
+        };
+
+        let email_sent = self.deliver(&invitation).await;
+        Ok(ApiResponse::updated(invitation_response(invitation, Some(email_sent))).into_response())
+    }
+
+    pub async fn revoke_invitation(&self, id: Uuid) -> Result<HttpResponse, AppError> {
+        info!("[Service] Starting revoke user invitation process for id: {}", id);
+
+        self.find_open_invitation(id).await?;
+        let Some(invitation) = self.invitation_repo.revoke_invitation(id).await? else {
+            return Err(AppError::BadRequest("Invitation was already accepted or revoked".into()));
+        };
+        info!("[Service] User invitation {} revoked", id);
+        Ok(ApiResponse::updated(invitation_response(invitation, None)).into_response())
+    }
+
+    pub async fn accept_invitation(&self, data: AcceptUserInvitation) -> Result<HttpResponse, AppError> {
+        info!("[Service] Starting accept user invitation process");
+
+        // Token e convite inválidos recebem a mesma resposta
+        let not_found = || AppError::NotFound("Invitation not found".into());
+        let (id, signature) = data.token.split_once('.').ok_or_else(not_found)?;
+        let id = Uuid::parse_str(id).map_err(|_| not_found())?;
+        let invitation = self.invitation_repo.find_invitation_by_id(id).await?.ok_or_else(not_found)?;
+        if !constant_time_eq(self.sign(invitation.id, invitation.send_count).as_bytes(), signature.as_bytes()) {
+            info!("[Service] Invalid signature for invitation {}", id);
+            return Err(not_found());
+        }
+        match invitation.status(chrono::Utc::now().naive_utc()) {
+            INVITATION_STATUS_PENDING => {}
+            INVITATION_STATUS_EXPIRED => {
+                info!("[Service] Invitation {} has expired", id);
+                return Err(AppError::BadRequest("Invitation has expired".into()));
+            }
+            _ => return Err(not_found()),
+        }
+
+        // Reservado antes de criar a conta: aceite concorrente, reenvio ou revogação não criam outra conta
+        if !self.invitation_repo.claim_invitation(&invitation).await? {
+            return Err(not_found());
+        }
+
+        let created = self.user_service.register_user(CreateUser {
+            full_name: data.full_name,
+            email: invitation.email.clone(),
+            password: data.password,
+            attributes: data.attributes,
+        }).await;
+        let user = match created {
+            Ok(user) => user,
+            Err(e) => {
+                info!("[Service] User creation for invitation {} failed, releasing it", id);
+                self.invitation_repo.release_invitation(id).await?;
+                return Err(e);
+            }
+        };
+
+        let before = user.clone();
+        let audit: AuditTrail<UserDataCreated> = Box::new(move |user| {
+            vec![audit_entry(AUDIT_USER_ROLE_CHANGED, AUDIT_RESOURCE_USER, user.id, Some(&before), Some(user))]
+        });
+        self.invitation_repo.complete_invitation(&invitation, user.id, audit).await?;
+        info!("[Service] Invitation {} accepted by user {}", id, user.id);
+
+        let user = self.user_repo.find_user_by_id(user.id).await?;
+        Ok(ApiResponse::created(user).into_response())
+    }
+
+    async fn find_open_invitation(&self, id: Uuid) -> Result<UserInvitation, AppError> {
+        let Some(invitation) = self.invitation_repo.find_invitation_by_id(id).await? else {
+            return Err(AppError::NotFound(format!("Invitation with id '{}' not found", id)));
+        };
+        if invitation.accepted_at.is_some() || invitation.revoked_at.is_some() {
+            return Err(AppError::BadRequest("Invitation was already accepted or revoked".into()));
+        }
+        Ok(invitation)
+    }
+
+    // Assinatura ligada ao envio: cada reenvio gera um token novo
+    fn sign(&self, id: Uuid, send_count: i32) -> String {
+        hmac_sha256_hex(self.config.jwt_secret.as_bytes(), format!("invitation:{}:{}", id, send_count).as_bytes())
+    }
+
+    // Falha no envio não desfaz o convite; o administrador pode reenviar
+    async fn deliver(&self, invitation: &UserInvitation) -> bool {
+        let token = format!("{}.{}", invitation.id, self.sign(invitation.id, invitation.send_count));
+        let message = EmailMessage {
+            to: invitation.email.clone(),
+            subject: "You have been invited".to_string(),
+            body: format!(
+                "You have been invited to create an account.\n\nAccept the invitation: {}?token={}\n\nThis link expires at {} UTC.",
+                self.config.invitation_accept_url,
+                token,
+                invitation.expires_at.format("%Y-%m-%d %H:%M"),
+            ),
+        };
+
+        match self.mailer.send(message).await {
+            Ok(()) => {
+                info!("[Service] Invitation email sent for invitation {}", invitation.id);
+                true
+            }
+            Err(e) => {
+                error!("[Service] Error sending invitation email for invitation {}: {:?}", invitation.id, e);
+                false
+            }
+        }
+    }
+}
+
+fn invitation_response(invitation: UserInvitation, email_sent: Option<bool>) -> UserInvitationResponse {
+    let status = invitation.status(chrono::Utc::now().naive_utc());
+    UserInvitationResponse { invitation, status, email_sent }
+}
@@ -1,30 +1,142 @@
-use actix_web::{web, HttpResponse};
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use actix_web::{http::{header, StatusCode}, web, HttpResponse};
+use futures::{stream, StreamExt};
 use log::{error, info};
 use uuid::Uuid;
+use crate::core::entities::audit::{
+    audit_entry, audit_redacted_changes, AuditTrail, NewAuditEntry, AUDIT_RESOURCE_USER, AUDIT_USER_CREATED, AUDIT_USER_DELETED,
+    AUDIT_USER_PASSWORD_CHANGED, AUDIT_USER_RESTORED, AUDIT_USER_ROLE_CHANGED, AUDIT_USER_STATUS_CHANGED, AUDIT_USER_UPDATED,
+};
+use crate::core::entities::export_job::ExportFilters;
+use crate::core::entities::patch::Patch;
+use crate::core::entities::user_attribute::{attribute_errors, is_blank_attribute, UserAttributeDefinition, UserAttributes, ATTRIBUTE_TYPE_BOOLEAN, ATTRIBUTE_TYPE_NUMBER};
 use crate::core::entities::user::{
+    can_change_status,
+    is_valid_role,
+    ChangeUserRole,
+    ChangeUserStatus,
     CreateUser,
+    PatchUser,
+    SortDirection,
+    UpdatePasswordUser,
     UpdateUser,
+    UserCursor,
+    UserDataCreated,
+    UserExportQuery,
+    UserImportFormat,
+    UserImportMode,
+    UserImportQuery,
+    UserImportReport,
+    UserImportRow,
+    UserImportRowResult,
+    UserImportRowStatus,
+    UserListParams,
+    UserListQuery,
+    UserSearchHighlights,
+    UserSearchHit,
+    UserSearchQuery,
+    UserSortField,
 };
-use crate::adapters::password_hasher::PasswordEncryptorPort;
+use crate::adapters::password_hasher::{matches_any, PasswordEncryptorPort};
+use crate::core::contracts::repository::user_attributes::UserAttributeRepository;
 use crate::core::contracts::repository::users::UserRepository;
+use crate::repositories::user_attribute_repository::PgUserAttributeRepository;
 use crate::repositories::user_repository::PgUserRepository;
+use crate::services::email_change_service::EmailChangeService;
 use crate::utils::errors::AppError;
+use crate::utils::etag::{etag, IfMatch};
+use crate::utils::export::{export_header, export_row};
+use crate::utils::pagination::{decode_cursor, encode_cursor, page_size};
 use crate::utils::response::ApiResponse;
-use crate::utils::validations::{is_valid_email, validate_required_fields};
+use crate::utils::errors::is_unique_violation;
+use crate::utils::validations::{is_valid_email, normalize_email, validate_required_fields};
 use crate::utils::{errors, response};
 
 
 pub struct UserService {
     user_repo: web::Data<PgUserRepository>,
-    password_encryptor: Box<dyn PasswordEncryptorPort>,
+    attribute_repo: web::Data<PgUserAttributeRepository>,
+    password_encryptor: Arc<dyn PasswordEncryptorPort>,
+    email_change_service: web::Data<EmailChangeService>,
+    require_if_match: bool,
+    // Senhas recentes, incluindo a atual, que não podem ser reutilizadas; 0 desativa a regra
+    password_history_size: i64,
 }
 
 impl UserService {
     pub fn new(
         user_repo: web::Data<PgUserRepository>,
-        password_encryptor: Box<dyn PasswordEncryptorPort>,
+        attribute_repo: web::Data<PgUserAttributeRepository>,
+        password_encryptor: Arc<dyn PasswordEncryptorPort>,
+        email_change_service: web::Data<EmailChangeService>,
+        require_if_match: bool,
+        password_history_size: i64,
     ) -> Self {
-        Self { user_repo, password_encryptor }
+        Self { user_repo, attribute_repo, password_encryptor, email_change_service, require_if_match, password_history_size }
+    }
+
+    // Quantidade de senhas anteriores guardadas no histórico; a atual fica na tabela de usuários
+    pub fn kept_password_history(&self) -> i64 {
+        (self.password_history_size - 1).max(0)
+    }
+
+    // Versões aceitas pelo If-Match; None quando qualquer versão serve
+    fn expected_versions(&self, if_match: IfMatch) -> Result<Option<Vec<i64>>, AppError> {
+        match if_match {
+            IfMatch::Missing if self.require_if_match => {
+                Err(AppError::PreconditionRequired("If-Match header is required".into()))
+            }
+            IfMatch::Missing | IfMatch::Any => Ok(None),
+            IfMatch::Versions(versions) => Ok(Some(versions)),
+        }
+    }
+
+    // A escrita condicional não afetou nenhuma linha: ou o usuário não existe, ou a versão mudou
+    async fn precondition_or_not_found(&self, id: Uuid) -> AppError {
+        match self.user_repo.find_user_by_id(id).await {
+            Ok(user) => {
+                info!("[Service] Version mismatch for user {}, current version is {}", id, user.version);
+                AppError::PreconditionFailed(format!("User with id '{}' was modified by another request", id))
+            }
+            Err(sqlx::Error::RowNotFound) => AppError::NotFound(format!("User with id '{}' not found", id)),
+            Err(e) => {
+                error!("[Service] Database error while finding user: {:?}", e);
+                AppError::InternalServerError
+            }
+        }
+    }
+
+    // Estado anterior à alteração, usado na troca de email e na trilha de auditoria
+    async fn existing_user(&self, id: Uuid) -> Result<UserDataCreated, AppError> {
+        match self.user_repo.find_user_by_id(id).await {
+            Ok(user) => Ok(user),
+            Err(sqlx::Error::RowNotFound) => Err(AppError::NotFound(format!("User with id '{}' not found", id))),
+            Err(e) => {
+                error!("[Service] Database error while finding user: {:?}", e);
+                Err(AppError::InternalServerError)
+            }
+        }
+    }
+
+    async fn current_user(&self, id: Uuid, expected_versions: Option<Vec<i64>>) -> Result<UserDataCreated, AppError> {
+        let user = self.user_repo.find_user_by_id(id).await?;
+        if expected_versions.is_some_and(|versions| !versions.contains(&user.version)) {
+            return Err(AppError::PreconditionFailed(format!("User with id '{}' was modified by another request", id)));
+        }
+        Ok(user)
+    }
+
+    // O novo email só vale após a confirmação; até lá a resposta mantém o endereço atual
+    async fn updated_response(&self, user: UserDataCreated, requested_email: Option<String>) -> Result<HttpResponse, AppError> {
+        let tag = etag(user.version);
+        let Some(new_email) = requested_email else {
+            return Ok(ApiResponse::updated(user).with_etag(tag).into_response());
+        };
+
+        self.email_change_service.request_change(&user, &new_email).await?;
+        let message = format!("Resource updated successfully; '{}' must be confirmed before the email changes", new_email);
+        Ok(ApiResponse::new(message, StatusCode::OK, Some(user)).with_etag(tag).into_response())
     }
 
     // Private validation helper function
@@ -54,15 +166,41 @@ impl UserService {
         Ok(())
     }
 
+    // Valida os atributos contra o esquema atual; fora do PATCH, valores vazios são descartados
+    async fn validate_attributes(&self, attributes: &mut UserAttributes, partial: bool, error_context: &str) -> Result<(), AppError> {
+        info!("[Service] Validating custom attributes");
+        let definitions = self.attribute_repo.find_definitions().await?;
+        if !partial {
+            attributes.retain(|_, value| !is_blank_attribute(value));
+        }
+
+        let errors = attribute_errors(&definitions, attributes, partial);
+        if !errors.is_empty() {
+            return Err(AppError::BadRequest(format!("{}{}", error_context, errors.join("; "))));
+        }
+        info!("[Service] Custom attributes validation passed");
+        Ok(())
+    }
+
     pub async fn create_user(&self, data: CreateUser) -> Result<HttpResponse, errors::AppError> {
+        let user = self.register_user(data).await?;
+        let tag = etag(user.version);
+        Ok(response::ApiResponse::created(user).with_etag(tag).into_response())
+    }
+
+    // Validação e criação da conta, compartilhadas com o aceite de convites
+    pub async fn register_user(&self, mut data: CreateUser) -> Result<UserDataCreated, errors::AppError> {
         info!("[Service] Starting user creation process for email: {}", data.email);
+        data.email = normalize_email(&data.email);
 
         self.validate_user_fields(&data.full_name, &data.email, Some(&data.password), "Error adding user: ")?;
+        self.validate_attributes(&mut data.attributes, false, "Error adding user: ").await?;
 
         info!("[Service] Checking if user already exists with email: {}", data.email);
-        if self.user_repo.find_user_by_email(data.email.clone()).await.is_ok() {
+        // Inclui contas excluídas ainda não expurgadas, que mantêm o email reservado
+        if self.user_repo.email_exists_for_other_user(&data.email, Uuid::nil()).await? {
             info!("[Service] User already exists with email: {}", data.email);
-            return Err(errors::AppError::BadRequest(
+            return Err(errors::AppError::Conflict(
                 format!("Error adding user: email '{}' already exists", data.email)
             ));
         }
@@ -79,11 +217,18 @@ impl UserService {
         info!("[Service] Password hashed successfully");
 
         info!("[Service] Saving user to database");
-        match self.user_repo.create_user(user_with_hash).await {
+        let audit: AuditTrail<UserDataCreated> = Box::new(|user| {
+            vec![audit_entry(AUDIT_USER_CREATED, AUDIT_RESOURCE_USER, user.id, None, Some(user))]
+        });
+        match self.user_repo.create_user(user_with_hash, audit).await {
             Ok(user) => {
                 info!("[Service] User created successfully with ID: {}", user.id);
-                Ok(response::ApiResponse::created(user).into_response())
+                Ok(user)
             },
+            Err(e) if is_unique_violation(&e) => {
+                info!("[Service] Email was taken concurrently by another request");
+                Err(errors::AppError::Conflict("Error adding user: email already exists".into()))
+            }
             Err(e) => {
                 error!("[Service] Error creating user in database: {:?}", e);
                 Err(errors::AppError::InternalServerError)
@@ -91,28 +236,209 @@ impl UserService {
         }
     }
 
-    pub async fn update_user(&self, data: UpdateUser, id: Uuid) -> Result<HttpResponse, AppError> {
+    pub async fn import_users(&self, format: UserImportFormat, body: web::Bytes, query: UserImportQuery) -> Result<HttpResponse, AppError> {
+        info!("[Service] Starting user import process ({:?}, dry_run: {}, mode: {:?})", format, query.dry_run, query.mode);
+
+        let parsed = parse_import_rows(format, &body)?;
+        if parsed.is_empty() {
+            return Err(AppError::BadRequest("Error importing users: the file has no rows".into()));
+        }
+        if parsed.len() > MAX_IMPORT_ROWS {
+            return Err(AppError::BadRequest(
+                format!("Error importing users: at most {} rows are allowed per import", MAX_IMPORT_ROWS)
+            ));
+        }
+
+        info!("[Service] Validating {} rows", parsed.len());
+        let definitions = self.attribute_repo.find_definitions().await?;
+        let mut rows = Vec::with_capacity(parsed.len());
+        let mut valid: Vec<(usize, UserImportRow)> = Vec::new();
+        let mut seen = HashSet::new();
+        for (index, parsed_row) in parsed.into_iter().enumerate() {
+            let mut result = UserImportRowResult {
+                row: index + 1,
+                email: None,
+                status: UserImportRowStatus::Valid,
+                id: None,
+                errors: Vec::new(),
+            };
+            match parsed_row {
+                Ok(mut row) => {
+                    row.email = normalize_email(&row.email);
+                    result.email = Some(row.email.clone()).filter(|email| !email.is_empty());
+                    if format == UserImportFormat::Csv {
+                        coerce_csv_attributes(&definitions, &mut row.attributes);
+                    }
+                    row.attributes.retain(|_, value| !is_blank_attribute(value));
+                    if let Err(e) = self.validate_user_fields(&row.full_name, &row.email, Some(&row.password), "") {
+                        result.errors.push(import_error_message(e));
+                    }
+                    result.errors.extend(attribute_errors(&definitions, &row.attributes, false));
+                    if result.errors.is_empty() {
+                        if seen.insert(row.email.clone()) {
+                            valid.push((index, row));
+                        } else {
+                            result.errors.push(format!("email '{}' appears more than once in the file", row.email));
+                        }
+                    }
+                }
+                Err(e) => result.errors.push(e),
+            }
+            if !result.errors.is_empty() {
+                result.status = UserImportRowStatus::Failed;
+            }
+            rows.push(result);
+        }
+
+        // Uma única consulta para todos os emails, incluindo usuários excluídos ainda não expurgados
+        let emails: Vec<String> = valid.iter().map(|(_, row)| row.email.clone()).collect();
+        let existing: HashSet<String> = self.user_repo.find_existing_emails(&emails).await?.into_iter().collect();
+        valid.retain(|(index, row)| {
+            let available = !existing.contains(&row.email);
+            if !available {
+                fail_import_row(&mut rows[*index], format!("email '{}' already exists", row.email));
+            }
+            available
+        });
+        info!("[Service] {} of {} rows are valid", valid.len(), rows.len());
+
+        if query.dry_run {
+            return Ok(import_response(&query, rows, "Dry run completed; no users were created", StatusCode::OK));
+        }
+        if query.mode == UserImportMode::AllOrNothing && valid.len() < rows.len() {
+            info!("[Service] Import aborted because some rows are invalid");
+            return Ok(import_response(&query, rows, "Import aborted; no users were created", StatusCode::UNPROCESSABLE_ENTITY));
+        }
+        if valid.is_empty() {
+            return Ok(import_response(&query, rows, "Import completed; no users were created", StatusCode::OK));
+        }
+
+        info!("[Service] Hashing {} passwords", valid.len());
+        let hashes = self.hash_passwords(valid.iter().map(|(_, row)| row.password.clone()).collect()).await?;
+        let users = valid.iter()
+            .zip(hashes)
+            .map(|((_, row), password)| CreateUser {
+                full_name: row.full_name.clone(),
+                email: row.email.clone(),
+                password,
+                attributes: row.attributes.clone(),
+            })
+            .collect();
+
+        info!("[Service] Saving imported users to database");
+        let audit: AuditTrail<Vec<UserDataCreated>> = Box::new(|created| {
+            created.iter()
+                .map(|user| audit_entry(AUDIT_USER_CREATED, AUDIT_RESOURCE_USER, user.id, None, Some(user)))
+                .collect()
+        });
+        match self.user_repo.import_users(users, query.mode == UserImportMode::Partial, audit).await {
+            Ok(created) => {
+                let ids: HashMap<String, Uuid> = created.into_iter().map(|user| (user.email, user.id)).collect();
+                for (index, row) in &valid {
+                    match ids.get(&row.email) {
+                        Some(id) => {
+                            rows[*index].status = UserImportRowStatus::Created;
+                            rows[*index].id = Some(*id);
+                        }
+                        // Criado por outra requisição entre a validação e a inserção
+                        None => fail_import_row(&mut rows[*index], format!("email '{}' already exists", row.email)),
+                    }
+                }
+                info!("[Service] User import completed with {} users created", ids.len());
+                Ok(import_response(&query, rows, "Import completed", StatusCode::OK))
+            }
+            Err(e) if is_unique_violation(&e) => {
+                info!("[Service] Import aborted because an email was taken concurrently");
+                let existing: HashSet<String> = self.user_repo.find_existing_emails(&emails).await?.into_iter().collect();
+                for (index, row) in &valid {
+                    if existing.contains(&row.email) {
+                        fail_import_row(&mut rows[*index], format!("email '{}' already exists", row.email));
+                    }
+                }
+                Ok(import_response(&query, rows, "Import aborted; no users were created", StatusCode::UNPROCESSABLE_ENTITY))
+            }
+            Err(e) => {
+                error!("[Service] Error importing users in database: {:?}", e);
+                Err(AppError::InternalServerError)
+            }
+        }
+    }
+
+    // Argon2 é custoso: o lote roda fora do executor, dividido entre os núcleos disponíveis
+    async fn hash_passwords(&self, passwords: Vec<String>) -> Result<Vec<String>, AppError> {
+        let encryptor = self.password_encryptor.clone();
+
+        web::block(move || {
+            let threads = std::thread::available_parallelism().map(|n| n.get()).unwrap_or(1);
+            let chunk_size = passwords.len().div_ceil(threads).max(1);
+            std::thread::scope(|scope| {
+                let handles: Vec<_> = passwords
+                    .chunks(chunk_size)
+                    .map(|chunk| {
+                        let encryptor = &encryptor;
+                        scope.spawn(move || {
+                            chunk.iter().map(|password| encryptor.hash_password(password)).collect::<Result<Vec<_>, _>>()
+                        })
+                    })
+                    .collect();
+                handles
+                    .into_iter()
+                    .map(|handle| handle.join().expect("Password hashing thread panicked"))
+                    .collect::<Result<Vec<_>, _>>()
+            })
+            .map(|chunks| chunks.concat())
+        })
+        .await
+        .map_err(|e| {
+            error!("[Service] Error running password hashing: {:?}", e);
+            AppError::InternalServerError
+        })?
+        .map_err(|e| {
+            error!("[Service] Error hashing password: {:?}", e);
+            AppError::InternalServerError
+        })
+    }
+
+    pub async fn update_user(&self, mut data: UpdateUser, id: Uuid, if_match: IfMatch) -> Result<HttpResponse, AppError> {
         info!("[Service] Starting update user process for id: {}", id);
+        data.email = normalize_email(&data.email);
+        let expected_versions = self.expected_versions(if_match)?;
 
         self.validate_user_fields(&data.full_name, &data.email, None, "Error updating user: ")?;
+        self.validate_attributes(&mut data.attributes, false, "Error updating user: ").await?;
 
         info!("[Service] Checking if the email is already in use by another user");
         if self.user_repo.email_exists_for_other_user(&data.email, id).await? {
-            return Err(AppError::BadRequest(
+            return Err(AppError::Conflict(
                 format!("Email '{}' is already in use by another user", data.email)
             ));
         }
         info!("[Service] Email is available, proceeding with the update");
 
+        let before = self.existing_user(id).await?;
+        let requested_email = if normalize_email(&before.email) != data.email {
+            info!("[Service] Email change requested, keeping current email until confirmation");
+            Some(std::mem::replace(&mut data.email, before.email.clone()))
+        } else {
+            None
+        };
+
         info!("[Service] Saving user to database");
-        match self.user_repo.update_user(id, data).await {
+        let audit: AuditTrail<UserDataCreated> = Box::new(move |user| {
+            vec![audit_entry(AUDIT_USER_UPDATED, AUDIT_RESOURCE_USER, id, Some(&before), Some(user))]
+        });
+        match self.user_repo.update_user(id, data, expected_versions, audit).await {
             Ok(user) => {
                 info!("[Service] User updated successfully with ID: {}", user.id);
-                Ok(response::ApiResponse::updated(user).into_response())
+                self.updated_response(user, requested_email).await
             },
             Err(sqlx::Error::RowNotFound) => {
-                error!("[Service] User with id {} not found for update", id);
-                Err(AppError::NotFound(format!("User with id '{}' not found", id)))
+                error!("[Service] User with id {} not updated", id);
+                Err(self.precondition_or_not_found(id).await)
+            }
+            Err(e) if is_unique_violation(&e) => {
+                info!("[Service] Email was taken concurrently by another request");
+                Err(AppError::Conflict("Email is already in use by another user".into()))
             }
             Err(e) => {
                 error!("[Service] Error updating user in database: {:?}", e);
@@ -121,13 +447,95 @@ impl UserService {
         }
     }
 
+    pub async fn patch_user(&self, mut data: PatchUser, id: Uuid, if_match: IfMatch) -> Result<HttpResponse, AppError> {
+        info!("[Service] Starting patch user process for id: {}", id);
+        if let Patch::Value(email) = &mut data.email {
+            *email = normalize_email(email);
+        }
+        let expected_versions = self.expected_versions(if_match)?;
+
+        if data.full_name.is_absent() && data.email.is_absent() && data.attributes.is_absent() {
+            info!("[Service] Empty patch, returning current user");
+            let user = self.current_user(id, expected_versions).await?;
+            let tag = etag(user.version);
+            return Ok(ApiResponse::success(user).with_etag(tag).into_response());
+        }
+
+        info!("[Service] Validating fields present in the patch");
+        for (field, value) in [("full_name", &data.full_name), ("email", &data.email)] {
+            match value {
+                Patch::Null => {
+                    return Err(AppError::BadRequest(format!("Error updating user: {} cannot be null", field)));
+                }
+                Patch::Value(v) => validate_required_fields(&[(field, v.is_empty())], "Error updating user")?,
+                Patch::Absent => {}
+            }
+        }
+        match &mut data.attributes {
+            Patch::Null => return Err(AppError::BadRequest("Error updating user: attributes cannot be null".into())),
+            Patch::Value(attributes) => self.validate_attributes(attributes, true, "Error updating user: ").await?,
+            Patch::Absent => {}
+        }
+
+        if let Patch::Value(email) = &data.email {
+            if !is_valid_email(email) {
+                return Err(AppError::BadRequest(format!("Error updating user: '{}' is not a valid email", email)));
+            }
+
+            info!("[Service] Checking if the email is already in use by another user");
+            if self.user_repo.email_exists_for_other_user(email, id).await? {
+                return Err(AppError::Conflict(
+                    format!("Email '{}' is already in use by another user", email)
+                ));
+            }
+        }
+        info!("[Service] Patch validation passed");
+
+        let before = self.existing_user(id).await?;
+        let mut requested_email = None;
+        if let Patch::Value(email) = &data.email
+            && normalize_email(&before.email) != *email
+        {
+            info!("[Service] Email change requested, keeping current email until confirmation");
+            requested_email = Some(email.clone());
+            data.email = Patch::Absent;
+        }
+        if data.full_name.is_absent() && data.email.is_absent() && data.attributes.is_absent() {
+            let user = self.current_user(id, expected_versions).await?;
+            return self.updated_response(user, requested_email).await;
+        }
+
+        let audit: AuditTrail<UserDataCreated> = Box::new(move |user| {
+            vec![audit_entry(AUDIT_USER_UPDATED, AUDIT_RESOURCE_USER, id, Some(&before), Some(user))]
+        });
+        match self.user_repo.patch_user(id, data, expected_versions, audit).await {
+            Ok(user) => {
+                info!("[Service] User patched successfully with ID: {}", user.id);
+                self.updated_response(user, requested_email).await
+            },
+            Err(sqlx::Error::RowNotFound) => {
+                error!("[Service] User with id {} not patched", id);
+                Err(self.precondition_or_not_found(id).await)
+            }
+            Err(e) if is_unique_violation(&e) => {
+                info!("[Service] Email was taken concurrently by another request");
+                Err(AppError::Conflict("Email is already in use by another user".into()))
+            }
+            Err(e) => {
+                error!("[Service] Error patching user in database: {:?}", e);
+                Err(errors::AppError::InternalServerError)
+            }
+        }
+    }
+
     pub async fn find_user_by_id(&self, id: Uuid) -> Result<HttpResponse, AppError> {
         info!("[Service] Starting find user by id process for id: {}", id);
 
         match self.user_repo.find_user_by_id(id).await {
             Ok(user) => {
                 info!("[Service] User with id {} found successfully", user.id);
-                Ok(response::ApiResponse::success(user).into_response())
+                let tag = etag(user.version);
+                Ok(response::ApiResponse::success(user).with_etag(tag).into_response())
             }
             Err(sqlx::Error::RowNotFound) => {
                 info!("[Service] User with id {} not found", id);
@@ -140,18 +548,22 @@ impl UserService {
         }
     }
 
-    pub async fn delete_user_by_id(&self, id: Uuid) -> Result<HttpResponse, AppError> {
+    pub async fn delete_user_by_id(&self, id: Uuid, if_match: IfMatch) -> Result<HttpResponse, AppError> {
         info!("[Service] Starting delete user by id process for id: {}", id);
+        let expected_versions = self.expected_versions(if_match)?;
+
+        let before = self.existing_user(id).await?;
 
         info!("[Service] Deleting user from database");
-        match self.user_repo.delete_user(id).await {
+        let audit = vec![audit_entry(AUDIT_USER_DELETED, AUDIT_RESOURCE_USER, id, Some(&before), None)];
+        match self.user_repo.delete_user(id, expected_versions, audit).await {
             Ok(true) => {
                 info!("[Service] User deleted successfully with ID: {}", id);
                 Ok(ApiResponse::success(()).into_response())
             }
             Ok(false) =>{
-                error!("[Service] User with id {} not found for deletion", id);
-                Err(AppError::NotFound(format!("User with id '{}' not found", id)))
+                error!("[Service] User with id {} not deleted", id);
+                Err(self.precondition_or_not_found(id).await)
             }
             Err(e) => {
                 error!("[Service] Database error while deleting user: {:?}", e);
@@ -160,13 +572,248 @@ impl UserService {
         }
     }
 
-    pub async fn find_all_users(&self) -> Result<HttpResponse, AppError> {
+    pub async fn find_all_users(&self, query: UserListQuery, organization_id: Option<Uuid>) -> Result<HttpResponse, AppError> {
         info!("[Service] Starting find users process");
+        let params = self.build_list_params(query, false, organization_id)?;
+        self.list_users(params).await
+    }
+
+    pub async fn find_deleted_users(&self, query: UserListQuery, organization_id: Option<Uuid>) -> Result<HttpResponse, AppError> {
+        info!("[Service] Starting find deleted users process");
+        let params = self.build_list_params(query, true, organization_id)?;
+        self.list_users(params).await
+    }
+
+    pub async fn restore_user(&self, id: Uuid) -> Result<HttpResponse, AppError> {
+        info!("[Service] Starting restore user process for id: {}", id);
+
+        let audit: AuditTrail<UserDataCreated> = Box::new(move |user| {
+            vec![audit_entry(AUDIT_USER_RESTORED, AUDIT_RESOURCE_USER, id, None, Some(user))]
+        });
+        match self.user_repo.restore_user(id, audit).await {
+            Ok(user) => {
+                info!("[Service] User restored successfully with ID: {}", user.id);
+                let tag = etag(user.version);
+                Ok(ApiResponse::updated(user).with_etag(tag).into_response())
+            }
+            Err(sqlx::Error::RowNotFound) => {
+                info!("[Service] Deleted user with id {} not found", id);
+                Err(AppError::NotFound(format!("Deleted user with id '{}' not found", id)))
+            }
+            Err(e) => {
+                error!("[Service] Database error while restoring user: {:?}", e);
+                Err(AppError::InternalServerError)
+            }
+        }
+    }
+
+    pub async fn change_status(&self, id: Uuid, to: &'static str, data: ChangeUserStatus, changed_by: Uuid) -> Result<HttpResponse, AppError> {
+        info!("[Service] Starting change status process to '{}' for user ID: {}", to, id);
 
-        match self.user_repo.find_all_users().await {
-            Ok(users) => {
+        let reason = data.reason.trim();
+        if reason.is_empty() {
+            return Err(AppError::BadRequest("Reason is required".into()));
+        }
+        if id == changed_by {
+            return Err(AppError::Forbidden("You cannot change the status of your own account".into()));
+        }
+
+        let Some(from) = self.user_repo.find_user_status(id).await? else {
+            info!("[Service] User with id {} not found", id);
+            return Err(AppError::NotFound(format!("User with id '{}' not found", id)));
+        };
+        if !can_change_status(&from, to) {
+            info!("[Service] Invalid status transition from '{}' to '{}'", from, to);
+            return Err(AppError::BadRequest(format!("Cannot change status from '{}' to '{}'", from, to)));
+        }
+
+        let audit = vec![NewAuditEntry {
+            action: AUDIT_USER_STATUS_CHANGED,
+            resource_type: AUDIT_RESOURCE_USER,
+            resource_id: Some(id),
+            changes: serde_json::json!({ "status": { "before": from, "after": to } }),
+        }];
+        match self.user_repo.change_status(id, &from, to, reason, changed_by, audit).await {
+            Ok(user) => {
+                info!("[Service] User {} status changed from '{}' to '{}'", user.id, from, to);
+                let tag = etag(user.version);
+                Ok(ApiResponse::updated(user).with_etag(tag).into_response())
+            }
+            Err(sqlx::Error::RowNotFound) => {
+                info!("[Service] User {} status changed concurrently", id);
+                Err(AppError::BadRequest("User status was changed by another request; please try again".into()))
+            }
+            Err(e) => {
+                error!("[Service] Database error while changing user status: {:?}", e);
+                Err(AppError::InternalServerError)
+            }
+        }
+    }
+
+    // Troca pelo próprio usuário, que confirma a senha atual
+    pub async fn change_password(&self, id: Uuid, data: UpdatePasswordUser) -> Result<HttpResponse, AppError> {
+        info!("[Service] Starting change password process for user ID: {}", id);
+
+        validate_required_fields(&[
+            ("current_password", data.current_password.is_empty()),
+            ("new_password", data.new_password.is_empty()),
+        ], "Error changing password")?;
+
+        let Some(current_hash) = self.user_repo.find_password_hash(id).await? else {
+            info!("[Service] User with id {} not found", id);
+            return Err(AppError::NotFound(format!("User with id '{}' not found", id)));
+        };
+
+        let encryptor = self.password_encryptor.clone();
+        let current_password = data.current_password;
+        let matches = web::block(move || encryptor.verify_password(&current_hash, &current_password))
+            .await
+            .map_err(|e| {
+                error!("[Service] Error running password hashing: {:?}", e);
+                AppError::InternalServerError
+            })?
+            .map_err(|e| {
+                error!("[Service] Error verifying password: {:?}", e);
+                AppError::InternalServerError
+            })?;
+        if !matches {
+            info!("[Service] Current password does not match for user ID: {}", id);
+            return Err(AppError::BadRequest("Current password is incorrect".into()));
+        }
+
+        let new_hash = self.hash_new_password(id, data.new_password).await?;
+
+        let audit = vec![NewAuditEntry {
+            action: AUDIT_USER_PASSWORD_CHANGED,
+            resource_type: AUDIT_RESOURCE_USER,
+            resource_id: Some(id),
+            changes: audit_redacted_changes(&["password"]),
+        }];
+        match self.user_repo.update_password_user(id, &new_hash, self.kept_password_history(), audit).await {
+            Ok(_) => {
+                info!("[Service] Password changed successfully for user ID: {}", id);
+                Ok(ApiResponse::<()>::updated_password().into_response())
+            }
+            Err(sqlx::Error::RowNotFound) => Err(AppError::NotFound(format!("User with id '{}' not found", id))),
+            Err(e) => {
+                error!("[Service] Database error while changing password: {:?}", e);
+                Err(AppError::InternalServerError)
+            }
+        }
+    }
+
+    // Hash da nova senha, recusando a atual e as anteriores guardadas no histórico
+    pub async fn hash_new_password(&self, id: Uuid, new_password: String) -> Result<String, AppError> {
+        let mut recent_hashes = Vec::new();
+        if self.password_history_size > 0 {
+            let Some(current_hash) = self.user_repo.find_password_hash(id).await? else {
+                return Err(AppError::NotFound(format!("User with id '{}' not found", id)));
+            };
+            recent_hashes.push(current_hash);
+            recent_hashes.extend(self.user_repo.find_password_history(id, self.kept_password_history()).await?);
+        }
+
+        let encryptor = self.password_encryptor.clone();
+        // None quando a senha nova repete uma das recentes
+        let new_hash = web::block(move || {
+            if matches_any(encryptor.as_ref(), &new_password, &recent_hashes)? {
+                return Ok(None);
+            }
+            encryptor.hash_password(&new_password).map(Some)
+        })
+        .await
+        .map_err(|e| {
+            error!("[Service] Error running password hashing: {:?}", e);
+            AppError::InternalServerError
+        })?
+        .map_err(|e| {
+            error!("[Service] Error hashing password: {:?}", e);
+            AppError::InternalServerError
+        })?;
+
+        new_hash.ok_or_else(|| {
+            info!("[Service] New password of user ID {} repeats a recent password", id);
+            AppError::BadRequest(format!("New password must not match any of the last {} passwords", self.password_history_size))
+        })
+    }
+
+    pub async fn change_role(&self, id: Uuid, data: ChangeUserRole, changed_by: Uuid) -> Result<HttpResponse, AppError> {
+        info!("[Service] Starting change role process to '{}' for user ID: {}", data.role, id);
+
+        if !is_valid_role(&data.role) {
+            return Err(AppError::BadRequest(format!("'{}' is not a valid role", data.role)));
+        }
+        if id == changed_by {
+            return Err(AppError::Forbidden("You cannot change the role of your own account".into()));
+        }
+
+        let before = self.existing_user(id).await?;
+        if before.role == data.role {
+            let tag = etag(before.version);
+            return Ok(ApiResponse::success(before).with_etag(tag).into_response());
+        }
+
+        let before_role = before.role.clone();
+        let audit: AuditTrail<UserDataCreated> = Box::new(move |user| {
+            vec![audit_entry(AUDIT_USER_ROLE_CHANGED, AUDIT_RESOURCE_USER, id, Some(&before), Some(user))]
+        });
+        match self.user_repo.change_role(id, &data.role, audit).await {
+            Ok(user) => {
+                info!("[Service] User {} role changed from '{}' to '{}'", id, before_role, user.role);
+                let tag = etag(user.version);
+                Ok(ApiResponse::updated(user).with_etag(tag).into_response())
+            }
+            Err(sqlx::Error::RowNotFound) => Err(AppError::NotFound(format!("User with id '{}' not found", id))),
+            Err(e) => {
+                error!("[Service] Database error while changing user role: {:?}", e);
+                Err(AppError::InternalServerError)
+            }
+        }
+    }
+
+    pub async fn find_status_history(&self, id: Uuid) -> Result<HttpResponse, AppError> {
+        info!("[Service] Starting find status history process for user ID: {}", id);
+
+        if self.user_repo.find_user_status(id).await?.is_none() {
+            return Err(AppError::NotFound(format!("User with id '{}' not found", id)));
+        }
+        let history = self.user_repo.find_status_history(id).await?;
+        info!("[Service] Found {} status changes", history.len());
+        Ok(ApiResponse::success(history).into_response())
+    }
+
+    // Remove definitivamente os usuários excluídos há mais de `retention_days` dias
+    pub async fn purge_deleted_users(&self, retention_days: i64) -> Result<u64, AppError> {
+        info!("[Service] Starting purge of users deleted more than {} days ago", retention_days);
+
+        let deleted_before = chrono::Utc::now().naive_utc() - chrono::Duration::days(retention_days);
+        match self.user_repo.purge_deleted_users(deleted_before).await {
+            Ok(purged) => {
+                info!("[Service] Purged {} deleted users", purged);
+                Ok(purged)
+            }
+            Err(e) => {
+                error!("[Service] Database error while purging deleted users: {:?}", e);
+                Err(AppError::InternalServerError)
+            }
+        }
+    }
+
+    async fn list_users(&self, mut params: UserListParams) -> Result<HttpResponse, AppError> {
+        let limit = params.limit;
+        // Uma linha extra indica se existe próxima página
+        params.limit += 1;
+
+        match self.user_repo.find_all_users(&params).await {
+            Ok(mut users) => {
+                let next_cursor = if users.len() as i64 > limit {
+                    users.truncate(limit as usize);
+                    users.last().map(|last| encode_cursor(&user_cursor(last, &params)))
+                } else {
+                    None
+                };
                 info!("[Service] Found {} users", users.len());
-                Ok(ApiResponse::success(users).into_response())
+                Ok(ApiResponse::paginated(users, next_cursor).into_response())
             }
             Err(e) => {
                 error!("[Service] Database error while finding users: {:?}", e);
@@ -174,4 +821,280 @@ impl UserService {
             }
         }
     }
+
+    pub async fn export_users(&self, query: UserExportQuery, organization_id: Option<Uuid>) -> Result<HttpResponse, AppError> {
+        info!("[Service] Starting export users process with format: {:?}", query.format);
+
+        let format = query.format;
+        let params = self.export_params(ExportFilters {
+            sort: query.sort,
+            email_contains: query.email_contains,
+            created_from: query.created_from,
+            created_to: query.created_to,
+            attributes: parse_attribute_filter(query.attributes.as_deref())?,
+        }, organization_id)?;
+
+        let rows = self.user_repo.stream_users(params).map(move |row| match row {
+            Ok(user) => export_row(format, &user),
+            Err(e) => {
+                error!("[Service] Database error while exporting users: {:?}", e);
+                Err(AppError::InternalServerError)
+            }
+        });
+        let body = stream::iter(export_header(format))
+            .chain(rows)
+            .map(|chunk| chunk.map(web::Bytes::from).map_err(actix_web::Error::from));
+
+        Ok(HttpResponse::Ok()
+            .content_type(format.content_type())
+            .insert_header((header::CONTENT_DISPOSITION, format!("attachment; filename=\"users.{}\"", format.as_str())))
+            .streaming(body))
+    }
+
+    pub async fn search_users(&self, query: UserSearchQuery, organization_id: Option<Uuid>) -> Result<HttpResponse, AppError> {
+        let term = query.q.trim();
+        info!("[Service] Starting search users process with term: {}", term);
+
+        if !(2..=100).contains(&term.chars().count()) {
+            return Err(AppError::BadRequest("Search term must have between 2 and 100 characters".into()));
+        }
+        let limit = page_size(query.limit)?;
+
+        match self.user_repo.search_users(term, limit, organization_id).await {
+            Ok(rows) => {
+                info!("[Service] Found {} users matching the search", rows.len());
+                let hits: Vec<UserSearchHit> = rows.into_iter()
+                    .map(|row| UserSearchHit {
+                        highlights: UserSearchHighlights {
+                            full_name: highlight(&row.user.full_name, term),
+                            email: highlight(&row.user.email, term),
+                        },
+                        user: row.user,
+                        rank: row.rank,
+                    })
+                    .collect();
+                Ok(ApiResponse::success(hits).into_response())
+            }
+            Err(e) => {
+                error!("[Service] Database error while searching users: {:?}", e);
+                Err(AppError::InternalServerError)
+            }
+        }
+    }
+
+    // Mesmos filtros e ordenação da listagem, sem paginação
+    pub fn export_params(&self, filters: ExportFilters, organization_id: Option<Uuid>) -> Result<UserListParams, AppError> {
+        let mut params = self.build_list_params(UserListQuery {
+            limit: None,
+            cursor: None,
+            sort: filters.sort,
+            email_contains: filters.email_contains,
+            created_from: filters.created_from,
+            created_to: filters.created_to,
+            attributes: None,
+        }, false, organization_id)?;
+        params.attributes = filters.attributes.map(attribute_filter).transpose()?.flatten();
+        Ok(params)
+    }
+
+    fn build_list_params(&self, query: UserListQuery, only_deleted: bool, organization_id: Option<Uuid>) -> Result<UserListParams, AppError> {
+        let (sort, direction) = parse_sort(query.sort.as_deref())?;
+
+        let after = match query.cursor.as_deref() {
+            Some(cursor) => {
+                let cursor: UserCursor = decode_cursor(cursor)?;
+                if cursor.sort != sort || cursor.direction != direction {
+                    return Err(AppError::BadRequest("Cursor does not match the requested sort".into()));
+                }
+                if sort == UserSortField::CreatedAt
+                    && chrono::NaiveDateTime::parse_from_str(&cursor.value, ISO_DATE_FORMAT).is_err() {
+                    return Err(AppError::BadRequest("Invalid cursor".into()));
+                }
+                Some(cursor)
+            }
+            None => None,
+        };
+
+        Ok(UserListParams {
+            limit: page_size(query.limit)?,
+            sort,
+            direction,
+            after,
+            email_contains: query.email_contains.filter(|email| !email.is_empty()),
+            created_from: query.created_from,
+            created_to: query.created_to,
+            attributes: parse_attribute_filter(query.attributes.as_deref())?,
+            only_deleted,
+            organization_id,
+        })
+    }
+}
+
+const ISO_DATE_FORMAT: &str = "%Y-%m-%dT%H:%M:%S%.f";
+const MAX_IMPORT_ROWS: usize = 5000;
+// Erros de leitura de uma linha não interrompem a importação; ficam no relatório da linha
+fn parse_import_rows(format: UserImportFormat, body: &[u8]) -> Result<Vec<Result<UserImportRow, String>>, AppError> {
+    match format {
+        UserImportFormat::Json => {
+            let values: Vec<serde_json::Value> = serde_json::from_slice(body)
+                .map_err(|e| AppError::BadRequest(format!("Error importing users: body must be a JSON array ({})", e)))?;
+            Ok(values.into_iter().map(|value| serde_json::from_value(value).map_err(|e| e.to_string())).collect())
+        }
+        UserImportFormat::Csv => {
+            let mut reader = csv::ReaderBuilder::new().trim(csv::Trim::All).from_reader(body);
+            let headers = reader.headers()
+                .map_err(|e| AppError::BadRequest(format!("Error importing users: invalid CSV header ({})", e)))?
+                .clone();
+            for column in ["full_name", "email", "password"] {
+                if !headers.iter().any(|header| header == column) {
+                    return Err(AppError::BadRequest(format!("Error importing users: CSV header is missing the '{}' column", column)));
+                }
+            }
+            // Colunas `attributes.<chave>` viram atributos customizados da linha
+            let attribute_columns: Vec<(usize, String)> = headers.iter()
+                .enumerate()
+                .filter_map(|(index, header)| header.strip_prefix("attributes.").map(|key| (index, key.to_string())))
+                .collect();
+            Ok(reader.records()
+                .map(|record| {
+                    let record = record.map_err(|e| e.to_string())?;
+                    let mut row: UserImportRow = record.deserialize(Some(&headers)).map_err(|e| e.to_string())?;
+                    for (index, key) in &attribute_columns {
+                        if let Some(value) = record.get(*index) {
+                            row.attributes.insert(key.clone(), serde_json::Value::String(value.to_string()));
+                        }
+                    }
+                    Ok(row)
+                })
+                .collect())
+        }
+    }
+}
+
+// No CSV todos os valores chegam como texto; números e booleanos são convertidos pelo tipo do atributo.
+// Valores que não convertem seguem como texto e são recusados na validação
+fn coerce_csv_attributes(definitions: &[UserAttributeDefinition], attributes: &mut UserAttributes) {
+    for definition in definitions {
+        let Some(serde_json::Value::String(text)) = attributes.get(&definition.key) else {
+            continue;
+        };
+        let coerced = match definition.attribute_type.as_str() {
+            ATTRIBUTE_TYPE_NUMBER => text.parse::<i64>().map(serde_json::Value::from).ok()
+                .or_else(|| text.parse::<f64>().ok().and_then(serde_json::Number::from_f64).map(serde_json::Value::Number)),
+            ATTRIBUTE_TYPE_BOOLEAN => text.parse::<bool>().map(serde_json::Value::Bool).ok(),
+            _ => None,
+        };
+        if let Some(value) = coerced {
+            attributes.insert(definition.key.clone(), value);
+        }
+    }
+}
+
+// Filtro por atributos da listagem: objeto JSON com valores simples, comparados por igualdade
+fn parse_attribute_filter(filter: Option<&str>) -> Result<Option<UserAttributes>, AppError> {
+    let Some(filter) = filter.filter(|filter| !filter.trim().is_empty()) else {
+        return Ok(None);
+    };
+    let attributes: UserAttributes = serde_json::from_str(filter)
+        .map_err(|_| AppError::BadRequest("attributes must be a JSON object, e.g. {\"department\":\"sales\"}".into()))?;
+    attribute_filter(attributes)
+}
+
+fn attribute_filter(attributes: UserAttributes) -> Result<Option<UserAttributes>, AppError> {
+    if attributes.values().any(|value| !(value.is_string() || value.is_number() || value.is_boolean())) {
+        return Err(AppError::BadRequest("Attribute filters accept only strings, numbers and booleans".into()));
+    }
+    Ok(Some(attributes).filter(|attributes| !attributes.is_empty()))
+}
+
+fn import_error_message(error: AppError) -> String {
+    match error {
+        AppError::BadRequest(message) => message.trim_start_matches(": ").to_string(),
+        other => other.to_string(),
+    }
+}
+
+fn fail_import_row(row: &mut UserImportRowResult, error: String) {
+    row.status = UserImportRowStatus::Failed;
+    row.errors.push(error);
+}
+
+fn import_response(query: &UserImportQuery, rows: Vec<UserImportRowResult>, message: &str, status: StatusCode) -> HttpResponse {
+    let report = UserImportReport {
+        dry_run: query.dry_run,
+        mode: query.mode,
+        total: rows.len(),
+        created: rows.iter().filter(|row| row.status == UserImportRowStatus::Created).count(),
+        failed: rows.iter().filter(|row| row.status == UserImportRowStatus::Failed).count(),
+        rows,
+    };
+    ApiResponse::new(message.to_string(), status, Some(report)).into_response()
+}
+
+// Formato aceito: "campo" ou "campo:asc|desc"; sem parâmetro mantém created_at:desc
+fn parse_sort(sort: Option<&str>) -> Result<(UserSortField, SortDirection), AppError> {
+    let Some(sort) = sort else {
+        return Ok((UserSortField::CreatedAt, SortDirection::Desc));
+    };
+
+    let (field, direction) = sort.split_once(':').unwrap_or((sort, "asc"));
+    let field = match field {
+        "created_at" => UserSortField::CreatedAt,
+        "full_name" => UserSortField::FullName,
+        "email" => UserSortField::Email,
+        _ => return Err(AppError::BadRequest(format!("Cannot sort by '{}'", field))),
+    };
+    let direction = match direction {
+        "asc" => SortDirection::Asc,
+        "desc" => SortDirection::Desc,
+        _ => return Err(AppError::BadRequest(format!("Invalid sort direction '{}'", direction))),
+    };
+
+    Ok((field, direction))
+}
+
+fn user_cursor(user: &UserDataCreated, params: &UserListParams) -> UserCursor {
+    let value = match params.sort {
+        UserSortField::CreatedAt => user.created_at.format(ISO_DATE_FORMAT).to_string(),
+        UserSortField::FullName => user.full_name.clone(),
+        UserSortField::Email => user.email.clone(),
+    };
+
+    UserCursor { sort: params.sort, direction: params.direction, value, id: user.id }
+}
+
+// Marca com <mark> os trechos do campo que contêm algum termo da busca (sem diferenciar maiúsculas).
+// O texto é escapado antes, já que o trecho destacado costuma ser renderizado como HTML
+fn highlight(value: &str, term: &str) -> String {
+    let lower = value.to_lowercase();
+    let mut marked = vec![false; value.len()];
+
+    // to_lowercase pode mudar o tamanho de caracteres fora do ASCII; nesse caso não destaca
+    if lower.len() == value.len() {
+        for word in term.to_lowercase().split_whitespace() {
+            for (start, _) in lower.match_indices(word) {
+                marked[start..start + word.len()].iter_mut().for_each(|m| *m = true);
+            }
+        }
+    }
+
+    let mut result = String::with_capacity(value.len());
+    let mut open = false;
+    for (index, ch) in value.char_indices() {
+        if marked[index] != open {
+            result.push_str(if open { "</mark>" } else { "<mark>" });
+            open = !open;
+        }
+        match ch {
+            '<' => result.push_str("&lt;"),
+            '>' => result.push_str("&gt;"),
+            '&' => result.push_str("&amp;"),
+            '"' => result.push_str("&quot;"),
+            _ => result.push(ch),
+        }
+    }
+    if open {
+        result.push_str("</mark>");
+    }
+    result
 }
@@ -0,0 +1,48 @@
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use ring::digest::{digest, SHA256};
+use ring::hmac;
+use ring::rand::{SecureRandom, SystemRandom};
+
+// Token opaco de 256 bits em base64url, usado em códigos e links de uso único
+pub fn random_token() -> String {
+    let mut bytes = [0u8; 32];
+    SystemRandom::new()
+        .fill(&mut bytes)
+        .expect("system random generator unavailable");
+    URL_SAFE_NO_PAD.encode(bytes)
+}
+
+// Chave aleatória de 256 bits para HMAC
+pub fn random_key() -> Vec<u8> {
+    let mut bytes = vec![0u8; 32];
+    SystemRandom::new()
+        .fill(&mut bytes)
+        .expect("system random generator unavailable");
+    bytes
+}
+
+// Tokens são persistidos apenas como hash, nunca em texto puro
+pub fn sha256_hex(value: &[u8]) -> String {
+    digest(&SHA256, value)
+        .as_ref()
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
+pub fn sha256_base64url(value: &[u8]) -> String {
+    URL_SAFE_NO_PAD.encode(digest(&SHA256, value).as_ref())
+}
+
+// Assinatura de links temporários, validada sem consulta ao banco
+pub fn hmac_sha256_hex(key: &[u8], message: &[u8]) -> String {
+    hmac::sign(&hmac::Key::new(hmac::HMAC_SHA256, key), message)
+        .as_ref()
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
+pub fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
@@ -25,6 +25,21 @@ pub enum AppError {
 
     #[error("Invalid Method Error: {0}")]
     InvalidMethodError(String),
+
+    #[error("Precondition Failed: {0}")]
+    PreconditionFailed(String),
+
+    #[error("Precondition Required: {0}")]
+    PreconditionRequired(String),
+
+    #[error("Payload Too Large: {0}")]
+    PayloadTooLarge(String),
+
+    #[error("Account Inactive: {0}")]
+    AccountInactive(String),
+
+    #[error("Conflict: {0}")]
+    Conflict(String),
 }
 
 impl ResponseError for AppError {
@@ -45,6 +60,16 @@ impl ResponseError for AppError {
                 (StatusCode::INTERNAL_SERVER_ERROR, "Database Error"),
             AppError::InvalidMethodError(_) =>
                 (StatusCode::METHOD_NOT_ALLOWED, "Invalid Method Error"),
+            AppError::PreconditionFailed(_) =>
+                (StatusCode::PRECONDITION_FAILED, "Precondition Failed"),
+            AppError::PreconditionRequired(_) =>
+                (StatusCode::PRECONDITION_REQUIRED, "Precondition Required"),
+            AppError::PayloadTooLarge(_) =>
+                (StatusCode::PAYLOAD_TOO_LARGE, "Payload Too Large"),
+            AppError::AccountInactive(_) =>
+                (StatusCode::FORBIDDEN, "Account Inactive"),
+            AppError::Conflict(_) =>
+                (StatusCode::CONFLICT, "Conflict"),
         };
 
         HttpResponse::build(status_code)
@@ -64,6 +89,11 @@ impl ResponseError for AppError {
             AppError::NotFound(_) => StatusCode::NOT_FOUND,
             AppError::DatabaseError(_) => StatusCode::INTERNAL_SERVER_ERROR,
             AppError::InvalidMethodError(_) => StatusCode::METHOD_NOT_ALLOWED,
+            AppError::PreconditionFailed(_) => StatusCode::PRECONDITION_FAILED,
+            AppError::PreconditionRequired(_) => StatusCode::PRECONDITION_REQUIRED,
+            AppError::PayloadTooLarge(_) => StatusCode::PAYLOAD_TOO_LARGE,
+            AppError::AccountInactive(_) => StatusCode::FORBIDDEN,
+            AppError::Conflict(_) => StatusCode::CONFLICT,
         }
     }
 }
@@ -72,7 +102,56 @@ impl From<sqlx::Error> for AppError {
     fn from(err: sqlx::Error) -> Self {
         match err {
             sqlx::Error::RowNotFound => AppError::NotFound("Resource not found".to_string()),
+            _ if is_unique_violation(&err) => AppError::Conflict("Resource already exists".to_string()),
             _ => AppError::DatabaseError(err.to_string()),
         }
     }
 }
+
+// 23505 (unique_violation): a restrição do banco é a garantia final contra duplicidade em requisições concorrentes
+pub fn is_unique_violation(err: &sqlx::Error) -> bool {
+    err.as_database_error()
+        .and_then(|e| e.code())
+        .is_some_and(|code| code == "23505")
+}
+
+// Erros no formato da RFC 6749 (seção 5.2), esperado pelas bibliotecas clientes OAuth
+#[derive(Debug, Error)]
+#[error("{error}: {description}")]
+pub struct OAuthError {
+    pub error: &'static str,
+    pub description: String,
+}
+
+impl OAuthError {
+    pub fn new(error: &'static str, description: impl Into<String>) -> Self {
+        Self { error, description: description.into() }
+    }
+}
+
+impl ResponseError for OAuthError {
+    fn error_response(&self) -> HttpResponse {
+        error!("OAuth error occurred: {}", self);
+        HttpResponse::build(self.status_code())
+            .insert_header(("Cache-Control", "no-store"))
+            .json(json!({
+                "error": self.error,
+                "error_description": self.description
+            }))
+    }
+
+    fn status_code(&self) -> StatusCode {
+        match self.error {
+            "invalid_client" => StatusCode::UNAUTHORIZED,
+            "server_error" => StatusCode::INTERNAL_SERVER_ERROR,
+            _ => StatusCode::BAD_REQUEST,
+        }
+    }
+}
+
+impl From<sqlx::Error> for OAuthError {
+    fn from(err: sqlx::Error) -> Self {
+        error!("Database error during OAuth request: {:?}", err);
+        OAuthError::new("server_error", "Unexpected error while processing the request")
+    }
+}
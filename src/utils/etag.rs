@@ -0,0 +1,48 @@
+use actix_web::{dev::Payload, http::header, Error, FromRequest, HttpRequest};
+use futures::future::{ready, Ready};
+
+// Condição enviada no cabeçalho If-Match
+#[derive(Debug, Clone, Default, PartialEq)]
+pub enum IfMatch {
+    #[default]
+    Missing,
+    Any,
+    Versions(Vec<i64>),
+}
+
+pub fn etag(version: i64) -> String {
+    format!("\"{}\"", version)
+}
+
+// Comparação forte (RFC 9110): ETags fracas (W/) e valores que não são versões nunca casam
+fn parse_if_match(value: &str) -> IfMatch {
+    if value.trim() == "*" {
+        return IfMatch::Any;
+    }
+
+    let versions = value
+        .split(',')
+        .map(str::trim)
+        .filter_map(|tag| tag.strip_prefix('"')?.strip_suffix('"')?.parse().ok())
+        .collect();
+    IfMatch::Versions(versions)
+}
+
+impl FromRequest for IfMatch {
+    type Error = Error;
+    type Future = Ready<Result<Self, Self::Error>>;
+
+    fn from_request(req: &HttpRequest, _: &mut Payload) -> Self::Future {
+        let values: Vec<&str> = req.headers()
+            .get_all(header::IF_MATCH)
+            .filter_map(|h| h.to_str().ok())
+            .collect();
+
+        let if_match = if values.is_empty() {
+            IfMatch::Missing
+        } else {
+            parse_if_match(&values.join(","))
+        };
+        ready(Ok(if_match))
+    }
+}
@@ -0,0 +1,89 @@
+use log::error;
+use crate::core::entities::audit::AuditEntry;
+use crate::core::entities::user::{UserDataCreated, UserExportFormat};
+use crate::utils::errors::AppError;
+
+const CSV_COLUMNS: [&str; 8] = ["id", "full_name", "email", "role", "version", "created_at", "updated_at", "attributes"];
+const AUDIT_CSV_COLUMNS: [&str; 13] = [
+    "seq", "id", "occurred_at", "actor_id", "organization_id", "request_id", "ip", "action", "resource_type", "resource_id",
+    "changes", "prev_hash", "hash",
+];
+const DATE_FORMAT: &str = "%Y-%m-%dT%H:%M:%S%.f";
+
+// Cabeçalho do arquivo exportado; NDJSON não tem cabeçalho
+pub fn export_header(format: UserExportFormat) -> Option<Result<Vec<u8>, AppError>> {
+    match format {
+        UserExportFormat::Csv => Some(csv_record(CSV_COLUMNS)),
+        UserExportFormat::Ndjson => None,
+    }
+}
+
+pub fn export_row(format: UserExportFormat, user: &UserDataCreated) -> Result<Vec<u8>, AppError> {
+    match format {
+        UserExportFormat::Csv => csv_record([
+            user.id.to_string(),
+            user.full_name.clone(),
+            user.email.clone(),
+            user.role.clone(),
+            user.version.to_string(),
+            user.created_at.format(DATE_FORMAT).to_string(),
+            user.updated_at.format(DATE_FORMAT).to_string(),
+            // Atributos customizados variam por instalação; vão numa única coluna em JSON
+            serde_json::to_string(&user.attributes).map_err(|e| {
+                error!("Error serializing user attributes for export: {:?}", e);
+                AppError::InternalServerError
+            })?,
+        ]),
+        UserExportFormat::Ndjson => {
+            let mut line = serde_json::to_vec(user).map_err(|e| {
+                error!("Error serializing user for export: {:?}", e);
+                AppError::InternalServerError
+            })?;
+            line.push(b'\n');
+            Ok(line)
+        }
+    }
+}
+
+pub fn audit_csv_header() -> Result<Vec<u8>, AppError> {
+    csv_record(AUDIT_CSV_COLUMNS)
+}
+
+pub fn audit_csv_row(entry: &AuditEntry) -> Result<Vec<u8>, AppError> {
+    let optional = |value: Option<String>| value.unwrap_or_default();
+    csv_record([
+        entry.seq.to_string(),
+        entry.id.to_string(),
+        entry.occurred_at.format(DATE_FORMAT).to_string(),
+        optional(entry.actor_id.map(|id| id.to_string())),
+        optional(entry.organization_id.map(|id| id.to_string())),
+        optional(entry.request_id.clone()),
+        optional(entry.ip.clone()),
+        entry.action.clone(),
+        entry.resource_type.clone(),
+        optional(entry.resource_id.map(|id| id.to_string())),
+        // Diferença em JSON numa única coluna, como os atributos na exportação de usuários
+        serde_json::to_string(&entry.changes).map_err(|e| {
+            error!("Error serializing audit changes for export: {:?}", e);
+            AppError::InternalServerError
+        })?,
+        entry.prev_hash.clone(),
+        entry.hash.clone(),
+    ])
+}
+
+fn csv_record<I, T>(fields: I) -> Result<Vec<u8>, AppError>
+where
+    I: IntoIterator<Item = T>,
+    T: AsRef<[u8]>,
+{
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    writer.write_record(fields).map_err(|e| {
+        error!("Error writing CSV record: {:?}", e);
+        AppError::InternalServerError
+    })?;
+    writer.into_inner().map_err(|e| {
+        error!("Error writing CSV record: {:?}", e);
+        AppError::InternalServerError
+    })
+}
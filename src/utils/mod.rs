@@ -1,3 +1,7 @@
 pub mod validations;
 pub mod errors;
 pub mod response;
+pub mod crypto;
+pub mod pagination;
+pub mod etag;
+pub mod export;
@@ -1,12 +1,14 @@
 use actix_web::{HttpResponse, http::StatusCode};
 use serde::Serialize;
-use serde_json::json;
+use serde_json::{json, Value};
 
 #[derive(Debug)]
 pub struct ApiResponse<T: Serialize> {
     message: String,
     status_code: StatusCode,
     data: Option<T>,
+    meta: Option<Value>,
+    etag: Option<String>,
 }
 
 impl<T: Serialize> ApiResponse<T> {
@@ -15,6 +17,8 @@ impl<T: Serialize> ApiResponse<T> {
             message,
             status_code,
             data,
+            meta: None,
+            etag: None,
         }
     }
 
@@ -23,6 +27,8 @@ impl<T: Serialize> ApiResponse<T> {
             message: "Operation successful".to_string(),
             status_code: StatusCode::OK,
             data: Some(data),
+            meta: None,
+            etag: None,
         }
     }
 
@@ -31,6 +37,8 @@ impl<T: Serialize> ApiResponse<T> {
             message: "Not found".to_string(),
             status_code: StatusCode::NOT_FOUND,
             data: None,
+            meta: None,
+            etag: None,
         }
     }
 
@@ -39,6 +47,8 @@ impl<T: Serialize> ApiResponse<T> {
             message: "Resource created successfully".to_string(),
             status_code: StatusCode::CREATED,
             data: Some(data),
+            meta: None,
+            etag: None,
         }
     }
 
@@ -47,6 +57,8 @@ impl<T: Serialize> ApiResponse<T> {
             message: "Resource updated successfully".to_string(),
             status_code: StatusCode::OK,
             data: Some(data),
+            meta: None,
+            etag: None,
         }
     }
 
@@ -55,6 +67,8 @@ impl<T: Serialize> ApiResponse<T> {
             message: "Resource updated successfully".to_string(),
             status_code: StatusCode::OK,
             data: None,
+            meta: None,
+            etag: None,
         }
     }
 
@@ -64,16 +78,41 @@ impl<T: Serialize> ApiResponse<T> {
             message: "Resource deleted successfully".to_string(),
             status_code: StatusCode::OK,
             data: None,
+            meta: None,
+            etag: None,
         }
     }
 
+    pub fn paginated(data: T, next_cursor: Option<String>) -> Self {
+        Self {
+            message: "Operation successful".to_string(),
+            status_code: StatusCode::OK,
+            data: Some(data),
+            meta: Some(json!({ "next_cursor": next_cursor })),
+            etag: None,
+        }
+    }
+
+    pub fn with_etag(mut self, etag: String) -> Self {
+        self.etag = Some(etag);
+        self
+    }
+
     pub fn into_response(self) -> HttpResponse {
-        HttpResponse::build(self.status_code)
-            .json(json!({
-                "message": self.message,
-                "status": self.status_code.as_u16(),
-                "data": self.data
-            }))
+        let mut body = json!({
+            "message": self.message,
+            "status": self.status_code.as_u16(),
+            "data": self.data
+        });
+        if let Some(meta) = self.meta {
+            body["meta"] = meta;
+        }
+
+        let mut response = HttpResponse::build(self.status_code);
+        if let Some(etag) = self.etag {
+            response.insert_header(("ETag", etag));
+        }
+        response.json(body)
     }
 
 
@@ -1,3 +1,4 @@
+use actix_web::http::Method;
 use crate::utils::errors::AppError;
 use::regex::Regex;
 use lazy_static::lazy_static;
@@ -7,16 +8,68 @@ lazy_static! {
     static ref EMAIL_REGEX: Regex = Regex::new(r"^[a-zA-Z0-9._%+-]+@[a-zA-Z0-9.-]+\.[a-zA-Z]{2,}$").unwrap();
 }
 
+// slug de organização: minúsculas, dígitos e hífens, sem hífen nas pontas
+lazy_static! {
+    static ref SLUG_REGEX: Regex = Regex::new(r"^[a-z0-9](?:[a-z0-9-]{0,61}[a-z0-9])?$").unwrap();
+}
+
+// chave de atributo customizado: minúsculas, dígitos e sublinhado, começando por letra
+lazy_static! {
+    static ref ATTRIBUTE_KEY_REGEX: Regex = Regex::new(r"^[a-z][a-z0-9_]{0,49}$").unwrap();
+}
+
+pub fn is_valid_attribute_key(key: &str) -> bool {
+    ATTRIBUTE_KEY_REGEX.is_match(key)
+}
+
+pub fn is_valid_slug(slug: &str) -> bool {
+    SLUG_REGEX.is_match(slug)
+}
+
 pub fn is_valid_email(email: &str) -> bool {
     EMAIL_REGEX.is_match(email)
 }
 
+// Emails são armazenados e comparados sempre em minúsculas: `Bob@x.com` e `bob@x.com` são a mesma conta
+pub fn normalize_email(email: &str) -> String {
+    email.trim().to_lowercase()
+}
+
 // public routes
 pub fn is_public_route(path: &str) -> bool {
     let public_routes = [
         "/api/v1/auth/login",
+        "/api/v1/auth/passkeys/login",
+        "/api/v1/auth/oidc/login",
+        "/api/v1/auth/oidc/callback",
+        "/api/v1/auth/device-alerts/",
+        "/api/v1/invitations/accept",
+        "/api/v1/email-changes/confirm",
+        "/api/v1/email-changes/revert",
     ];
     public_routes.iter().any(|route| path.starts_with(route))
+        || is_client_authenticated_route(path)
+        || is_signed_link_route(path)
+}
+
+// Única rota aceita com o token emitido para uma senha vencida
+pub fn is_password_change_route(method: &Method, path: &str, user_id: &str) -> bool {
+    method == Method::PUT && path == format!("/api/v1/users/{}/password", user_id)
+}
+
+// Links temporários assinados, autenticados pela própria assinatura na URL
+pub fn is_signed_link_route(path: &str) -> bool {
+    path.starts_with("/api/v1/exports/") && path.ends_with("/download")
+}
+
+// Rotas OAuth chamadas por outras aplicações, autenticadas pelas credenciais do cliente
+pub fn is_client_authenticated_route(path: &str) -> bool {
+    let client_routes = [
+        "/api/v1/oauth/token",
+        "/api/v1/oauth/introspect",
+        "/api/v1/oauth/revoke",
+    ];
+    client_routes.contains(&path)
 }
 
 pub fn validate_required_fields(validations: &[(&str, bool)], error_prefix: &str) -> Result<(), AppError> {
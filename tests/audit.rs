@@ -0,0 +1,75 @@
+// Pseudonimização dos dados pessoais na trilha de auditoria com a chave do titular.
+// Requer um banco com as migrações aplicadas em TEST_DATABASE_URL: `cargo test -- --ignored`.
+mod common;
+
+use std::sync::Arc;
+use actix_web::web;
+use rust_api_architecture_model::adapters::file_storage::InMemoryFileStorage;
+use rust_api_architecture_model::adapters::password_hasher::Argon2PasswordEncryptor;
+use rust_api_architecture_model::config::tenant::{TenantScope, CURRENT_TENANT};
+use rust_api_architecture_model::core::entities::audit::AUDIT_USER_CREATED;
+use rust_api_architecture_model::core::entities::user::CreateUser;
+use rust_api_architecture_model::repositories::privacy_repository::PgPrivacyRepository;
+use rust_api_architecture_model::repositories::user_repository::PgUserRepository;
+use rust_api_architecture_model::services::avatar_service::AvatarService;
+use rust_api_architecture_model::services::privacy_service::PrivacyService;
+use rust_api_architecture_model::utils::crypto::{hmac_sha256_hex, sha256_hex};
+use serde_json::Value;
+use uuid::Uuid;
+
+#[actix_web::test]
+#[ignore = "requires TEST_DATABASE_URL"]
+async fn personal_data_is_keyed_per_user_and_erasure_destroys_the_key() {
+    let pool = common::test_pool().await;
+    let config = common::test_config();
+    let user_service = common::user_service(&pool, &config);
+    let privacy_service = PrivacyService::new(
+        web::Data::new(PgPrivacyRepository::new(pool.clone())),
+        web::Data::new(AvatarService::new(
+            web::Data::new(PgUserRepository::new(pool.clone())),
+            Box::new(InMemoryFileStorage::new()),
+            config.avatar_max_bytes,
+        )),
+        Arc::new(Argon2PasswordEncryptor::new()),
+    );
+    let email = format!("audit-{}@test.example", Uuid::new_v4().simple());
+
+    let user = CURRENT_TENANT.scope(TenantScope::All, user_service.register_user(CreateUser {
+        full_name: "Audit Subject".to_string(),
+        email: email.clone(),
+        password: "Secret123!".to_string(),
+        attributes: Default::default(),
+    }))
+    .await
+    .unwrap();
+
+    let changes: Value = sqlx::query_scalar("SELECT changes FROM audit_log WHERE action = $1 AND resource_id = $2")
+        .bind(AUDIT_USER_CREATED)
+        .bind(user.id)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    let key: Vec<u8> = sqlx::query_scalar("SELECT key FROM user_audit_keys WHERE user_id = $1")
+        .bind(user.id)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+
+    // Sem a chave, conhecer o email não basta para confirmar a entrada
+    let stored = changes["email"]["after"].as_str().unwrap();
+    let email_json = Value::String(email.clone()).to_string();
+    assert_eq!(stored, format!("hmac-sha256:{}", hmac_sha256_hex(&key, email_json.as_bytes())));
+    assert_ne!(stored, format!("sha256:{}", sha256_hex(email_json.as_bytes())));
+    assert!(changes["full_name"]["after"].as_str().unwrap().starts_with("hmac-sha256:"));
+    assert!(!changes.to_string().contains(&email));
+
+    CURRENT_TENANT.scope(TenantScope::All, privacy_service.erase_user(user.id, user.id)).await.unwrap();
+    let remaining: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM user_audit_keys WHERE user_id = $1")
+        .bind(user.id)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(remaining, 0);
+
+    common::delete_user(&pool, user.id).await;
+}
@@ -0,0 +1,151 @@
+// Configuração e serviços compartilhados pelos testes de integração que usam o banco.
+// Cada arquivo de teste usa apenas parte dos auxiliares.
+#![allow(dead_code)]
+
+use std::sync::Arc;
+use actix_web::body::to_bytes;
+use actix_web::{web, HttpResponse};
+use rust_api_architecture_model::adapters::mailer::LogMailer;
+use rust_api_architecture_model::adapters::passkey_verifier::WebAuthnVerifier;
+use rust_api_architecture_model::adapters::password_hasher::Argon2PasswordEncryptor;
+use rust_api_architecture_model::adapters::token_hasher::JwtTokenGenerator;
+use rust_api_architecture_model::config::config_env::Config;
+use rust_api_architecture_model::repositories::device_repository::PgDeviceRepository;
+use rust_api_architecture_model::repositories::email_change_repository::PgEmailChangeRepository;
+use rust_api_architecture_model::repositories::login_attempt_repository::PgLoginAttemptRepository;
+use rust_api_architecture_model::repositories::organization_repository::PgOrganizationRepository;
+use rust_api_architecture_model::repositories::passkey_repository::PgPasskeyRepository;
+use rust_api_architecture_model::repositories::user_attribute_repository::PgUserAttributeRepository;
+use rust_api_architecture_model::repositories::user_repository::PgUserRepository;
+use rust_api_architecture_model::services::auth_service::AuthService;
+use rust_api_architecture_model::services::device_alert_service::DeviceAlertService;
+use rust_api_architecture_model::services::email_change_service::EmailChangeService;
+use rust_api_architecture_model::services::login_activity_service::LoginActivityService;
+use rust_api_architecture_model::services::user_service::UserService;
+use serde_json::Value;
+use sqlx::postgres::PgPoolOptions;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+pub const RP_ID: &str = "localhost";
+pub const ORIGIN: &str = "http://localhost:8080";
+
+pub async fn test_pool() -> PgPool {
+    let database_url = std::env::var("TEST_DATABASE_URL").expect("TEST_DATABASE_URL must point to a migrated database");
+    PgPoolOptions::new().max_connections(2).connect(&database_url).await.unwrap()
+}
+
+pub fn test_config() -> Config {
+    Config {
+        database_url: String::new(),
+        server_addr: "127.0.0.1:0".to_string(),
+        jwt_secret: "test-secret".to_string(),
+        api_key: "test-api-key".to_string(),
+        webauthn_rp_id: RP_ID.to_string(),
+        webauthn_rp_name: "Rust API".to_string(),
+        webauthn_origin: ORIGIN.to_string(),
+        oidc_issuer: None,
+        oidc_client_id: String::new(),
+        oidc_client_secret: String::new(),
+        oidc_redirect_uri: String::new(),
+        user_purge_retention_days: 30,
+        user_purge_interval_seconds: 3600,
+        require_if_match: false,
+        export_storage_dir: "storage/exports".to_string(),
+        export_retention_hours: 24,
+        export_cleanup_interval_seconds: 600,
+        avatar_storage: "memory".to_string(),
+        avatar_storage_dir: "storage/avatars".to_string(),
+        avatar_max_bytes: 5 * 1024 * 1024,
+        mailer: "log".to_string(),
+        smtp_url: String::new(),
+        mail_from: "no-reply@localhost".to_string(),
+        invitation_ttl_hours: 72,
+        invitation_accept_url: "http://localhost:3000/invitations/accept".to_string(),
+        email_change_ttl_hours: 24,
+        email_change_revert_days: 7,
+        email_change_confirm_url: "http://localhost:3000/email-change/confirm".to_string(),
+        email_change_revert_url: "http://localhost:3000/email-change/revert".to_string(),
+        login_attempt_retention_days: 90,
+        login_attempt_purge_interval_seconds: 3600,
+        device_alert_ttl_hours: 72,
+        device_alert_report_url: "http://localhost:3000/security/not-me".to_string(),
+        password_history_size: 5,
+        password_max_age_days: 90,
+    }
+}
+
+// Mesma montagem do main.rs, com o mailer que apenas registra no log
+pub fn user_service(pool: &PgPool, config: &Config) -> web::Data<UserService> {
+    let user_repository = web::Data::new(PgUserRepository::new(pool.clone()));
+
+    let email_change_service = web::Data::new(EmailChangeService::new(
+        web::Data::new(PgEmailChangeRepository::new(pool.clone())),
+        user_repository.clone(),
+        Arc::new(LogMailer::new()),
+        web::Data::new(config.clone()),
+    ));
+    web::Data::new(UserService::new(
+        user_repository,
+        web::Data::new(PgUserAttributeRepository::new(pool.clone())),
+        Arc::new(Argon2PasswordEncryptor::new()),
+        email_change_service,
+        config.require_if_match,
+        config.password_history_size,
+    ))
+}
+
+pub fn auth_service(pool: &PgPool, config: &Config) -> web::Data<AuthService> {
+    let mailer = Arc::new(LogMailer::new());
+    let user_repository = web::Data::new(PgUserRepository::new(pool.clone()));
+    let user_service = user_service(pool, config);
+
+    let device_alert_service = web::Data::new(DeviceAlertService::new(
+        web::Data::new(PgDeviceRepository::new(pool.clone())),
+        user_service,
+        mailer,
+        web::Data::new(config.clone()),
+    ));
+    let login_activity_service = web::Data::new(LoginActivityService::new(
+        web::Data::new(PgLoginAttemptRepository::new(pool.clone())),
+        user_repository.clone(),
+    ));
+
+    web::Data::new(AuthService::new(
+        user_repository,
+        web::Data::new(PgPasskeyRepository::new(pool.clone())),
+        web::Data::new(PgOrganizationRepository::new(pool.clone())),
+        login_activity_service,
+        device_alert_service,
+        web::Data::new(config.clone()),
+        Box::new(Argon2PasswordEncryptor::new()),
+        Box::new(JwtTokenGenerator::new()),
+        Box::new(WebAuthnVerifier::new(config.webauthn_rp_id.clone(), config.webauthn_origin.clone())),
+    ))
+}
+
+pub async fn create_user(pool: &PgPool, email: &str, password_hash: &str) -> Uuid {
+    let id = Uuid::new_v4();
+    let now = chrono::Utc::now().naive_utc();
+    sqlx::query("INSERT INTO users (id, full_name, email, password, created_at, updated_at) VALUES ($1, $2, $3, $4, $5, $5)")
+        .bind(id)
+        .bind("Integration Test")
+        .bind(email)
+        .bind(password_hash)
+        .bind(now)
+        .execute(pool)
+        .await
+        .unwrap();
+    id
+}
+
+pub async fn delete_user(pool: &PgPool, id: Uuid) {
+    sqlx::query("DELETE FROM users WHERE id = $1").bind(id).execute(pool).await.unwrap();
+}
+
+// Corpo JSON da resposta no formato `ApiResponse`: devolve o campo `data`
+pub async fn response_data(response: HttpResponse) -> Value {
+    let body = to_bytes(response.into_body()).await.unwrap();
+    let mut json: Value = serde_json::from_slice(&body).unwrap();
+    json["data"].take()
+}
@@ -0,0 +1,398 @@
+// Login via OpenID Connect contra um provedor local (discovery, JWKS e token endpoint servidos pelo teste).
+// Os testes do adaptador rodam sempre; o fluxo completo pelo OidcService precisa de um banco com as
+// migrações aplicadas em TEST_DATABASE_URL: `cargo test -- --ignored`.
+mod common;
+
+use std::collections::HashMap;
+use std::net::TcpListener;
+use std::sync::{Arc, Mutex};
+use actix_web::{web, App, HttpResponse, HttpServer};
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use jsonwebtoken::{encode, Algorithm, EncodingKey, Header};
+use ring::rand::SystemRandom;
+use ring::signature::{EcdsaKeyPair, KeyPair, ECDSA_P256_SHA256_FIXED_SIGNING};
+use rust_api_architecture_model::adapters::oidc_provider::{HttpOidcProvider, OidcError, OidcProviderPort};
+use rust_api_architecture_model::adapters::password_hasher::Argon2PasswordEncryptor;
+use rust_api_architecture_model::config::tenant::{TenantScope, CURRENT_TENANT};
+use rust_api_architecture_model::core::entities::audit::AUDIT_IDENTITY_UNLINKED;
+use rust_api_architecture_model::core::entities::identity::OidcCallback;
+use rust_api_architecture_model::repositories::identity_repository::PgIdentityRepository;
+use rust_api_architecture_model::repositories::user_repository::PgUserRepository;
+use rust_api_architecture_model::services::oidc_service::OidcService;
+use rust_api_architecture_model::utils::errors::AppError;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+const CLIENT_ID: &str = "rust-api";
+const CLIENT_SECRET: &str = "client-secret";
+const REDIRECT_URI: &str = "http://localhost:3000/oidc/callback";
+
+struct SigningKey {
+    kid: String,
+    pkcs8: Vec<u8>,
+    public_key: Vec<u8>,
+}
+
+impl SigningKey {
+    fn new(kid: &str) -> Self {
+        let rng = SystemRandom::new();
+        let pkcs8 = EcdsaKeyPair::generate_pkcs8(&ECDSA_P256_SHA256_FIXED_SIGNING, &rng).unwrap();
+        let key_pair = EcdsaKeyPair::from_pkcs8(&ECDSA_P256_SHA256_FIXED_SIGNING, pkcs8.as_ref(), &rng).unwrap();
+        Self { kid: kid.to_string(), pkcs8: pkcs8.as_ref().to_vec(), public_key: key_pair.public_key().as_ref().to_vec() }
+    }
+
+    // Ponto não comprimido: 0x04 || x || y
+    fn jwk(&self) -> Value {
+        json!({
+            "kty": "EC",
+            "crv": "P-256",
+            "use": "sig",
+            "alg": "ES256",
+            "kid": self.kid,
+            "x": URL_SAFE_NO_PAD.encode(&self.public_key[1..33]),
+            "y": URL_SAFE_NO_PAD.encode(&self.public_key[33..65]),
+        })
+    }
+
+    fn sign(&self, kid: &str, claims: &Value) -> String {
+        let mut header = Header::new(Algorithm::ES256);
+        header.kid = Some(kid.to_string());
+        encode(&header, claims, &EncodingKey::from_ec_der(&self.pkcs8)).unwrap()
+    }
+
+    fn id_token(&self, claims: &Value) -> String {
+        self.sign(&self.kid, claims)
+    }
+}
+
+// Estado do provedor: chaves publicadas no JWKS e códigos de autorização emitidos
+struct MockIssuer {
+    issuer: String,
+    published_keys: Mutex<Vec<Value>>,
+    codes: Mutex<HashMap<String, String>>,
+    code_verifiers: Mutex<Vec<String>>,
+}
+
+impl MockIssuer {
+    fn publish(&self, key: &SigningKey) {
+        self.published_keys.lock().unwrap().push(key.jwk());
+    }
+
+    // Simula o usuário concluindo o login no provedor: o código passa a valer um id token
+    fn issue_code(&self, id_token: String) -> String {
+        let code = Uuid::new_v4().simple().to_string();
+        self.codes.lock().unwrap().insert(code.clone(), id_token);
+        code
+    }
+
+    fn claims(&self, nonce: &str, subject: &str, email: &str, email_verified: bool) -> Value {
+        let now = chrono::Utc::now().timestamp();
+        json!({
+            "iss": self.issuer,
+            "aud": CLIENT_ID,
+            "sub": subject,
+            "nonce": nonce,
+            "email": email,
+            "email_verified": email_verified,
+            "name": "SSO User",
+            "iat": now,
+            "exp": now + 300,
+        })
+    }
+
+    fn provider(&self) -> HttpOidcProvider {
+        HttpOidcProvider::new(self.issuer.clone(), CLIENT_ID.to_string(), CLIENT_SECRET.to_string(), REDIRECT_URI.to_string())
+    }
+}
+
+#[derive(Deserialize)]
+struct TokenRequest {
+    grant_type: String,
+    code: String,
+    client_id: String,
+    client_secret: String,
+    code_verifier: String,
+}
+
+async fn discovery(mock: web::Data<MockIssuer>) -> HttpResponse {
+    HttpResponse::Ok().json(json!({
+        "issuer": mock.issuer,
+        "authorization_endpoint": format!("{}/authorize", mock.issuer),
+        "token_endpoint": format!("{}/token", mock.issuer),
+        "jwks_uri": format!("{}/jwks", mock.issuer),
+    }))
+}
+
+async fn jwks(mock: web::Data<MockIssuer>) -> HttpResponse {
+    HttpResponse::Ok().json(json!({ "keys": *mock.published_keys.lock().unwrap() }))
+}
+
+async fn token(mock: web::Data<MockIssuer>, form: web::Form<TokenRequest>) -> HttpResponse {
+    if form.grant_type != "authorization_code" || form.client_id != CLIENT_ID || form.client_secret != CLIENT_SECRET {
+        return HttpResponse::BadRequest().json(json!({ "error": "invalid_client" }));
+    }
+    mock.code_verifiers.lock().unwrap().push(form.code_verifier.clone());
+    // Cada código vale uma única troca
+    match mock.codes.lock().unwrap().remove(&form.code) {
+        Some(id_token) => HttpResponse::Ok().json(json!({ "access_token": "access", "id_token": id_token })),
+        None => HttpResponse::BadRequest().json(json!({ "error": "invalid_grant", "error_description": "unknown code" })),
+    }
+}
+
+// O discovery responde em qualquer realm, sempre com o issuer do realm `main`
+async fn start_issuer() -> Arc<MockIssuer> {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let mock = Arc::new(MockIssuer {
+        issuer: format!("http://{}/realms/main", listener.local_addr().unwrap()),
+        published_keys: Mutex::new(Vec::new()),
+        codes: Mutex::new(HashMap::new()),
+        code_verifiers: Mutex::new(Vec::new()),
+    });
+
+    let data = web::Data::from(mock.clone());
+    let server = HttpServer::new(move || {
+        App::new()
+            .app_data(data.clone())
+            .route("/realms/{realm}/.well-known/openid-configuration", web::get().to(discovery))
+            .route("/realms/main/jwks", web::get().to(jwks))
+            .route("/realms/main/token", web::post().to(token))
+    })
+    .workers(1)
+    .listen(listener)
+    .unwrap()
+    .run();
+    actix_web::rt::spawn(server);
+
+    mock
+}
+
+fn authorization_params(authorization_url: &str) -> HashMap<String, String> {
+    let (_, query) = authorization_url.split_once('?').unwrap();
+    serde_urlencoded::from_str(query).unwrap()
+}
+
+#[actix_web::test]
+async fn discovery_builds_the_authorization_url() {
+    let mock = start_issuer().await;
+    let provider = mock.provider();
+
+    let url = provider.authorization_url("state-1", "nonce-1", "challenge-1").await.unwrap();
+    assert!(url.starts_with(&format!("{}/authorize?", mock.issuer)));
+
+    let params = authorization_params(&url);
+    assert_eq!(params["response_type"], "code");
+    assert_eq!(params["client_id"], CLIENT_ID);
+    assert_eq!(params["redirect_uri"], REDIRECT_URI);
+    assert_eq!(params["state"], "state-1");
+    assert_eq!(params["nonce"], "nonce-1");
+    assert_eq!(params["code_challenge"], "challenge-1");
+    assert_eq!(params["code_challenge_method"], "S256");
+}
+
+#[actix_web::test]
+async fn discovery_document_for_another_issuer_is_rejected() {
+    let mock = start_issuer().await;
+    let provider = HttpOidcProvider::new(
+        mock.issuer.replace("/realms/main", "/realms/other"),
+        CLIENT_ID.to_string(),
+        CLIENT_SECRET.to_string(),
+        REDIRECT_URI.to_string(),
+    );
+
+    let result = provider.authorization_url("state", "nonce", "challenge").await;
+    assert!(matches!(result, Err(OidcError::Provider(_))));
+}
+
+#[actix_web::test]
+async fn id_token_is_verified_with_the_key_named_by_kid() {
+    let mock = start_issuer().await;
+    let first = SigningKey::new("key-1");
+    let second = SigningKey::new("key-2");
+    mock.publish(&first);
+    mock.publish(&second);
+    let provider = mock.provider();
+    let claims = mock.claims("nonce", "subject-1", "sso@test.example", true);
+
+    let identity = provider.validate_id_token(&second.id_token(&claims), "nonce").await.unwrap();
+    assert_eq!(identity.subject, "subject-1");
+    assert_eq!(identity.email.as_deref(), Some("sso@test.example"));
+    assert!(identity.email_verified);
+    assert_eq!(identity.name.as_deref(), Some("SSO User"));
+
+    // Assinado com a primeira chave, mas apontando para a segunda
+    let mismatched = first.sign("key-2", &claims);
+    assert!(matches!(provider.validate_id_token(&mismatched, "nonce").await, Err(OidcError::InvalidIdToken(_))));
+
+    // Chave que o provedor não publica
+    let unknown = SigningKey::new("key-3");
+    assert!(matches!(provider.validate_id_token(&unknown.id_token(&claims), "nonce").await, Err(OidcError::InvalidIdToken(_))));
+}
+
+#[actix_web::test]
+async fn jwks_is_reloaded_when_the_provider_rotates_keys() {
+    let mock = start_issuer().await;
+    let current = SigningKey::new("current");
+    mock.publish(&current);
+    let provider = mock.provider();
+    let claims = mock.claims("nonce", "subject-1", "sso@test.example", true);
+
+    provider.validate_id_token(&current.id_token(&claims), "nonce").await.unwrap();
+
+    // A chave nova não está no JWKS em cache
+    let rotated = SigningKey::new("rotated");
+    mock.publish(&rotated);
+    provider.validate_id_token(&rotated.id_token(&claims), "nonce").await.unwrap();
+}
+
+#[actix_web::test]
+async fn id_token_with_another_nonce_or_audience_is_rejected() {
+    let mock = start_issuer().await;
+    let key = SigningKey::new("key-1");
+    mock.publish(&key);
+    let provider = mock.provider();
+
+    let claims = mock.claims("nonce", "subject-1", "sso@test.example", true);
+    let result = provider.validate_id_token(&key.id_token(&claims), "another-nonce").await;
+    assert!(matches!(result, Err(OidcError::InvalidIdToken(_))));
+
+    let mut claims = mock.claims("nonce", "subject-1", "sso@test.example", true);
+    claims["aud"] = json!("another-client");
+    let result = provider.validate_id_token(&key.id_token(&claims), "nonce").await;
+    assert!(matches!(result, Err(OidcError::InvalidIdToken(_))));
+}
+
+#[actix_web::test]
+async fn code_is_exchanged_once_for_the_id_token() {
+    let mock = start_issuer().await;
+    let key = SigningKey::new("key-1");
+    let id_token = key.id_token(&mock.claims("nonce", "subject-1", "sso@test.example", true));
+    let code = mock.issue_code(id_token.clone());
+    let provider = mock.provider();
+
+    assert_eq!(provider.exchange_code(&code, "verifier-1").await.unwrap(), id_token);
+    assert_eq!(*mock.code_verifiers.lock().unwrap(), vec!["verifier-1".to_string()]);
+
+    let replayed = provider.exchange_code(&code, "verifier-1").await;
+    assert!(matches!(replayed, Err(OidcError::Provider(message)) if message.starts_with("invalid_grant")));
+}
+
+fn oidc_service(pool: &PgPool, mock: &MockIssuer) -> OidcService {
+    let config = common::test_config();
+    OidcService::new(
+        web::Data::new(PgIdentityRepository::new(pool.clone())),
+        web::Data::new(PgUserRepository::new(pool.clone())),
+        common::auth_service(pool, &config),
+        Arc::new(Argon2PasswordEncryptor::new()),
+        Some(Box::new(mock.provider())),
+    )
+}
+
+// Inicia o login pelo serviço e devolve o state e o nonce que foram ao provedor
+async fn start_login(service: &OidcService) -> (String, String) {
+    let data = common::response_data(service.start_login().await.unwrap()).await;
+    let mut params = authorization_params(data["authorization_url"].as_str().unwrap());
+    (params.remove("state").unwrap(), params.remove("nonce").unwrap())
+}
+
+#[actix_web::test]
+#[ignore = "requires TEST_DATABASE_URL"]
+async fn sso_login_links_an_existing_user_by_verified_email() {
+    let pool = common::test_pool().await;
+    let mock = start_issuer().await;
+    let key = SigningKey::new("key-1");
+    mock.publish(&key);
+    let service = oidc_service(&pool, &mock);
+    let email = format!("oidc-{}@test.example", Uuid::new_v4().simple());
+    let user_id = common::create_user(&pool, &email, "not-a-password-hash").await;
+    let subject = Uuid::new_v4().to_string();
+
+    CURRENT_TENANT.scope(TenantScope::All, async {
+        // Email não verificado pelo provedor não vincula a conta
+        let (state, nonce) = start_login(&service).await;
+        let code = mock.issue_code(key.id_token(&mock.claims(&nonce, &subject, &email, false)));
+        let unverified = service.callback(OidcCallback { code, state }).await;
+        assert!(matches!(unverified, Err(AppError::Unauthorized(_))));
+
+        // Email verificado, com outra caixa: vincula ao usuário existente
+        let (state, nonce) = start_login(&service).await;
+        let code = mock.issue_code(key.id_token(&mock.claims(&nonce, &subject, &email.to_uppercase(), true)));
+        let callback = || OidcCallback { code: code.clone(), state: state.clone() };
+        let logged_in = common::response_data(service.callback(callback()).await.unwrap()).await;
+        assert_eq!(logged_in["id"], user_id.to_string());
+        assert!(logged_in["token"].as_str().is_some_and(|token| !token.is_empty()));
+
+        // O state é de uso único
+        let replayed = service.callback(callback()).await;
+        assert!(matches!(replayed, Err(AppError::Unauthorized(_))));
+
+        // State que o serviço nunca emitiu
+        let code = mock.issue_code(key.id_token(&mock.claims(&nonce, &subject, &email, true)));
+        let unknown = service.callback(OidcCallback { code, state: "unknown-state".to_string() }).await;
+        assert!(matches!(unknown, Err(AppError::Unauthorized(_))));
+
+        // Id token emitido para outro login
+        let (state, _) = start_login(&service).await;
+        let code = mock.issue_code(key.id_token(&mock.claims(&nonce, &subject, &email, true)));
+        let wrong_nonce = service.callback(OidcCallback { code, state }).await;
+        assert!(matches!(wrong_nonce, Err(AppError::Unauthorized(_))));
+
+        // Depois do vínculo o subject basta, mesmo que o provedor mude o email
+        let (state, nonce) = start_login(&service).await;
+        let code = mock.issue_code(key.id_token(&mock.claims(&nonce, &subject, "changed@test.example", false)));
+        let logged_in = common::response_data(service.callback(OidcCallback { code, state }).await.unwrap()).await;
+        assert_eq!(logged_in["id"], user_id.to_string());
+
+        let identities = common::response_data(service.find_identities(user_id).await.unwrap()).await;
+        assert_eq!(identities.as_array().unwrap().len(), 1);
+        assert_eq!(identities[0]["issuer"], mock.issuer);
+        assert_eq!(identities[0]["subject"], subject);
+        let identity_id: Uuid = serde_json::from_value(identities[0]["id"].clone()).unwrap();
+
+        // Desvincular grava a auditoria; a segunda tentativa não encontra a identidade
+        service.unlink_identity(user_id, identity_id).await.unwrap();
+        let audited: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM audit_log WHERE action = $1 AND resource_id = $2")
+            .bind(AUDIT_IDENTITY_UNLINKED)
+            .bind(identity_id)
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(audited, 1);
+        let missing = service.unlink_identity(user_id, identity_id).await;
+        assert!(matches!(missing, Err(AppError::NotFound(_))));
+    }).await;
+
+    common::delete_user(&pool, user_id).await;
+}
+
+#[actix_web::test]
+#[ignore = "requires TEST_DATABASE_URL"]
+async fn sso_login_creates_a_user_for_an_unknown_verified_email() {
+    let pool = common::test_pool().await;
+    let mock = start_issuer().await;
+    let key = SigningKey::new("key-1");
+    mock.publish(&key);
+    let service = oidc_service(&pool, &mock);
+    let email = format!("oidc-new-{}@test.example", Uuid::new_v4().simple());
+
+    let user_id = CURRENT_TENANT.scope(TenantScope::All, async {
+        let (state, nonce) = start_login(&service).await;
+        let code = mock.issue_code(key.id_token(&mock.claims(&nonce, &Uuid::new_v4().to_string(), &email, true)));
+        let logged_in = common::response_data(service.callback(OidcCallback { code, state }).await.unwrap()).await;
+        assert_eq!(logged_in["email"], email);
+        assert_eq!(logged_in["full_name"], "SSO User");
+        serde_json::from_value::<Uuid>(logged_in["id"].clone()).unwrap()
+    }).await;
+
+    // A senha aleatória é um hash argon2 que ninguém conhece
+    let password: String = sqlx::query_scalar("SELECT password FROM users WHERE id = $1")
+        .bind(user_id)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert!(password.starts_with("$argon2"));
+
+    common::delete_user(&pool, user_id).await;
+}
@@ -0,0 +1,275 @@
+// Cerimônias WebAuthn com um autenticador em software (chave P-256 gerada no teste).
+// Os testes do verificador rodam sempre; o fluxo completo pelo AuthService precisa de um banco com as
+// migrações aplicadas em TEST_DATABASE_URL: `cargo test -- --ignored`.
+mod common;
+
+use ciborium::value::Value;
+use ring::digest::{digest, SHA256};
+use ring::rand::{SecureRandom, SystemRandom};
+use ring::signature::{EcdsaKeyPair, KeyPair, ECDSA_P256_SHA256_ASN1_SIGNING};
+use rust_api_architecture_model::adapters::passkey_verifier::{
+    encode_base64url, PasskeyError, PasskeyVerifierPort, WebAuthnVerifier,
+};
+use rust_api_architecture_model::config::tenant::{TenantScope, CURRENT_TENANT};
+use rust_api_architecture_model::core::entities::passkey::{PasskeyLogin, PasskeyLoginOptionsRequest, PasskeyRegistration};
+use rust_api_architecture_model::utils::errors::AppError;
+use uuid::Uuid;
+use common::{ORIGIN, RP_ID};
+
+const FLAGS_ASSERTION: u8 = 0x05; // UP | UV
+const FLAGS_REGISTRATION: u8 = 0x45; // UP | UV | AT
+
+struct SoftwareAuthenticator {
+    key_pair: EcdsaKeyPair,
+    credential_id: Vec<u8>,
+    sign_count: u32,
+}
+
+struct Assertion {
+    client_data_json: Vec<u8>,
+    authenticator_data: Vec<u8>,
+    signature: Vec<u8>,
+}
+
+impl SoftwareAuthenticator {
+    fn new() -> Self {
+        let rng = SystemRandom::new();
+        let pkcs8 = EcdsaKeyPair::generate_pkcs8(&ECDSA_P256_SHA256_ASN1_SIGNING, &rng).unwrap();
+        let key_pair = EcdsaKeyPair::from_pkcs8(&ECDSA_P256_SHA256_ASN1_SIGNING, pkcs8.as_ref(), &rng).unwrap();
+        let mut credential_id = vec![0u8; 32];
+        rng.fill(&mut credential_id).unwrap();
+        Self { key_pair, credential_id, sign_count: 0 }
+    }
+
+    fn public_key(&self) -> &[u8] {
+        self.key_pair.public_key().as_ref()
+    }
+
+    fn credential_id(&self) -> String {
+        encode_base64url(&self.credential_id)
+    }
+
+    fn client_data(kind: &str, challenge: &str, origin: &str) -> Vec<u8> {
+        serde_json::json!({ "type": kind, "challenge": challenge, "origin": origin, "crossOrigin": false })
+            .to_string()
+            .into_bytes()
+    }
+
+    // rpIdHash (32) | flags (1) | signCount (4)
+    fn authenticator_data(&self, flags: u8, sign_count: u32) -> Vec<u8> {
+        let mut data = digest(&SHA256, RP_ID.as_bytes()).as_ref().to_vec();
+        data.push(flags);
+        data.extend_from_slice(&sign_count.to_be_bytes());
+        data
+    }
+
+    // Chave COSE EC2: kty=2, alg=ES256, crv=P-256, x, y
+    fn cose_public_key(&self) -> Vec<u8> {
+        let point = self.public_key();
+        let key = Value::Map(vec![
+            (Value::Integer(1.into()), Value::Integer(2.into())),
+            (Value::Integer(3.into()), Value::Integer((-7).into())),
+            (Value::Integer((-1).into()), Value::Integer(1.into())),
+            (Value::Integer((-2).into()), Value::Bytes(point[1..33].to_vec())),
+            (Value::Integer((-3).into()), Value::Bytes(point[33..65].to_vec())),
+        ]);
+        let mut encoded = Vec::new();
+        ciborium::ser::into_writer(&key, &mut encoded).unwrap();
+        encoded
+    }
+
+    // Retorna (clientDataJSON, attestationObject) com attestation "none"
+    fn register(&self, challenge: &str, origin: &str) -> (Vec<u8>, Vec<u8>) {
+        let mut auth_data = self.authenticator_data(FLAGS_REGISTRATION, self.sign_count);
+        auth_data.extend_from_slice(&[0u8; 16]);
+        auth_data.extend_from_slice(&(self.credential_id.len() as u16).to_be_bytes());
+        auth_data.extend_from_slice(&self.credential_id);
+        auth_data.extend_from_slice(&self.cose_public_key());
+
+        let attestation = Value::Map(vec![
+            (Value::Text("fmt".into()), Value::Text("none".into())),
+            (Value::Text("attStmt".into()), Value::Map(Vec::new())),
+            (Value::Text("authData".into()), Value::Bytes(auth_data)),
+        ]);
+        let mut attestation_object = Vec::new();
+        ciborium::ser::into_writer(&attestation, &mut attestation_object).unwrap();
+
+        (Self::client_data("webauthn.create", challenge, origin), attestation_object)
+    }
+
+    // Assina authenticatorData | SHA-256(clientDataJSON) com o contador informado
+    fn assert_with_count(&self, challenge: &str, origin: &str, sign_count: u32) -> Assertion {
+        let client_data_json = Self::client_data("webauthn.get", challenge, origin);
+        let authenticator_data = self.authenticator_data(FLAGS_ASSERTION, sign_count);
+
+        let mut signed = authenticator_data.clone();
+        signed.extend_from_slice(digest(&SHA256, &client_data_json).as_ref());
+        let signature = self.key_pair.sign(&SystemRandom::new(), &signed).unwrap().as_ref().to_vec();
+
+        Assertion { client_data_json, authenticator_data, signature }
+    }
+
+    fn assert(&mut self, challenge: &str, origin: &str) -> Assertion {
+        self.sign_count += 1;
+        self.assert_with_count(challenge, origin, self.sign_count)
+    }
+}
+
+fn verifier() -> WebAuthnVerifier {
+    WebAuthnVerifier::new(RP_ID.to_string(), ORIGIN.to_string())
+}
+
+fn verify(verifier: &WebAuthnVerifier, challenge: &str, public_key: &[u8], stored: u32, assertion: &Assertion) -> Result<u32, PasskeyError> {
+    verifier.verify_assertion(
+        challenge,
+        public_key,
+        stored,
+        &assertion.client_data_json,
+        &assertion.authenticator_data,
+        &assertion.signature,
+    )
+}
+
+#[test]
+fn registration_and_assertion_are_verified() {
+    let verifier = verifier();
+    let mut authenticator = SoftwareAuthenticator::new();
+
+    let challenge = verifier.generate_challenge().unwrap();
+    let (client_data_json, attestation_object) = authenticator.register(&challenge, ORIGIN);
+    let registered = verifier.verify_registration(&challenge, &client_data_json, &attestation_object).unwrap();
+    assert_eq!(registered.credential_id, authenticator.credential_id);
+    assert_eq!(registered.public_key, authenticator.public_key());
+    assert_eq!(registered.sign_count, 0);
+
+    let challenge = verifier.generate_challenge().unwrap();
+    let assertion = authenticator.assert(&challenge, ORIGIN);
+    assert_eq!(verify(&verifier, &challenge, &registered.public_key, registered.sign_count, &assertion).unwrap(), 1);
+}
+
+#[test]
+fn wrong_origin_is_rejected() {
+    let verifier = verifier();
+    let mut authenticator = SoftwareAuthenticator::new();
+
+    let challenge = verifier.generate_challenge().unwrap();
+    let (client_data_json, attestation_object) = authenticator.register(&challenge, "https://evil.example");
+    let registration = verifier.verify_registration(&challenge, &client_data_json, &attestation_object);
+    assert!(matches!(registration, Err(PasskeyError::ClientDataMismatch("origin"))));
+
+    let assertion = authenticator.assert(&challenge, "https://evil.example");
+    let result = verify(&verifier, &challenge, authenticator.public_key(), 0, &assertion);
+    assert!(matches!(result, Err(PasskeyError::ClientDataMismatch("origin"))));
+}
+
+#[test]
+fn assertion_for_another_challenge_is_rejected() {
+    let verifier = verifier();
+    let mut authenticator = SoftwareAuthenticator::new();
+
+    let issued = verifier.generate_challenge().unwrap();
+    let assertion = authenticator.assert(&verifier.generate_challenge().unwrap(), ORIGIN);
+    let result = verify(&verifier, &issued, authenticator.public_key(), 0, &assertion);
+    assert!(matches!(result, Err(PasskeyError::ClientDataMismatch("challenge"))));
+}
+
+#[test]
+fn sign_count_that_does_not_increase_is_rejected() {
+    let verifier = verifier();
+    let authenticator = SoftwareAuthenticator::new();
+    let challenge = verifier.generate_challenge().unwrap();
+
+    for sign_count in [5, 4] {
+        let assertion = authenticator.assert_with_count(&challenge, ORIGIN, sign_count);
+        let result = verify(&verifier, &challenge, authenticator.public_key(), 5, &assertion);
+        assert!(matches!(result, Err(PasskeyError::CounterRegression)));
+    }
+
+    let assertion = authenticator.assert_with_count(&challenge, ORIGIN, 6);
+    assert_eq!(verify(&verifier, &challenge, authenticator.public_key(), 5, &assertion).unwrap(), 6);
+}
+
+#[test]
+fn signature_from_another_key_is_rejected() {
+    let verifier = verifier();
+    let mut authenticator = SoftwareAuthenticator::new();
+    let other = SoftwareAuthenticator::new();
+
+    let challenge = verifier.generate_challenge().unwrap();
+    let assertion = authenticator.assert(&challenge, ORIGIN);
+    let result = verify(&verifier, &challenge, other.public_key(), 0, &assertion);
+    assert!(matches!(result, Err(PasskeyError::InvalidSignature)));
+}
+
+fn login_payload(challenge_id: Uuid, authenticator: &SoftwareAuthenticator, assertion: &Assertion) -> PasskeyLogin {
+    PasskeyLogin {
+        challenge_id,
+        credential_id: authenticator.credential_id(),
+        client_data_json: encode_base64url(&assertion.client_data_json),
+        authenticator_data: encode_base64url(&assertion.authenticator_data),
+        signature: encode_base64url(&assertion.signature),
+    }
+}
+
+#[actix_web::test]
+#[ignore = "requires TEST_DATABASE_URL"]
+async fn passkey_register_and_login_through_auth_service() {
+    let pool = common::test_pool().await;
+    let config = common::test_config();
+    let service = common::auth_service(&pool, &config);
+    let email = format!("passkey-{}@test.example", Uuid::new_v4().simple());
+    let user_id = common::create_user(&pool, &email, "not-a-password-hash").await;
+    let mut authenticator = SoftwareAuthenticator::new();
+
+    // Rotas autenticadas de um usuário sem organização e rotas públicas declaram acesso a todas as organizações
+    CURRENT_TENANT.scope(TenantScope::All, async {
+        // Registro
+        let options = common::response_data(service.passkey_registration_options(user_id).await.unwrap()).await;
+        let challenge_id: Uuid = serde_json::from_value(options["challenge_id"].clone()).unwrap();
+        let challenge = options["challenge"].as_str().unwrap().to_string();
+        let (client_data_json, attestation_object) = authenticator.register(&challenge, ORIGIN);
+        let registration = || PasskeyRegistration {
+            challenge_id,
+            client_data_json: encode_base64url(&client_data_json),
+            attestation_object: encode_base64url(&attestation_object),
+            transports: vec!["internal".to_string()],
+        };
+        let registered = common::response_data(service.register_passkey(user_id, registration()).await.unwrap()).await;
+        assert_eq!(registered["credential_id"], authenticator.credential_id());
+
+        // O desafio do registro é de uso único
+        let replayed = service.register_passkey(user_id, registration()).await;
+        assert!(matches!(replayed, Err(AppError::BadRequest(_))));
+
+        // Login
+        let login_options = || PasskeyLoginOptionsRequest { email: Some(email.clone()) };
+        let options = common::response_data(service.passkey_login_options(login_options()).await.unwrap()).await;
+        assert_eq!(options["allow_credentials"][0]["id"], authenticator.credential_id());
+        let challenge_id: Uuid = serde_json::from_value(options["challenge_id"].clone()).unwrap();
+        let challenge = options["challenge"].as_str().unwrap().to_string();
+        let assertion = authenticator.assert(&challenge, ORIGIN);
+        let logged_in = common::response_data(service.login_with_passkey(login_payload(challenge_id, &authenticator, &assertion)).await.unwrap()).await;
+        assert_eq!(logged_in["id"], user_id.to_string());
+        assert!(logged_in["token"].as_str().is_some_and(|token| !token.is_empty()));
+
+        // O mesmo desafio não serve para um segundo login
+        let replayed = service.login_with_passkey(login_payload(challenge_id, &authenticator, &assertion)).await;
+        assert!(matches!(replayed, Err(AppError::Unauthorized(_))));
+
+        // Origem diferente da configurada
+        let options = common::response_data(service.passkey_login_options(login_options()).await.unwrap()).await;
+        let challenge_id: Uuid = serde_json::from_value(options["challenge_id"].clone()).unwrap();
+        let assertion = authenticator.assert(options["challenge"].as_str().unwrap(), "https://evil.example");
+        let wrong_origin = service.login_with_passkey(login_payload(challenge_id, &authenticator, &assertion)).await;
+        assert!(matches!(wrong_origin, Err(AppError::Unauthorized(_))));
+
+        // Contador que não avança em relação ao último login aceito (1): autenticador possivelmente clonado
+        let options = common::response_data(service.passkey_login_options(login_options()).await.unwrap()).await;
+        let challenge_id: Uuid = serde_json::from_value(options["challenge_id"].clone()).unwrap();
+        let assertion = authenticator.assert_with_count(options["challenge"].as_str().unwrap(), ORIGIN, 1);
+        let cloned = service.login_with_passkey(login_payload(challenge_id, &authenticator, &assertion)).await;
+        assert!(matches!(cloned, Err(AppError::Unauthorized(_))));
+    }).await;
+
+    common::delete_user(&pool, user_id).await;
+}
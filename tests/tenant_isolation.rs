@@ -0,0 +1,231 @@
+// Teste de integração do isolamento por organização (RLS).
+// Requer um banco com as migrações aplicadas em TEST_DATABASE_URL: `cargo test -- --ignored`.
+// Os repositórios rodam com um papel sem privilégios, já que superusuários ignoram RLS.
+mod common;
+
+use actix_web::http::StatusCode;
+use actix_web::{test, web, App};
+use rust_api_architecture_model::adapters::token_hasher::{JwtTokenGenerator, TokenGeneratorPort};
+use rust_api_architecture_model::config::tenant::{TenantScope, CURRENT_TENANT};
+use rust_api_architecture_model::core::contracts::repository::users::UserRepository;
+use rust_api_architecture_model::core::entities::user::{SortDirection, UserListParams, UserSortField, ROLE_ADMIN, ROLE_USER};
+use rust_api_architecture_model::middleware::auth::AuthMiddleware;
+use rust_api_architecture_model::repositories::organization_repository::PgOrganizationRepository;
+use rust_api_architecture_model::repositories::user_repository::PgUserRepository;
+use rust_api_architecture_model::routes::config::base_routes::configure_routes;
+use sqlx::postgres::PgPoolOptions;
+use sqlx::{Executor, PgPool};
+use uuid::Uuid;
+
+const APP_ROLE: &str = "tenant_isolation_test_app";
+
+struct Tenant {
+    organization_id: Uuid,
+    user_id: Uuid,
+}
+
+async fn create_tenant(admin: &PgPool, name: &str) -> Tenant {
+    let organization_id = Uuid::new_v4();
+    let user_id = Uuid::new_v4();
+    let now = chrono::Utc::now().naive_utc();
+
+    sqlx::query("INSERT INTO organizations (id, name, slug, created_at, updated_at) VALUES ($1, $2, $3, $4, $4)")
+        .bind(organization_id)
+        .bind(name)
+        .bind(format!("{}-{}", name, organization_id.simple()))
+        .bind(now)
+        .execute(admin)
+        .await
+        .unwrap();
+    sqlx::query("INSERT INTO users (id, full_name, email, password, created_at, updated_at) VALUES ($1, $2, $3, 'x', $4, $4)")
+        .bind(user_id)
+        .bind(name)
+        .bind(format!("{}@{}.test", user_id.simple(), name))
+        .bind(now)
+        .execute(admin)
+        .await
+        .unwrap();
+    sqlx::query("INSERT INTO organization_memberships (organization_id, user_id, role, created_at) VALUES ($1, $2, 'owner', $3)")
+        .bind(organization_id)
+        .bind(user_id)
+        .bind(now)
+        .execute(admin)
+        .await
+        .unwrap();
+
+    Tenant { organization_id, user_id }
+}
+
+// Pool que executa as consultas com um papel comum, sujeito às políticas
+async fn app_pool() -> PgPool {
+    let database_url = std::env::var("TEST_DATABASE_URL").expect("TEST_DATABASE_URL must point to a migrated database");
+
+    let admin = PgPoolOptions::new().max_connections(1).connect(&database_url).await.unwrap();
+    admin
+        .execute(format!(
+            "DO $$ BEGIN
+                IF NOT EXISTS (SELECT 1 FROM pg_roles WHERE rolname = '{role}') THEN CREATE ROLE {role} NOLOGIN; END IF;
+            END $$;
+            GRANT USAGE ON SCHEMA public TO {role};
+            GRANT SELECT, INSERT, UPDATE, DELETE ON ALL TABLES IN SCHEMA public TO {role};",
+            role = APP_ROLE
+        ).as_str())
+        .await
+        .unwrap();
+
+    PgPoolOptions::new()
+        .max_connections(2)
+        .after_connect(|conn, _| Box::pin(async move {
+            conn.execute(format!("SET ROLE {}", APP_ROLE).as_str()).await?;
+            Ok(())
+        }))
+        .connect(&database_url)
+        .await
+        .unwrap()
+}
+
+fn list_params() -> UserListParams {
+    UserListParams {
+        limit: 1000,
+        sort: UserSortField::CreatedAt,
+        direction: SortDirection::Desc,
+        after: None,
+        email_contains: None,
+        created_from: None,
+        created_to: None,
+        attributes: None,
+        only_deleted: false,
+        // Sem filtro da aplicação: apenas a RLS separa as organizações
+        organization_id: None,
+    }
+}
+
+#[actix_web::test]
+#[ignore = "requires TEST_DATABASE_URL"]
+async fn cross_tenant_reads_return_nothing() {
+    let admin = common::test_pool().await;
+    let repo = PgUserRepository::new(app_pool().await);
+
+    let a = create_tenant(&admin, "tenant-a").await;
+    let b = create_tenant(&admin, "tenant-b").await;
+
+    let own = CURRENT_TENANT.scope(TenantScope::Organization(a.organization_id), repo.find_user_by_id(a.user_id)).await;
+    let other = CURRENT_TENANT.scope(TenantScope::Organization(a.organization_id), repo.find_user_by_id(b.user_id)).await;
+    let listed = CURRENT_TENANT.scope(TenantScope::Organization(a.organization_id), repo.find_all_users(&list_params())).await.unwrap();
+    let other_status = CURRENT_TENANT.scope(TenantScope::Organization(a.organization_id), repo.find_user_status(b.user_id)).await.unwrap();
+    let other_deleted = CURRENT_TENANT.scope(TenantScope::Organization(a.organization_id), repo.delete_user(b.user_id, None, Vec::new())).await.unwrap();
+    // Sem escopo declarado as políticas não liberam nenhuma linha
+    let unscoped = repo.find_user_by_id(b.user_id).await;
+    let all_organizations = CURRENT_TENANT.scope(TenantScope::All, repo.find_user_by_id(b.user_id)).await;
+
+    sqlx::query("DELETE FROM organizations WHERE id = ANY($1)")
+        .bind(vec![a.organization_id, b.organization_id])
+        .execute(&admin)
+        .await
+        .unwrap();
+    sqlx::query("DELETE FROM users WHERE id = ANY($1)")
+        .bind(vec![a.user_id, b.user_id])
+        .execute(&admin)
+        .await
+        .unwrap();
+
+    assert_eq!(own.unwrap().id, a.user_id);
+    assert!(matches!(other, Err(sqlx::Error::RowNotFound)));
+    assert!(listed.iter().any(|user| user.id == a.user_id));
+    assert!(listed.iter().all(|user| user.id != b.user_id));
+    assert_eq!(other_status, None);
+    assert!(!other_deleted);
+    assert!(matches!(unscoped, Err(sqlx::Error::RowNotFound)));
+    assert_eq!(all_organizations.unwrap().id, b.user_id);
+}
+
+#[actix_web::test]
+#[ignore = "requires TEST_DATABASE_URL"]
+async fn tokens_without_organization_reach_only_their_own_account() {
+    let admin = common::test_pool().await;
+    let pool = app_pool().await;
+    let config = common::test_config();
+    let app = test::init_service(
+        App::new()
+            .wrap(AuthMiddleware)
+            .app_data(web::Data::new(PgUserRepository::new(pool.clone())))
+            .app_data(web::Data::new(PgOrganizationRepository::new(pool.clone())))
+            .app_data(common::user_service(&pool, &config))
+            .app_data(web::Data::new(config.clone()))
+            .configure(configure_routes),
+    )
+    .await;
+
+    let a = create_tenant(&admin, "tenant-a").await;
+    let b = create_tenant(&admin, "tenant-b").await;
+    let outsider = common::create_user(&admin, &format!("outsider-{}@test.example", Uuid::new_v4().simple()), "x").await;
+    let global_admin = common::create_user(&admin, &format!("admin-{}@test.example", Uuid::new_v4().simple()), "x").await;
+    sqlx::query("UPDATE users SET role = $2 WHERE id = $1")
+        .bind(global_admin)
+        .bind(ROLE_ADMIN)
+        .execute(&admin)
+        .await
+        .unwrap();
+
+    let token = |user_id: Uuid, role: &str, organization_id: Option<Uuid>| {
+        JwtTokenGenerator::new()
+            .generate_token(user_id.to_string(), "Test".into(), "test@test.example".into(), role.into(), organization_id, &config.jwt_secret)
+            .unwrap()
+    };
+    let outsider_token = token(outsider, ROLE_USER, None);
+    let member_token = token(a.user_id, ROLE_USER, Some(a.organization_id));
+    let admin_token = token(global_admin, ROLE_ADMIN, None);
+
+    let get = |user_id: Uuid, token: &str| {
+        test::TestRequest::get()
+            .uri(&format!("/api/v1/users/{}", user_id))
+            .insert_header(("api_key", config.api_key.as_str()))
+            .insert_header(("Authorization", format!("Bearer {}", token)))
+            .to_request()
+    };
+
+    // Sem organização e sem ser administrador: apenas a própria conta
+    let own = test::call_service(&app, get(outsider, &outsider_token)).await.status();
+    let other = test::call_service(&app, get(b.user_id, &outsider_token)).await.status();
+    let patched = test::call_service(
+        &app,
+        test::TestRequest::patch()
+            .uri(&format!("/api/v1/users/{}", b.user_id))
+            .insert_header(("api_key", config.api_key.as_str()))
+            .insert_header(("Authorization", format!("Bearer {}", outsider_token)))
+            .set_json(serde_json::json!({ "full_name": "Pwned" }))
+            .to_request(),
+    )
+    .await
+    .status();
+    // Membro de uma organização: apenas a organização ativa
+    let member_own = test::call_service(&app, get(a.user_id, &member_token)).await.status();
+    let member_other = test::call_service(&app, get(b.user_id, &member_token)).await.status();
+    // Administrador global sem organização ativa: todas as organizações
+    let admin_other = test::call_service(&app, get(b.user_id, &admin_token)).await.status();
+
+    let b_name: String = sqlx::query_scalar("SELECT full_name FROM users WHERE id = $1")
+        .bind(b.user_id)
+        .fetch_one(&admin)
+        .await
+        .unwrap();
+
+    sqlx::query("DELETE FROM organizations WHERE id = ANY($1)")
+        .bind(vec![a.organization_id, b.organization_id])
+        .execute(&admin)
+        .await
+        .unwrap();
+    sqlx::query("DELETE FROM users WHERE id = ANY($1)")
+        .bind(vec![a.user_id, b.user_id, outsider, global_admin])
+        .execute(&admin)
+        .await
+        .unwrap();
+
+    assert_eq!(own, StatusCode::OK);
+    assert_eq!(other, StatusCode::NOT_FOUND);
+    assert_eq!(patched, StatusCode::NOT_FOUND);
+    assert_eq!(b_name, "tenant-b");
+    assert_eq!(member_own, StatusCode::OK);
+    assert_eq!(member_other, StatusCode::NOT_FOUND);
+    assert_eq!(admin_other, StatusCode::OK);
+}